@@ -212,6 +212,8 @@ impl BufferPoolManager {
     // 删除缓冲池中的页
     pub fn delete_page(&mut self, page_id: PageId) -> bool {
         if !self.page_table.contains_key(&page_id) {
+            // 不在缓冲池中，只需从磁盘上删除
+            self.disk_manager.deallocate_page(page_id).unwrap();
             return true;
         }
         let frame_id = self.page_table[&page_id];
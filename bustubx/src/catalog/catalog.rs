@@ -2,14 +2,19 @@ use std::{collections::HashMap, sync::atomic::AtomicU32};
 use std::{result, vec};
 
 use crate::buffer::TABLE_HEAP_BUFFER_POOL_SIZE;
-use crate::catalog::SchemaRef;
+use crate::catalog::{Column, SchemaRef};
+use crate::common::{ScalarValue, TableReference};
+use crate::storage::Tuple;
 use crate::{
     buffer::BufferPoolManager,
+    common::rid::Rid,
     storage::{
         index::{BPlusTreeIndex, IndexMetadata},
         TableHeap,
     },
 };
+use crate::{BustubxError, BustubxResult};
+use std::sync::Arc;
 
 use super::Schema;
 
@@ -122,20 +127,12 @@ impl Catalog {
         let tuple_schema = table_info.schema.clone();
         let key_schema = tuple_schema.project(&key_attrs).unwrap();
 
-        let index_metadata = IndexMetadata::new(
+        let b_plus_tree_index = self.new_index(
             index_name.clone(),
             table_name.clone(),
-            tuple_schema.clone(),
+            tuple_schema,
             key_attrs,
         );
-        // one buffer pool manager for one index
-        let buffer_pool_manager = BufferPoolManager::new(
-            TABLE_HEAP_BUFFER_POOL_SIZE,
-            self.buffer_pool_manager.disk_manager.clone(),
-            3,
-        );
-        // TODO compute leaf_max_size and internal_max_size
-        let b_plus_tree_index = BPlusTreeIndex::new(index_metadata, buffer_pool_manager, 10, 10);
 
         let index_oid = self
             .next_index_oid
@@ -162,6 +159,24 @@ impl Catalog {
         self.indexes.get(&index_oid).unwrap()
     }
 
+    fn new_index(
+        &self,
+        index_name: String,
+        table_name: String,
+        tuple_schema: SchemaRef,
+        key_attrs: Vec<usize>,
+    ) -> BPlusTreeIndex {
+        let index_metadata = IndexMetadata::new(index_name, table_name, tuple_schema, key_attrs);
+        // one buffer pool manager for one index
+        let buffer_pool_manager = BufferPoolManager::new(
+            TABLE_HEAP_BUFFER_POOL_SIZE,
+            self.buffer_pool_manager.disk_manager.clone(),
+            3,
+        );
+        // TODO compute leaf_max_size and internal_max_size
+        BPlusTreeIndex::new(index_metadata, buffer_pool_manager, 10, 10)
+    }
+
     pub fn get_index_by_oid(&self, oid: IndexOid) -> Option<&IndexInfo> {
         self.indexes.get(&oid)
     }
@@ -184,6 +199,248 @@ impl Catalog {
             })
             .unwrap_or(vec![])
     }
+
+    pub fn get_mut_table_indexes(&mut self, table_name: &str) -> Vec<&mut IndexInfo> {
+        let index_oids = self
+            .index_names
+            .get(table_name)
+            .map(|index_names| index_names.values().copied().collect::<Vec<IndexOid>>())
+            .unwrap_or_default();
+        self.indexes
+            .iter_mut()
+            .filter(|(index_oid, _)| index_oids.contains(index_oid))
+            .map(|(_, index_info)| index_info)
+            .collect()
+    }
+
+    // 删除表及其所有索引，并释放它们的页，表不存在时返回false
+    pub fn drop_table(&mut self, table_name: &str) -> bool {
+        let table_oid = match self.table_names.remove(table_name) {
+            Some(table_oid) => table_oid,
+            None => return false,
+        };
+        let index_oids = self
+            .index_names
+            .remove(table_name)
+            .map(|index_names| index_names.into_values().collect::<Vec<IndexOid>>())
+            .unwrap_or_default();
+        for index_oid in index_oids {
+            if let Some(index_info) = self.indexes.remove(&index_oid) {
+                index_info.index.destroy();
+            }
+        }
+        if let Some(table_info) = self.tables.remove(&table_oid) {
+            table_info.table.destroy();
+        }
+        true
+    }
+
+    // 添加列，已有的行用value填充
+    pub fn add_column(
+        &mut self,
+        table_name: &str,
+        column: Column,
+        value: ScalarValue,
+    ) -> BustubxResult<()> {
+        let mut columns = self.table_columns(table_name)?;
+        columns.push(column);
+        self.rewrite_table(
+            table_name,
+            Arc::new(Schema::new(columns)),
+            |mut row| {
+                row.push(value.clone());
+                row
+            },
+            |key_attrs| Some(key_attrs.to_vec()),
+        )
+    }
+
+    // 删除第index列，包含该列的索引也被删除
+    pub fn drop_column(&mut self, table_name: &str, index: usize) -> BustubxResult<()> {
+        let mut columns = self.table_columns(table_name)?;
+        columns.remove(index);
+        self.rewrite_table(
+            table_name,
+            Arc::new(Schema::new(columns)),
+            |mut row| {
+                row.remove(index);
+                row
+            },
+            |key_attrs| {
+                if key_attrs.contains(&index) {
+                    return None;
+                }
+                Some(
+                    key_attrs
+                        .iter()
+                        .map(|i| if *i > index { i - 1 } else { *i })
+                        .collect(),
+                )
+            },
+        )
+    }
+
+    pub fn rename_column(
+        &mut self,
+        table_name: &str,
+        index: usize,
+        new_name: String,
+    ) -> BustubxResult<()> {
+        let mut columns = self.table_columns(table_name)?;
+        columns[index] = columns[index].clone().with_name(new_name);
+        self.set_table_schema(table_name, Arc::new(Schema::new(columns)))
+    }
+
+    pub fn rename_table(
+        &mut self,
+        table_name: &str,
+        new_table: TableReference,
+    ) -> BustubxResult<()> {
+        let new_name = new_table.table().to_string();
+        if self.table_names.contains_key(&new_name) {
+            return Err(BustubxError::Execution(format!(
+                "table {} already exists",
+                new_name
+            )));
+        }
+        let columns = self
+            .table_columns(table_name)?
+            .into_iter()
+            .map(|col| col.with_relation(Some(new_table.clone())))
+            .collect();
+        self.set_table_schema(table_name, Arc::new(Schema::new(columns)))?;
+
+        let table_oid = self.table_names.remove(table_name).unwrap();
+        self.table_names.insert(new_name.clone(), table_oid);
+        self.tables.get_mut(&table_oid).unwrap().name = new_name.clone();
+        if let Some(index_names) = self.index_names.remove(table_name) {
+            self.index_names.insert(new_name.clone(), index_names);
+        }
+        for index_info in self.get_mut_table_indexes(&new_name) {
+            index_info.table_name = new_name.clone();
+            index_info.index.index_metadata.table_name = new_name.clone();
+        }
+        Ok(())
+    }
+
+    fn table_columns(&self, table_name: &str) -> BustubxResult<Vec<Column>> {
+        self.get_table_by_name(table_name)
+            .map(|info| {
+                info.schema
+                    .columns
+                    .iter()
+                    .map(|col| col.as_ref().clone())
+                    .collect()
+            })
+            .ok_or_else(|| BustubxError::Execution(format!("table {} not found", table_name)))
+    }
+
+    // 只改变schema，不改变存储的数据
+    fn set_table_schema(&mut self, table_name: &str, schema: SchemaRef) -> BustubxResult<()> {
+        let table_info = self
+            .get_mut_table_by_name(table_name)
+            .ok_or_else(|| BustubxError::Execution(format!("table {} not found", table_name)))?;
+        table_info.schema = schema.clone();
+        table_info.table.schema = schema.clone();
+        for index_info in self.get_mut_table_indexes(table_name) {
+            let key_schema = schema.project(&index_info.index.index_metadata.key_attrs)?;
+            index_info.key_schema = key_schema.clone();
+            index_info.index.index_metadata.key_schema = key_schema;
+        }
+        Ok(())
+    }
+
+    // 将所有行经map_row转换后写入新的table heap并释放旧的页，
+    // rid随之改变，因此重建索引，map_key_attrs返回None的索引被删除
+    fn rewrite_table(
+        &mut self,
+        table_name: &str,
+        schema: SchemaRef,
+        map_row: impl Fn(Vec<ScalarValue>) -> Vec<ScalarValue>,
+        map_key_attrs: impl Fn(&[usize]) -> Option<Vec<usize>>,
+    ) -> BustubxResult<()> {
+        let disk_manager = self.buffer_pool_manager.disk_manager.clone();
+        let table_info = self
+            .get_mut_table_by_name(table_name)
+            .ok_or_else(|| BustubxError::Execution(format!("table {} not found", table_name)))?;
+
+        // 先检查所有行，失败时表保持不变
+        let mut rows = vec![];
+        let mut iterator = table_info.table.iter(None, None);
+        while let Some((meta, tuple)) = iterator.next(&mut table_info.table) {
+            if meta.is_deleted {
+                continue;
+            }
+            let row = map_row(tuple.data);
+            for (value, column) in row.iter().zip(schema.columns.iter()) {
+                if value.is_null() && !column.nullable {
+                    return Err(BustubxError::Execution(format!(
+                        "column {} of table {} contains null values",
+                        column.name, table_name
+                    )));
+                }
+            }
+            rows.push((meta, Tuple::new(schema.clone(), row)));
+        }
+
+        let buffer_pool_manager =
+            BufferPoolManager::new(TABLE_HEAP_BUFFER_POOL_SIZE, disk_manager, 3);
+        let mut table_heap = TableHeap::try_new(schema.clone(), buffer_pool_manager);
+        let mut tuples: Vec<(Rid, Tuple)> = vec![];
+        for (meta, tuple) in rows {
+            let rid = table_heap.insert_tuple(&meta, &tuple)?;
+            tuples.push((rid, tuple));
+        }
+        std::mem::replace(&mut table_info.table, table_heap).destroy();
+        table_info.schema = schema.clone();
+
+        let index_oids = self
+            .index_names
+            .get(table_name)
+            .map(|index_names| index_names.values().copied().collect::<Vec<IndexOid>>())
+            .unwrap_or_default();
+        for index_oid in index_oids {
+            let index_info = self.indexes.get(&index_oid).unwrap();
+            let index_name = index_info.name.clone();
+            let Some(key_attrs) = map_key_attrs(&index_info.index.index_metadata.key_attrs) else {
+                self.drop_index(table_name, &index_name);
+                continue;
+            };
+            let mut index = self.new_index(
+                index_name,
+                table_name.to_string(),
+                schema.clone(),
+                key_attrs.clone(),
+            );
+            for (rid, tuple) in tuples.iter() {
+                let key = Tuple::new(
+                    index.index_metadata.key_schema.clone(),
+                    key_attrs.iter().map(|i| tuple.data[*i].clone()).collect(),
+                );
+                index.insert(&key, *rid);
+            }
+            let index_info = self.indexes.get_mut(&index_oid).unwrap();
+            index_info.key_schema = index.index_metadata.key_schema.clone();
+            std::mem::replace(&mut index_info.index, index).destroy();
+        }
+        Ok(())
+    }
+
+    // 删除索引并释放它的页，索引不存在时返回false
+    pub fn drop_index(&mut self, table_name: &str, index_name: &str) -> bool {
+        let index_oid = match self
+            .index_names
+            .get_mut(table_name)
+            .and_then(|index_names| index_names.remove(index_name))
+        {
+            Some(index_oid) => index_oid,
+            None => return false,
+        };
+        if let Some(index_info) = self.indexes.remove(&index_oid) {
+            index_info.index.destroy();
+        }
+        true
+    }
     pub fn get_table_names(&self) -> Vec<String> {
         self.table_names.keys().cloned().collect()
     }
@@ -214,9 +471,10 @@ mod tests {
     use std::{fs::remove_file, sync::Arc};
 
     use crate::{
-        buffer::BufferPoolManager,
+        buffer::{BufferPoolManager, BUSTUBX_PAGE_SIZE},
         catalog::{Column, DataType, Schema},
-        storage::DiskManager,
+        common::{rid::Rid, TableReference},
+        storage::{DiskManager, Tuple, TupleMeta},
     };
 
     #[test]
@@ -314,7 +572,7 @@ mod tests {
         let _ = remove_file(db_path);
 
         let disk_manager = DiskManager::try_new(&db_path).unwrap();
-        let buffer_pool_manager = BufferPoolManager::new(1000, Arc::new(disk_manager),2);
+        let buffer_pool_manager = BufferPoolManager::new(1000, Arc::new(disk_manager), 2);
         let mut catalog = super::Catalog::new(buffer_pool_manager);
 
         let table_name = "test_table1".to_string();
@@ -394,4 +652,177 @@ mod tests {
 
         let _ = remove_file(db_path);
     }
+
+    #[test]
+    pub fn test_catalog_drop_table() {
+        let db_path = "./test_catalog_drop_table.db";
+        let _ = remove_file(db_path);
+
+        let disk_manager = DiskManager::try_new(db_path).unwrap();
+        let buffer_pool_manager = BufferPoolManager::new(1000, Arc::new(disk_manager), 2);
+        let mut catalog = super::Catalog::new(buffer_pool_manager);
+
+        let table_name = "test_table1".to_string();
+        let schema = Arc::new(Schema::new(vec![
+            Column::new("a".to_string(), DataType::Int8, true),
+            Column::new("b".to_string(), DataType::Int16, true),
+        ]));
+        let _ = catalog.create_table(table_name.clone(), schema.clone());
+        let table_info = catalog.get_mut_table_by_name(&table_name).unwrap();
+        let meta = TupleMeta {
+            insert_txn_id: 0,
+            delete_txn_id: 0,
+            is_deleted: false,
+        };
+        table_info
+            .table
+            .insert_tuple(
+                &meta,
+                &Tuple::new(schema.clone(), vec![1i8.into(), 2i16.into()]),
+            )
+            .unwrap();
+        table_info.table.buffer_pool_manager.flush_all_pages();
+        let table_page_id = table_info.table.first_page_id;
+
+        let index_name1 = "test_index1".to_string();
+        let index_name2 = "test_index2".to_string();
+        let _ = catalog.create_index(index_name1.clone(), table_name.clone(), vec![0]);
+        let _ = catalog.create_index(index_name2.clone(), table_name.clone(), vec![1]);
+        let index_info = catalog.get_mut_table_indexes(&table_name).pop().unwrap();
+        let key = Tuple::new(index_info.key_schema.clone(), vec![1i8.into()]);
+        index_info.index.insert(&key, Rid::new(table_page_id, 0));
+        index_info.index.buffer_pool_manager.flush_all_pages();
+        let index_page_id = index_info.index.root_page_id;
+
+        assert!(catalog.drop_index(&table_name, &index_name2));
+        assert!(!catalog.drop_index(&table_name, &index_name2));
+        assert!(catalog
+            .get_index_by_name(&table_name, &index_name2)
+            .is_none());
+        assert_eq!(catalog.get_table_indexes(&table_name).len(), 1);
+
+        assert!(catalog.drop_table(&table_name));
+        assert!(!catalog.drop_table(&table_name));
+        assert!(catalog.get_table_by_name(&table_name).is_none());
+        assert!(catalog
+            .get_index_by_name(&table_name, &index_name1)
+            .is_none());
+        assert!(catalog.tables.is_empty());
+        assert!(catalog.indexes.is_empty());
+
+        // pages of the table and its indexes are given back to the disk manager
+        let disk_manager = catalog.buffer_pool_manager.disk_manager.clone();
+        for page_id in [table_page_id, index_page_id] {
+            assert_eq!(
+                disk_manager.read_page(page_id).unwrap(),
+                [0u8; BUSTUBX_PAGE_SIZE]
+            );
+        }
+
+        let _ = catalog.create_table(table_name.clone(), schema);
+        assert!(catalog.get_table_by_name(&table_name).is_some());
+
+        let _ = remove_file(db_path);
+    }
+
+    #[test]
+    pub fn test_catalog_alter_table() {
+        let db_path = "./test_catalog_alter_table.db";
+        let _ = remove_file(db_path);
+
+        let disk_manager = DiskManager::try_new(db_path).unwrap();
+        let buffer_pool_manager = BufferPoolManager::new(1000, Arc::new(disk_manager), 2);
+        let mut catalog = super::Catalog::new(buffer_pool_manager);
+
+        let table_name = "test_table1".to_string();
+        let schema = Arc::new(Schema::new(vec![
+            Column::new("a".to_string(), DataType::Int8, true),
+            Column::new("b".to_string(), DataType::Int16, true),
+            Column::new("c".to_string(), DataType::Int32, true),
+        ]));
+        let _ = catalog.create_table(table_name.clone(), schema.clone());
+        let _ = catalog.create_index("test_index1".to_string(), table_name.clone(), vec![1]);
+        let _ = catalog.create_index("test_index2".to_string(), table_name.clone(), vec![2]);
+        let meta = TupleMeta {
+            insert_txn_id: 0,
+            delete_txn_id: 0,
+            is_deleted: false,
+        };
+        for i in 1..=2 {
+            let tuple = Tuple::new(
+                schema.clone(),
+                vec![(i as i8).into(), (i as i16).into(), (i * 10).into()],
+            );
+            let rid = catalog
+                .get_mut_table_by_name(&table_name)
+                .unwrap()
+                .table
+                .insert_tuple(&meta, &tuple)
+                .unwrap();
+            for index_info in catalog.get_mut_table_indexes(&table_name) {
+                let key_attrs = index_info.index.index_metadata.key_attrs.clone();
+                let key = Tuple::new(
+                    index_info.key_schema.clone(),
+                    key_attrs.iter().map(|i| tuple.data[*i].clone()).collect(),
+                );
+                index_info.index.insert(&key, rid);
+            }
+        }
+
+        catalog
+            .add_column(
+                &table_name,
+                Column::new("d".to_string(), DataType::Int32, false),
+                7i32.into(),
+            )
+            .unwrap();
+        catalog.drop_column(&table_name, 1).unwrap();
+        catalog
+            .rename_column(&table_name, 0, "e".to_string())
+            .unwrap();
+
+        let table_info = catalog.get_mut_table_by_name(&table_name).unwrap();
+        let names = table_info
+            .schema
+            .columns
+            .iter()
+            .map(|col| col.name.clone())
+            .collect::<Vec<String>>();
+        assert_eq!(names, vec!["e", "c", "d"]);
+        let mut iterator = table_info.table.iter(None, None);
+        let (_, tuple) = iterator.next(&mut table_info.table).unwrap();
+        assert_eq!(tuple.data, vec![1i8.into(), 10i32.into(), 7i32.into()]);
+
+        // the index on the dropped column is gone, the other one points to the new rows
+        assert!(catalog
+            .get_index_by_name(&table_name, "test_index1")
+            .is_none());
+        let mut index_info = catalog.get_mut_table_indexes(&table_name);
+        assert_eq!(index_info.len(), 1);
+        assert_eq!(index_info[0].index.index_metadata.key_attrs, vec![1]);
+        let key = Tuple::new(index_info[0].key_schema.clone(), vec![20i32.into()]);
+        let rid = index_info[0].index.get(&key).unwrap();
+        let (_, tuple) = catalog
+            .get_mut_table_by_name(&table_name)
+            .unwrap()
+            .table
+            .get_tuple(rid);
+        assert_eq!(tuple.data, vec![2i8.into(), 20i32.into(), 7i32.into()]);
+
+        let new_table = TableReference::bare("test_table2".to_string());
+        catalog.rename_table(&table_name, new_table).unwrap();
+        assert!(catalog.get_table_by_name(&table_name).is_none());
+        let table_info = catalog.get_table_by_name("test_table2").unwrap();
+        assert_eq!(table_info.name, "test_table2");
+        assert_eq!(
+            table_info.schema.columns[0].relation,
+            Some(TableReference::bare("test_table2".to_string()))
+        );
+        let index_info = catalog
+            .get_index_by_name("test_table2", "test_index2")
+            .unwrap();
+        assert_eq!(index_info.table_name, "test_table2");
+
+        let _ = remove_file(db_path);
+    }
 }
@@ -12,6 +12,10 @@ pub struct Column {
     pub name: String,
     pub data_type: DataType,
     pub nullable: bool,
+    /// Kept unbound so that functions like `now()` are evaluated by each INSERT
+    pub default: Option<Box<sqlparser::ast::Expr>>,
+    /// Left out of `SELECT *`, e.g. the per-side key columns of a USING join
+    pub hidden: bool,
 }
 
 impl PartialEq for Column {
@@ -29,6 +33,8 @@ impl Column {
             name,
             data_type,
             nullable,
+            default: None,
+            hidden: false,
         }
     }
 }
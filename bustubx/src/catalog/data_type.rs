@@ -1,31 +1,63 @@
+use crate::common::decimal::{DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE};
 use crate::error::BustubxError;
+use sqlparser::ast::{ExactNumberInfo, TimezoneInfo};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DataType {
+    /// Type of an untyped `NULL` literal, resolved to a concrete type by the planner.
+    Null,
     Boolean,
     Int8,
     Int16,
     Int32,
     Int64,
     UInt64,
-    // VarlenType,
-    // DecimalType
-    // NumericType
+    Float32,
+    Float64,
+    /// Exact fixed-point number with precision (total digits, at most 38) and scale
+    /// (digits after the decimal point).
+    Decimal128(u8, u8),
+    /// Days since 1970-01-01
+    Date,
+    /// Microseconds since midnight
+    Time,
+    /// Microseconds since 1970-01-01 00:00:00, without time zone
+    Timestamp,
+    Interval,
+    /// Variable-length string with an optional maximum length in characters.
+    /// `TEXT` is represented as `Varchar(None)`.
+    Varchar(Option<usize>),
 }
 
 impl DataType {
-    // 实现了一个DataType中的函数
-    pub fn type_size(&self) -> usize {
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 | DataType::UInt64
+        )
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        self.is_integer()
+            || matches!(
+                self,
+                DataType::Float32 | DataType::Float64 | DataType::Decimal128(_, _)
+            )
+    }
+
+    /// The smallest decimal type that holds every value of an integer type.
+    pub fn integer_as_decimal(&self) -> Option<DataType> {
         match self {
-            DataType::Boolean => 1,
-            DataType::Int8 => 1,
-            DataType::Int16 => 2,
-            DataType::Int32 => 4,
-            DataType::Int64 => 8,
-            DataType::UInt64 => 8,
+            DataType::Int8 => Some(DataType::Decimal128(3, 0)),
+            DataType::Int16 => Some(DataType::Decimal128(5, 0)),
+            DataType::Int32 => Some(DataType::Decimal128(10, 0)),
+            DataType::Int64 => Some(DataType::Decimal128(19, 0)),
+            DataType::UInt64 => Some(DataType::Decimal128(20, 0)),
+            _ => None,
         }
     }
 }
+
 // 有一个TryFrom特种，DataType去实现了这个特征
 impl TryFrom<&sqlparser::ast::DataType> for DataType {
     type Error = BustubxError;
@@ -38,6 +70,57 @@ impl TryFrom<&sqlparser::ast::DataType> for DataType {
             sqlparser::ast::DataType::Int(_) => Ok(DataType::Int32),
             sqlparser::ast::DataType::BigInt(_) => Ok(DataType::Int64),
             sqlparser::ast::DataType::UnsignedBigInt(_) => Ok(DataType::UInt64),
+            sqlparser::ast::DataType::Real => Ok(DataType::Float32),
+            sqlparser::ast::DataType::Double | sqlparser::ast::DataType::DoublePrecision => {
+                Ok(DataType::Float64)
+            }
+            // FLOAT(p) follows postgres: precision 1-24 is REAL, otherwise DOUBLE PRECISION
+            sqlparser::ast::DataType::Float(precision) => match precision {
+                Some(p) if *p <= 24 => Ok(DataType::Float32),
+                _ => Ok(DataType::Float64),
+            },
+            sqlparser::ast::DataType::Decimal(info) | sqlparser::ast::DataType::Numeric(info) => {
+                let (precision, scale) = match info {
+                    ExactNumberInfo::None => (
+                        DECIMAL128_MAX_PRECISION as u64,
+                        DECIMAL_DEFAULT_SCALE as u64,
+                    ),
+                    ExactNumberInfo::Precision(p) => (*p, 0),
+                    ExactNumberInfo::PrecisionAndScale(p, s) => (*p, *s),
+                };
+                if precision == 0 || precision > DECIMAL128_MAX_PRECISION as u64 {
+                    return Err(BustubxError::Plan(format!(
+                        "decimal precision {} must be between 1 and {}",
+                        precision, DECIMAL128_MAX_PRECISION
+                    )));
+                }
+                if scale > precision {
+                    return Err(BustubxError::Plan(format!(
+                        "decimal scale {} must not be greater than precision {}",
+                        scale, precision
+                    )));
+                }
+                Ok(DataType::Decimal128(precision as u8, scale as u8))
+            }
+            sqlparser::ast::DataType::Date => Ok(DataType::Date),
+            sqlparser::ast::DataType::Time(
+                _,
+                TimezoneInfo::None | TimezoneInfo::WithoutTimeZone,
+            ) => Ok(DataType::Time),
+            sqlparser::ast::DataType::Timestamp(
+                _,
+                TimezoneInfo::None | TimezoneInfo::WithoutTimeZone,
+            )
+            | sqlparser::ast::DataType::Datetime(_) => Ok(DataType::Timestamp),
+            sqlparser::ast::DataType::Interval => Ok(DataType::Interval),
+            sqlparser::ast::DataType::Varchar(len)
+            | sqlparser::ast::DataType::CharVarying(len)
+            | sqlparser::ast::DataType::CharacterVarying(len) => {
+                Ok(DataType::Varchar(len.map(|l| l.length as usize)))
+            }
+            sqlparser::ast::DataType::Text | sqlparser::ast::DataType::String => {
+                Ok(DataType::Varchar(None))
+            }
             _ => Err(BustubxError::NotSupport(format!(
                 "unsupported datatype: {}",
                 value
@@ -48,6 +131,11 @@ impl TryFrom<&sqlparser::ast::DataType> for DataType {
 
 impl std::fmt::Display for DataType {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{self:?}")
+        match self {
+            DataType::Decimal128(precision, scale) => write!(f, "Decimal({precision},{scale})"),
+            DataType::Varchar(Some(len)) => write!(f, "Varchar({len})"),
+            DataType::Varchar(None) => write!(f, "Varchar"),
+            _ => write!(f, "{self:?}"),
+        }
     }
 }
@@ -8,4 +8,7 @@ pub use catalog::{
 };
 pub use column::{Column, ColumnRef};
 pub use data_type::DataType;
-pub use schema::{Schema, SchemaRef, EMPTY_SCHEMA_REF, INSERT_OUTPUT_SCHEMA_REF};
+pub use schema::{
+    Schema, SchemaRef, DELETE_OUTPUT_SCHEMA_REF, EMPTY_SCHEMA_REF, INSERT_OUTPUT_SCHEMA_REF,
+    UPDATE_OUTPUT_SCHEMA_REF,
+};
@@ -12,6 +12,12 @@ lazy_static::lazy_static! {
     pub static ref INSERT_OUTPUT_SCHEMA_REF: SchemaRef = Arc::new(Schema::new(
         vec![Column::new("insert_rows".to_string(), DataType::Int32, false)]
     ));
+    pub static ref DELETE_OUTPUT_SCHEMA_REF: SchemaRef = Arc::new(Schema::new(
+        vec![Column::new("delete_rows".to_string(), DataType::Int32, false)]
+    ));
+    pub static ref UPDATE_OUTPUT_SCHEMA_REF: SchemaRef = Arc::new(Schema::new(
+        vec![Column::new("update_rows".to_string(), DataType::Int32, false)]
+    ));
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -80,10 +86,6 @@ impl Schema {
         Ok(idx)
     }
 
-    pub fn fixed_len(&self) -> usize {
-        self.columns.iter().map(|c| c.data_type.type_size()).sum()
-    }
-
     pub fn column_count(&self) -> usize {
         self.columns.len()
     }
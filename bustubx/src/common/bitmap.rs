@@ -23,7 +23,7 @@ impl DynamicBitmap {
     }
 
     pub fn get(&self, index: usize) -> Option<bool> {
-        if index >= self.map.len() << 8 {
+        if index >= self.map.len() << 3 {
             return None;
         }
         let byte_idx = index >> 3; // idx / 8
@@ -61,6 +61,8 @@ mod tests {
         assert_eq!(bitmap.get(0), Some(false));
         assert_eq!(bitmap.get(3), Some(true));
         assert_eq!(bitmap.get(10), Some(true));
+        assert_eq!(bitmap.get(15), Some(false));
+        assert_eq!(bitmap.get(16), None);
 
         let new_bitmap = DynamicBitmap::from_bytes(&bitmap.to_bytes());
         assert_eq!(new_bitmap, bitmap);
@@ -0,0 +1,260 @@
+//! Fixed-point helpers for `DECIMAL(p, s)` values.
+//!
+//! A decimal is stored as an unscaled `i128` together with its precision (total number of
+//! digits) and scale (digits after the decimal point), so `12.34` in `DECIMAL(5, 2)` is `1234`.
+//! Rounding always goes half away from zero, the same as postgres `numeric`.
+
+use crate::{BustubxError, BustubxResult};
+use std::cmp::Ordering;
+
+pub const DECIMAL128_MAX_PRECISION: u8 = 38;
+/// Scale used for a bare `DECIMAL` / `NUMERIC` without precision and scale.
+pub const DECIMAL_DEFAULT_SCALE: u8 = 10;
+/// Minimal scale kept when the result type of an arithmetic operation has to be narrowed.
+const DECIMAL_MIN_ADJUSTED_SCALE: u32 = 6;
+
+pub fn pow10(exp: u8) -> BustubxResult<i128> {
+    10i128.checked_pow(exp as u32).ok_or(overflow())
+}
+
+fn overflow() -> BustubxError {
+    BustubxError::Execution("numeric value overflow".to_string())
+}
+
+/// Check the unscaled value fits in `precision` digits.
+pub fn validate_precision(value: i128, precision: u8) -> BustubxResult<i128> {
+    if value.unsigned_abs() >= pow10(precision)? as u128 {
+        return Err(BustubxError::Execution(format!(
+            "numeric field overflow, value does not fit in precision {}",
+            precision
+        )));
+    }
+    Ok(value)
+}
+
+/// Integer division rounding half away from zero.
+fn div_round(numerator: i128, denominator: i128) -> BustubxResult<i128> {
+    if denominator == 0 {
+        return Err(BustubxError::Execution("division by zero".to_string()));
+    }
+    let quotient = numerator.checked_div(denominator).ok_or(overflow())?;
+    let remainder = numerator % denominator;
+    if remainder.unsigned_abs() >= denominator.unsigned_abs() - remainder.unsigned_abs() {
+        let sign = if (numerator < 0) == (denominator < 0) {
+            1
+        } else {
+            -1
+        };
+        quotient.checked_add(sign).ok_or(overflow())
+    } else {
+        Ok(quotient)
+    }
+}
+
+/// Change the scale of an unscaled value, rounding when digits are dropped.
+pub fn rescale(value: i128, from_scale: u8, to_scale: u8) -> BustubxResult<i128> {
+    match from_scale.cmp(&to_scale) {
+        Ordering::Equal => Ok(value),
+        Ordering::Less => value
+            .checked_mul(pow10(to_scale - from_scale)?)
+            .ok_or(overflow()),
+        Ordering::Greater => div_round(value, pow10(from_scale - to_scale)?),
+    }
+}
+
+pub fn cmp(v1: i128, s1: u8, v2: i128, s2: u8) -> Ordering {
+    let scale = s1.max(s2);
+    match (rescale(v1, s1, scale), rescale(v2, s2, scale)) {
+        (Ok(v1), Ok(v2)) => v1.cmp(&v2),
+        // only the side being scaled up can overflow, so its magnitude is the larger one
+        (Err(_), _) => v1.cmp(&0),
+        (_, Err(_)) => 0.cmp(&v2),
+    }
+}
+
+pub fn format(value: i128, scale: u8) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "-" } else { "" };
+    if scale == 0 {
+        return format!("{sign}{digits}");
+    }
+    let scale = scale as usize;
+    let digits = format!("{digits:0>width$}", width = scale + 1);
+    let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+    format!("{sign}{int_part}.{frac_part}")
+}
+
+/// Parse a plain decimal string like `-12.345` into an unscaled value of `scale`.
+pub fn parse(s: &str, scale: u8) -> BustubxResult<i128> {
+    let invalid = || BustubxError::Execution(format!("invalid input syntax for numeric: '{}'", s));
+    let trimmed = s.trim();
+    let (negative, unsigned) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let (int_part, frac_part) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    if int_part.is_empty() && frac_part.is_empty() {
+        return Err(invalid());
+    }
+    if !int_part
+        .chars()
+        .chain(frac_part.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    let frac_scale = u8::try_from(frac_part.len()).map_err(|_| overflow())?;
+    let unscaled: i128 = format!("{int_part}{frac_part}")
+        .parse()
+        .map_err(|_| overflow())?;
+    let value = rescale(unscaled, frac_scale, scale)?;
+    Ok(if negative { -value } else { value })
+}
+
+/// Narrow a result type that does not fit in 38 digits by giving up fractional digits,
+/// keeping at least 6 of them.
+fn adjust(precision: u32, scale: u32) -> (u8, u8) {
+    let max = DECIMAL128_MAX_PRECISION as u32;
+    if precision <= max {
+        return (precision as u8, scale as u8);
+    }
+    let int_digits = precision - scale;
+    let scale = scale.min(
+        max.saturating_sub(int_digits)
+            .max(DECIMAL_MIN_ADJUSTED_SCALE),
+    );
+    (max as u8, scale as u8)
+}
+
+pub fn add_type(p1: u8, s1: u8, p2: u8, s2: u8) -> (u8, u8) {
+    let (p1, s1, p2, s2) = (p1 as u32, s1 as u32, p2 as u32, s2 as u32);
+    let scale = s1.max(s2);
+    adjust((p1 - s1).max(p2 - s2) + scale + 1, scale)
+}
+
+pub fn mul_type(p1: u8, s1: u8, p2: u8, s2: u8) -> (u8, u8) {
+    adjust(p1 as u32 + p2 as u32 + 1, s1 as u32 + s2 as u32)
+}
+
+pub fn div_type(p1: u8, s1: u8, p2: u8, s2: u8) -> (u8, u8) {
+    let (p1, s1, p2, s2) = (p1 as u32, s1 as u32, p2 as u32, s2 as u32);
+    let scale = DECIMAL_MIN_ADJUSTED_SCALE.max(s1 + p2 + 1);
+    adjust(p1 - s1 + s2 + scale, scale)
+}
+
+pub fn rem_type(p1: u8, s1: u8, p2: u8, s2: u8) -> (u8, u8) {
+    let (p1, s1, p2, s2) = (p1 as u32, s1 as u32, p2 as u32, s2 as u32);
+    let scale = s1.max(s2);
+    adjust((p1 - s1).min(p2 - s2) + scale, scale)
+}
+
+pub fn add(v1: i128, s1: u8, v2: i128, s2: u8, scale: u8) -> BustubxResult<i128> {
+    let sum = rescale(v1, s1, s1.max(s2))?
+        .checked_add(rescale(v2, s2, s1.max(s2))?)
+        .ok_or(overflow())?;
+    rescale(sum, s1.max(s2), scale)
+}
+
+pub fn sub(v1: i128, s1: u8, v2: i128, s2: u8, scale: u8) -> BustubxResult<i128> {
+    add(v1, s1, v2.checked_neg().ok_or(overflow())?, s2, scale)
+}
+
+pub fn mul(v1: i128, s1: u8, v2: i128, s2: u8, scale: u8) -> BustubxResult<i128> {
+    let product = v1.checked_mul(v2).ok_or(overflow())?;
+    rescale(product, s1 + s2, scale)
+}
+
+pub fn div(v1: i128, s1: u8, v2: i128, s2: u8, scale: u8) -> BustubxResult<i128> {
+    if v2 == 0 {
+        return Err(BustubxError::Execution("division by zero".to_string()));
+    }
+    // v1 / 10^s1 / (v2 / 10^s2) * 10^scale = v1 * 10^(scale + s2 - s1) / v2
+    let exp = scale as i32 + s2 as i32 - s1 as i32;
+    if exp >= 0 {
+        let numerator = v1.checked_mul(pow10(exp as u8)?).ok_or(overflow())?;
+        div_round(numerator, v2)
+    } else {
+        let denominator = v2.checked_mul(pow10((-exp) as u8)?).ok_or(overflow())?;
+        div_round(v1, denominator)
+    }
+}
+
+/// Remainder with the sign of the dividend, like postgres.
+pub fn rem(v1: i128, s1: u8, v2: i128, s2: u8, scale: u8) -> BustubxResult<i128> {
+    if v2 == 0 {
+        return Err(BustubxError::Execution("division by zero".to_string()));
+    }
+    let common = s1.max(s2);
+    let remainder = rescale(v1, s1, common)?.wrapping_rem(rescale(v2, s2, common)?);
+    rescale(remainder, common, scale)
+}
+
+/// Round to `digits` fractional digits, a negative `digits` rounds to tens, hundreds etc.
+/// The result keeps the scale of the input.
+pub fn round(value: i128, scale: u8, digits: i32) -> BustubxResult<i128> {
+    let dropped = scale as i32 - digits;
+    if dropped <= 0 {
+        return Ok(value);
+    }
+    if dropped > DECIMAL128_MAX_PRECISION as i32 {
+        return Ok(0);
+    }
+    let factor = pow10(dropped as u8)?;
+    div_round(value, factor)?
+        .checked_mul(factor)
+        .ok_or(overflow())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::decimal;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn decimal_rescale_and_format() {
+        assert_eq!(decimal::rescale(1234, 2, 4).unwrap(), 123400);
+        assert_eq!(decimal::rescale(1235, 2, 1).unwrap(), 124);
+        assert_eq!(decimal::rescale(-1235, 2, 1).unwrap(), -124);
+        assert_eq!(decimal::rescale(1234, 2, 1).unwrap(), 123);
+        assert_eq!(decimal::format(1234, 2), "12.34");
+        assert_eq!(decimal::format(-5, 3), "-0.005");
+        assert_eq!(decimal::format(42, 0), "42");
+        assert_eq!(decimal::parse("12.345", 2).unwrap(), 1235);
+        assert_eq!(decimal::parse("-0.5", 0).unwrap(), -1);
+        assert_eq!(decimal::parse("7", 2).unwrap(), 700);
+        assert!(decimal::parse("1.2.3", 2).is_err());
+        assert!(decimal::validate_precision(12345, 4).is_err());
+        assert!(decimal::validate_precision(-9999, 4).is_ok());
+        assert_eq!(decimal::cmp(100, 2, 1, 0), Ordering::Equal);
+        assert_eq!(decimal::cmp(i128::MAX, 0, 1, 5), Ordering::Greater);
+    }
+
+    #[test]
+    fn decimal_arithmetic() {
+        // 1.05 + 2.1 = 3.15
+        assert_eq!(decimal::add_type(5, 2, 3, 1), (6, 2));
+        assert_eq!(decimal::add(105, 2, 21, 1, 2).unwrap(), 315);
+        assert_eq!(decimal::sub(105, 2, 21, 1, 2).unwrap(), -105);
+        // 1.5 * 1.25 = 1.875
+        assert_eq!(decimal::mul_type(2, 1, 3, 2), (6, 3));
+        assert_eq!(decimal::mul(15, 1, 125, 2, 3).unwrap(), 1875);
+        // 10.00 / 3 = 3.333333
+        assert_eq!(decimal::div_type(4, 2, 1, 0), (8, 6));
+        assert_eq!(decimal::div(1000, 2, 3, 0, 6).unwrap(), 3333333);
+        // 2 / 3 rounds half away from zero
+        assert_eq!(decimal::div(-2, 0, 3, 0, 6).unwrap(), -666667);
+        assert!(decimal::div(1, 0, 0, 0, 6).is_err());
+        assert_eq!(decimal::div_type(38, 10, 38, 10), (38, 6));
+        assert!(decimal::mul(i128::MAX, 0, 2, 0, 0).is_err());
+        // 5.5 % 2 = 1.5, -5.5 % 2 = -1.5
+        assert_eq!(decimal::rem_type(2, 1, 1, 0), (2, 1));
+        assert_eq!(decimal::rem(55, 1, 2, 0, 1).unwrap(), 15);
+        assert_eq!(decimal::rem(-55, 1, 2, 0, 1).unwrap(), -15);
+        assert!(decimal::rem(1, 0, 0, 2, 2).is_err());
+        // 12.345 rounded to 1 digit is 12.300, -15 rounded to tens is -20
+        assert_eq!(decimal::round(12345, 3, 1).unwrap(), 12300);
+        assert_eq!(decimal::round(-15, 0, -1).unwrap(), -20);
+        assert_eq!(decimal::round(12345, 3, 5).unwrap(), 12345);
+        assert_eq!(decimal::round(12345, 3, -40).unwrap(), 0);
+    }
+}
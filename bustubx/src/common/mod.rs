@@ -1,7 +1,9 @@
 mod bitmap;
+pub mod decimal;
 pub mod rid;
 mod scalar;
 mod table_ref;
+pub mod temporal;
 pub mod util;
 
 pub use bitmap::DynamicBitmap;
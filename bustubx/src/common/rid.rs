@@ -1,4 +1,6 @@
 use crate::buffer::PageId;
+use crate::common::ScalarValue;
+use crate::{BustubxError, BustubxResult};
 
 // Record Identifier
 #[derive(derive_new::new, Debug, Clone, Copy, PartialEq, Eq)]
@@ -25,4 +27,22 @@ impl Rid {
         bytes[4..8].copy_from_slice(&self.slot_num.to_be_bytes());
         bytes
     }
+
+    /// Packs the rid into a single value so that it can be carried as a column of a row.
+    pub fn to_scalar(self) -> ScalarValue {
+        ScalarValue::UInt64(Some(((self.page_id as u64) << 32) | self.slot_num as u64))
+    }
+
+    pub fn try_from_scalar(value: &ScalarValue) -> BustubxResult<Self> {
+        match value {
+            ScalarValue::UInt64(Some(v)) => Ok(Self {
+                page_id: (v >> 32) as PageId,
+                slot_num: *v as u32,
+            }),
+            _ => Err(BustubxError::Internal(format!(
+                "Can not get rid from value {:?}",
+                value
+            ))),
+        }
+    }
 }
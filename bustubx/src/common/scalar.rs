@@ -1,129 +1,367 @@
 use crate::catalog::DataType;
+use crate::common::temporal::IntervalValue;
+use crate::common::{decimal, temporal};
 use crate::{BustubxError, BustubxResult};
 use std::cmp::Ordering;
 
 #[derive(Debug, Clone)]
 pub enum ScalarValue {
+    /// An untyped `NULL`
+    Null,
     Boolean(Option<bool>),
     Int8(Option<i8>),
     Int16(Option<i16>),
     Int32(Option<i32>),
     Int64(Option<i64>),
     UInt64(Option<u64>),
+    Float32(Option<f32>),
+    Float64(Option<f64>),
+    /// Unscaled value, precision and scale
+    Decimal128(Option<i128>, u8, u8),
+    Date(Option<i32>),
+    Time(Option<i64>),
+    Timestamp(Option<i64>),
+    Interval(Option<IntervalValue>),
+    Varchar(Option<String>),
 }
 
 impl ScalarValue {
     pub fn new_empty(data_type: DataType) -> Self {
         match data_type {
+            DataType::Null => Self::Null,
             DataType::Boolean => Self::Boolean(None),
             DataType::Int8 => Self::Int8(None),
             DataType::Int16 => Self::Int16(None),
             DataType::Int32 => Self::Int32(None),
             DataType::Int64 => Self::Int64(None),
             DataType::UInt64 => Self::UInt64(None),
+            DataType::Float32 => Self::Float32(None),
+            DataType::Float64 => Self::Float64(None),
+            DataType::Decimal128(precision, scale) => Self::Decimal128(None, precision, scale),
+            DataType::Date => Self::Date(None),
+            DataType::Time => Self::Time(None),
+            DataType::Timestamp => Self::Timestamp(None),
+            DataType::Interval => Self::Interval(None),
+            DataType::Varchar(_) => Self::Varchar(None),
         }
     }
-    pub fn from_bytes(bytes: &[u8], data_type: DataType) -> Self {
-        match data_type {
-            DataType::Boolean => Self::Boolean(Some(Self::boolean_from_bytes(bytes))),
-            DataType::Int8 => Self::Int8(Some(i8::from_be_bytes([bytes[0]]))),
-            DataType::Int16 => Self::Int16(Some(i16::from_be_bytes([bytes[0], bytes[1]]))),
-            DataType::Int32 => Self::Int32(Some(i32::from_be_bytes([
-                bytes[0], bytes[1], bytes[2], bytes[3],
-            ]))),
-            DataType::Int64 => Self::Int64(Some(i64::from_be_bytes([
-                bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
-            ]))),
-            DataType::UInt64 => Self::UInt64(Some(u64::from_be_bytes([
-                bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
-            ]))),
-        }
-    }
-
-    pub fn to_bytes(&self) -> Vec<u8> {
-        match self {
-            Self::Boolean(Some(v)) => Self::boolean_to_bytes(*v),
-            Self::Int8(Some(v)) => v.to_be_bytes().to_vec(),
-            Self::Int16(Some(v)) => v.to_be_bytes().to_vec(),
-            Self::Int32(Some(v)) => v.to_be_bytes().to_vec(),
-            Self::Int64(Some(v)) => v.to_be_bytes().to_vec(),
-            Self::UInt64(Some(v)) => v.to_be_bytes().to_vec(),
-
-            // TODO fixme
-            Self::Boolean(None) => vec![0u8; 1],
-            Self::Int8(None) => vec![0u8; 1],
-            Self::Int16(None) => vec![0u8; 2],
-            Self::Int32(None) => vec![0u8; 4],
-            Self::Int64(None) => vec![0u8; 8],
-            Self::UInt64(None) => vec![0u8; 8],
-            _ => unimplemented!(),
-        }
-    }
-
-    pub fn boolean_from_bytes(bytes: &[u8]) -> bool {
-        bytes[0] != 0
-    }
-    pub fn boolean_to_bytes(value: bool) -> Vec<u8> {
-        if value {
-            vec![1]
-        } else {
-            vec![0]
-        }
-    }
-
     pub fn data_type(&self) -> DataType {
         match self {
+            ScalarValue::Null => DataType::Null,
             ScalarValue::Boolean(_) => DataType::Boolean,
             ScalarValue::Int8(_) => DataType::Int8,
             ScalarValue::Int16(_) => DataType::Int16,
             ScalarValue::Int32(_) => DataType::Int32,
             ScalarValue::Int64(_) => DataType::Int64,
             ScalarValue::UInt64(_) => DataType::UInt64,
+            ScalarValue::Float32(_) => DataType::Float32,
+            ScalarValue::Float64(_) => DataType::Float64,
+            ScalarValue::Decimal128(_, precision, scale) => {
+                DataType::Decimal128(*precision, *scale)
+            }
+            ScalarValue::Date(_) => DataType::Date,
+            ScalarValue::Time(_) => DataType::Time,
+            ScalarValue::Timestamp(_) => DataType::Timestamp,
+            ScalarValue::Interval(_) => DataType::Interval,
+            ScalarValue::Varchar(_) => DataType::Varchar(None),
         }
     }
 
     pub fn is_null(&self) -> bool {
         match self {
+            ScalarValue::Null => true,
             ScalarValue::Boolean(v) => v.is_none(),
             ScalarValue::Int8(v) => v.is_none(),
             ScalarValue::Int16(v) => v.is_none(),
             ScalarValue::Int32(v) => v.is_none(),
             ScalarValue::Int64(v) => v.is_none(),
             ScalarValue::UInt64(v) => v.is_none(),
+            ScalarValue::Float32(v) => v.is_none(),
+            ScalarValue::Float64(v) => v.is_none(),
+            ScalarValue::Decimal128(v, _, _) => v.is_none(),
+            ScalarValue::Date(v) => v.is_none(),
+            ScalarValue::Time(v) => v.is_none(),
+            ScalarValue::Timestamp(v) => v.is_none(),
+            ScalarValue::Interval(v) => v.is_none(),
+            ScalarValue::Varchar(v) => v.is_none(),
         }
     }
 
     /// Try to cast this value to a ScalarValue of type `data_type`
     pub fn cast_to(&self, data_type: &DataType) -> BustubxResult<Self> {
+        if let ScalarValue::Null = self {
+            return Ok(ScalarValue::new_empty(*data_type));
+        }
         match data_type {
+            DataType::Null => match self {
+                v if v.is_null() => Ok(ScalarValue::Null),
+                _ => Err(BustubxError::NotSupport(format!(
+                    "Failed to cast {} to {} type",
+                    self, data_type
+                ))),
+            },
             DataType::Boolean => match self {
-                ScalarValue::Boolean(v) => Ok(ScalarValue::Boolean(v.clone())),
+                ScalarValue::Boolean(v) => Ok(ScalarValue::Boolean(*v)),
+                ScalarValue::Int8(Some(v)) => Ok(ScalarValue::Boolean(Some(*v != 0))),
+                ScalarValue::Int16(Some(v)) => Ok(ScalarValue::Boolean(Some(*v != 0))),
+                ScalarValue::Int32(Some(v)) => Ok(ScalarValue::Boolean(Some(*v != 0))),
+                ScalarValue::Int64(Some(v)) => Ok(ScalarValue::Boolean(Some(*v != 0))),
+                ScalarValue::UInt64(Some(v)) => Ok(ScalarValue::Boolean(Some(*v != 0))),
+                ScalarValue::Varchar(Some(v)) => Ok(ScalarValue::Boolean(Some(
+                    parse_bool(v).ok_or_else(|| invalid_input(data_type, v))?,
+                ))),
+                v if v.is_null() => Ok(ScalarValue::Boolean(None)),
                 _ => Err(BustubxError::NotSupport(format!(
                     "Failed to cast {} to {} type",
                     self, data_type
                 ))),
             },
-            DataType::Int32 => match self {
-                ScalarValue::Int8(v) => Ok(ScalarValue::Int32(v.map(|v| v as i32))),
-                ScalarValue::Int64(v) => Ok(ScalarValue::Int32(v.map(|v| v as i32))),
+            DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt64 => self.cast_to_integer(data_type),
+            DataType::Float32 | DataType::Float64 => self.cast_to_float(data_type),
+            DataType::Decimal128(_, _) => self.cast_to_decimal(data_type),
+            DataType::Date => match self {
+                ScalarValue::Date(v) => Ok(ScalarValue::Date(*v)),
+                ScalarValue::Timestamp(v) => Ok(ScalarValue::Date(
+                    v.map(temporal::timestamp_to_date).transpose()?,
+                )),
+                ScalarValue::Varchar(Some(v)) => {
+                    Ok(ScalarValue::Date(Some(temporal::parse_date(v)?)))
+                }
+                v if v.is_null() => Ok(ScalarValue::Date(None)),
                 _ => Err(BustubxError::NotSupport(format!(
                     "Failed to cast {} to {} type",
                     self, data_type
                 ))),
             },
-            _ => Err(BustubxError::NotSupport(format!(
-                "Not support cast to {} type",
+            DataType::Time => match self {
+                ScalarValue::Time(v) => Ok(ScalarValue::Time(*v)),
+                ScalarValue::Timestamp(v) => {
+                    Ok(ScalarValue::Time(v.map(temporal::timestamp_to_time)))
+                }
+                ScalarValue::Varchar(Some(v)) => {
+                    Ok(ScalarValue::Time(Some(temporal::parse_time(v)?)))
+                }
+                v if v.is_null() => Ok(ScalarValue::Time(None)),
+                _ => Err(BustubxError::NotSupport(format!(
+                    "Failed to cast {} to {} type",
+                    self, data_type
+                ))),
+            },
+            DataType::Timestamp => match self {
+                ScalarValue::Timestamp(v) => Ok(ScalarValue::Timestamp(*v)),
+                ScalarValue::Date(v) => {
+                    Ok(ScalarValue::Timestamp(v.map(temporal::date_to_timestamp)))
+                }
+                ScalarValue::Varchar(Some(v)) => {
+                    Ok(ScalarValue::Timestamp(Some(temporal::parse_timestamp(v)?)))
+                }
+                v if v.is_null() => Ok(ScalarValue::Timestamp(None)),
+                _ => Err(BustubxError::NotSupport(format!(
+                    "Failed to cast {} to {} type",
+                    self, data_type
+                ))),
+            },
+            DataType::Interval => match self {
+                ScalarValue::Interval(v) => Ok(ScalarValue::Interval(*v)),
+                ScalarValue::Varchar(Some(v)) => {
+                    Ok(ScalarValue::Interval(Some(temporal::parse_interval(v)?)))
+                }
+                v if v.is_null() => Ok(ScalarValue::Interval(None)),
+                _ => Err(BustubxError::NotSupport(format!(
+                    "Failed to cast {} to {} type",
+                    self, data_type
+                ))),
+            },
+            DataType::Varchar(len) => {
+                let value = match self {
+                    ScalarValue::Varchar(v) => v.clone(),
+                    v if v.is_null() => None,
+                    v => Some(v.to_string()),
+                };
+                if let (Some(v), Some(len)) = (&value, len) {
+                    if v.chars().count() > *len {
+                        return Err(BustubxError::Execution(format!(
+                            "Value too long for type {}: '{}'",
+                            data_type, v
+                        )));
+                    }
+                }
+                Ok(ScalarValue::Varchar(value))
+            }
+        }
+    }
+
+    fn cast_to_integer(&self, data_type: &DataType) -> BustubxResult<Self> {
+        // widen to i128 first so every integer target is range checked the same way
+        let value: Option<i128> = match self {
+            ScalarValue::Int8(v) => v.map(i128::from),
+            ScalarValue::Int16(v) => v.map(i128::from),
+            ScalarValue::Int32(v) => v.map(i128::from),
+            ScalarValue::Int64(v) => v.map(i128::from),
+            ScalarValue::UInt64(v) => v.map(i128::from),
+            ScalarValue::Float32(v) => v
+                .map(|v| self.float_to_i128(f64::from(v), data_type))
+                .transpose()?,
+            ScalarValue::Float64(v) => v.map(|v| self.float_to_i128(v, data_type)).transpose()?,
+            ScalarValue::Decimal128(v, _, scale) => {
+                v.map(|v| decimal::rescale(v, *scale, 0)).transpose()?
+            }
+            ScalarValue::Boolean(v) => v.map(i128::from),
+            ScalarValue::Varchar(Some(v)) => Some(
+                v.trim()
+                    .parse::<i128>()
+                    .map_err(|_| invalid_input(data_type, v))?,
+            ),
+            v if v.is_null() => None,
+            _ => {
+                return Err(BustubxError::NotSupport(format!(
+                    "Failed to cast {} to {} type",
+                    self, data_type
+                )))
+            }
+        };
+        let out_of_range =
+            |_| BustubxError::Execution(format!("{} out of range for type {}", self, data_type));
+        match data_type {
+            DataType::Int8 => Ok(ScalarValue::Int8(
+                value.map(i8::try_from).transpose().map_err(out_of_range)?,
+            )),
+            DataType::Int16 => Ok(ScalarValue::Int16(
+                value.map(i16::try_from).transpose().map_err(out_of_range)?,
+            )),
+            DataType::Int32 => Ok(ScalarValue::Int32(
+                value.map(i32::try_from).transpose().map_err(out_of_range)?,
+            )),
+            DataType::Int64 => Ok(ScalarValue::Int64(
+                value.map(i64::try_from).transpose().map_err(out_of_range)?,
+            )),
+            DataType::UInt64 => Ok(ScalarValue::UInt64(
+                value.map(u64::try_from).transpose().map_err(out_of_range)?,
+            )),
+            _ => Err(BustubxError::Internal(format!(
+                "{} is not an integer type",
                 data_type
             ))),
         }
     }
 
-    pub fn wrapping_add(&self, other: Self) -> BustubxResult<Self> {
-        todo!()
+    /// Floats are rounded half to even like postgres, NaN and infinity have no integer value.
+    fn float_to_i128(&self, value: f64, data_type: &DataType) -> BustubxResult<i128> {
+        if !value.is_finite() {
+            return Err(BustubxError::Execution(format!(
+                "{} out of range for type {}",
+                self, data_type
+            )));
+        }
+        Ok(value.round_ties_even() as i128)
     }
 
-    pub fn wrapping_sub(&self, other: Self) -> BustubxResult<Self> {
-        todo!()
+    fn cast_to_decimal(&self, data_type: &DataType) -> BustubxResult<Self> {
+        let DataType::Decimal128(precision, scale) = *data_type else {
+            return Err(BustubxError::Internal(format!(
+                "{} is not a decimal type",
+                data_type
+            )));
+        };
+        let from_int = |v: Option<i128>| v.map(|v| decimal::rescale(v, 0, scale)).transpose();
+        let value = match self {
+            ScalarValue::Int8(v) => from_int(v.map(i128::from))?,
+            ScalarValue::Int16(v) => from_int(v.map(i128::from))?,
+            ScalarValue::Int32(v) => from_int(v.map(i128::from))?,
+            ScalarValue::Int64(v) => from_int(v.map(i128::from))?,
+            ScalarValue::UInt64(v) => from_int(v.map(i128::from))?,
+            // go through the shortest representation that round-trips, so 0.1 stays exact
+            ScalarValue::Float32(v) => v
+                .map(|v| self.float_to_decimal(f64::from(v), &v.to_string(), data_type))
+                .transpose()?,
+            ScalarValue::Float64(v) => v
+                .map(|v| self.float_to_decimal(v, &v.to_string(), data_type))
+                .transpose()?,
+            ScalarValue::Decimal128(v, _, from_scale) => v
+                .map(|v| decimal::rescale(v, *from_scale, scale))
+                .transpose()?,
+            ScalarValue::Varchar(Some(v)) => Some(decimal::parse(v, scale)?),
+            v if v.is_null() => None,
+            _ => {
+                return Err(BustubxError::NotSupport(format!(
+                    "Failed to cast {} to {} type",
+                    self, data_type
+                )))
+            }
+        };
+        let value = value
+            .map(|v| decimal::validate_precision(v, precision))
+            .transpose()?;
+        Ok(ScalarValue::Decimal128(value, precision, scale))
+    }
+
+    fn float_to_decimal(
+        &self,
+        value: f64,
+        repr: &str,
+        data_type: &DataType,
+    ) -> BustubxResult<i128> {
+        let DataType::Decimal128(_, scale) = *data_type else {
+            unreachable!()
+        };
+        if !value.is_finite() {
+            return Err(BustubxError::Execution(format!(
+                "Can not cast {} to {} type",
+                self, data_type
+            )));
+        }
+        decimal::parse(repr, scale)
+    }
+
+    fn cast_to_float(&self, data_type: &DataType) -> BustubxResult<Self> {
+        let value: Option<f64> = match self {
+            ScalarValue::Int8(v) => v.map(f64::from),
+            ScalarValue::Int16(v) => v.map(f64::from),
+            ScalarValue::Int32(v) => v.map(f64::from),
+            ScalarValue::Int64(v) => v.map(|v| v as f64),
+            ScalarValue::UInt64(v) => v.map(|v| v as f64),
+            ScalarValue::Float32(v) => v.map(f64::from),
+            ScalarValue::Float64(v) => *v,
+            ScalarValue::Decimal128(v, _, scale) => v
+                .map(|v| Ok::<_, BustubxError>(v as f64 / decimal::pow10(*scale)? as f64))
+                .transpose()?,
+            // also accepts NaN, inf and infinity in any case
+            ScalarValue::Varchar(Some(v)) => Some(
+                v.trim()
+                    .parse::<f64>()
+                    .map_err(|_| invalid_input(data_type, v))?,
+            ),
+            v if v.is_null() => None,
+            _ => {
+                return Err(BustubxError::NotSupport(format!(
+                    "Failed to cast {} to {} type",
+                    self, data_type
+                )))
+            }
+        };
+        match data_type {
+            DataType::Float32 => {
+                let narrowed = value.map(|v| v as f32);
+                if let (Some(v), Some(n)) = (value, narrowed) {
+                    if v.is_finite() && n.is_infinite() {
+                        return Err(BustubxError::Execution(format!(
+                            "{} out of range for type {}",
+                            self, data_type
+                        )));
+                    }
+                }
+                Ok(ScalarValue::Float32(narrowed))
+            }
+            DataType::Float64 => Ok(ScalarValue::Float64(value)),
+            _ => Err(BustubxError::Internal(format!(
+                "{} is not a floating-point type",
+                data_type
+            ))),
+        }
     }
 }
 
@@ -131,6 +369,8 @@ impl PartialEq for ScalarValue {
     fn eq(&self, other: &Self) -> bool {
         use ScalarValue::*;
         match (self, other) {
+            (Null, Null) => true,
+            (Null, _) => false,
             (Boolean(v1), Boolean(v2)) => v1.eq(v2),
             (Boolean(_), _) => false,
             (Int8(v1), Int8(v2)) => v1.eq(v2),
@@ -143,16 +383,90 @@ impl PartialEq for ScalarValue {
             (Int64(_), _) => false,
             (UInt64(v1), UInt64(v2)) => v1.eq(v2),
             (UInt64(_), _) => false,
+            (Float32(v1), Float32(v2)) => {
+                cmp_float(v1.map(f64::from), v2.map(f64::from)) == Ordering::Equal
+            }
+            (Float32(_), _) => false,
+            (Float64(v1), Float64(v2)) => cmp_float(*v1, *v2) == Ordering::Equal,
+            (Float64(_), _) => false,
+            (Decimal128(v1, _, s1), Decimal128(v2, _, s2)) => match (v1, v2) {
+                (Some(v1), Some(v2)) => decimal::cmp(*v1, *s1, *v2, *s2) == Ordering::Equal,
+                (None, None) => true,
+                _ => false,
+            },
+            (Decimal128(_, _, _), _) => false,
+            (Date(v1), Date(v2)) => v1.eq(v2),
+            (Date(_), _) => false,
+            (Time(v1), Time(v2)) => v1.eq(v2),
+            (Time(_), _) => false,
+            (Timestamp(v1), Timestamp(v2)) => v1.eq(v2),
+            (Timestamp(_), _) => false,
+            // '1 mon' equals '30 days' like in postgres
+            (Interval(v1), Interval(v2)) => {
+                v1.map(|v| v.total_micros()) == v2.map(|v| v.total_micros())
+            }
+            (Interval(_), _) => false,
+            (Varchar(v1), Varchar(v2)) => v1.eq(v2),
+            (Varchar(_), _) => false,
         }
     }
 }
 
 impl Eq for ScalarValue {}
 
+/// Consistent with `PartialEq`: `-0.0` and `0.0` hash the same, as do all NaNs, decimals that
+/// only differ in trailing zeros, and intervals of the same length.
+impl std::hash::Hash for ScalarValue {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        use ScalarValue::*;
+        let hash_float = |v: Option<f64>, state: &mut H| {
+            v.map(|v| {
+                if v.is_nan() {
+                    f64::NAN.to_bits()
+                } else if v == 0.0 {
+                    0
+                } else {
+                    v.to_bits()
+                }
+            })
+            .hash(state)
+        };
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Null => {}
+            Boolean(v) => v.hash(state),
+            Int8(v) => v.hash(state),
+            Int16(v) => v.hash(state),
+            Int32(v) => v.hash(state),
+            Int64(v) => v.hash(state),
+            UInt64(v) => v.hash(state),
+            Float32(v) => hash_float(v.map(f64::from), state),
+            Float64(v) => hash_float(*v, state),
+            Decimal128(v, _, scale) => v
+                .map(|mut v| {
+                    let mut scale = *scale;
+                    while scale > 0 && v % 10 == 0 {
+                        v /= 10;
+                        scale -= 1;
+                    }
+                    (v, scale)
+                })
+                .hash(state),
+            Date(v) => v.hash(state),
+            Time(v) => v.hash(state),
+            Timestamp(v) => v.hash(state),
+            Interval(v) => v.map(|v| v.total_micros()).hash(state),
+            Varchar(v) => v.hash(state),
+        }
+    }
+}
+
 impl PartialOrd for ScalarValue {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         use ScalarValue::*;
         match (self, other) {
+            (Null, Null) => Some(Ordering::Equal),
+            (Null, _) => None,
             (Boolean(v1), Boolean(v2)) => v1.partial_cmp(v2),
             (Boolean(_), _) => None,
             (Int8(v1), Int8(v2)) => v1.partial_cmp(v2),
@@ -165,13 +479,51 @@ impl PartialOrd for ScalarValue {
             (Int64(_), _) => None,
             (UInt64(v1), UInt64(v2)) => v1.partial_cmp(v2),
             (UInt64(_), _) => None,
+            (Float32(v1), Float32(v2)) => Some(cmp_float(v1.map(f64::from), v2.map(f64::from))),
+            (Float32(_), _) => None,
+            (Float64(v1), Float64(v2)) => Some(cmp_float(*v1, *v2)),
+            (Float64(_), _) => None,
+            (Decimal128(v1, _, s1), Decimal128(v2, _, s2)) => match (v1, v2) {
+                (Some(v1), Some(v2)) => Some(decimal::cmp(*v1, *s1, *v2, *s2)),
+                _ => v1.partial_cmp(v2),
+            },
+            (Decimal128(_, _, _), _) => None,
+            (Date(v1), Date(v2)) => v1.partial_cmp(v2),
+            (Date(_), _) => None,
+            (Time(v1), Time(v2)) => v1.partial_cmp(v2),
+            (Time(_), _) => None,
+            (Timestamp(v1), Timestamp(v2)) => v1.partial_cmp(v2),
+            (Timestamp(_), _) => None,
+            (Interval(v1), Interval(v2)) => v1
+                .map(|v| v.total_micros())
+                .partial_cmp(&v2.map(|v| v.total_micros())),
+            (Interval(_), _) => None,
+            (Varchar(v1), Varchar(v2)) => v1.partial_cmp(v2),
+            (Varchar(_), _) => None,
         }
     }
 }
 
+/// Total order over floats following postgres: NaN equals NaN and sorts above every other value,
+/// `-0.0` equals `0.0`. NULL sorts first like the `Option` ordering used by the other variants.
+fn cmp_float(v1: Option<f64>, v2: Option<f64>) -> Ordering {
+    match (v1, v2) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(a), Some(b)) => match (a.is_nan(), b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        },
+    }
+}
+
 impl std::fmt::Display for ScalarValue {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
+            ScalarValue::Null => write!(f, "NULL"),
             ScalarValue::Boolean(None) => write!(f, "NULL"),
             ScalarValue::Boolean(Some(v)) => write!(f, "{v}"),
             ScalarValue::Int8(None) => write!(f, "NULL"),
@@ -184,10 +536,56 @@ impl std::fmt::Display for ScalarValue {
             ScalarValue::Int64(Some(v)) => write!(f, "{v}"),
             ScalarValue::UInt64(None) => write!(f, "NULL"),
             ScalarValue::UInt64(Some(v)) => write!(f, "{v}"),
+            ScalarValue::Float32(None) => write!(f, "NULL"),
+            ScalarValue::Float32(Some(v)) => fmt_float(f, *v),
+            ScalarValue::Float64(None) => write!(f, "NULL"),
+            ScalarValue::Float64(Some(v)) => fmt_float(f, *v),
+            ScalarValue::Decimal128(None, _, _) => write!(f, "NULL"),
+            ScalarValue::Decimal128(Some(v), _, scale) => {
+                write!(f, "{}", decimal::format(*v, *scale))
+            }
+            ScalarValue::Date(None) => write!(f, "NULL"),
+            ScalarValue::Date(Some(v)) => write!(f, "{}", temporal::format_date(*v)),
+            ScalarValue::Time(None) => write!(f, "NULL"),
+            ScalarValue::Time(Some(v)) => write!(f, "{}", temporal::format_time(*v)),
+            ScalarValue::Timestamp(None) => write!(f, "NULL"),
+            ScalarValue::Timestamp(Some(v)) => write!(f, "{}", temporal::format_timestamp(*v)),
+            ScalarValue::Interval(None) => write!(f, "NULL"),
+            ScalarValue::Interval(Some(v)) => write!(f, "{v}"),
+            ScalarValue::Varchar(None) => write!(f, "NULL"),
+            ScalarValue::Varchar(Some(v)) => write!(f, "{v}"),
         }
     }
 }
 
+fn invalid_input(data_type: &DataType, value: &str) -> BustubxError {
+    BustubxError::Execution(format!(
+        "invalid input syntax for type {}: '{}'",
+        data_type, value
+    ))
+}
+
+/// Boolean literals accepted by postgres, e.g. `'t'`, `'yes'`, `'off'`.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "t" | "true" | "y" | "yes" | "on" | "1" => Some(true),
+        "f" | "false" | "n" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn fmt_float<T: std::fmt::Display + Into<f64> + Copy>(
+    f: &mut std::fmt::Formatter,
+    v: T,
+) -> std::fmt::Result {
+    let wide: f64 = v.into();
+    if wide.is_infinite() {
+        write!(f, "{}", if wide > 0.0 { "Infinity" } else { "-Infinity" })
+    } else {
+        write!(f, "{v}")
+    }
+}
+
 macro_rules! impl_from_for_scalar {
     ($ty:ty, $scalar:tt) => {
         impl From<$ty> for ScalarValue {
@@ -209,4 +607,212 @@ impl_from_for_scalar!(i16, Int16);
 impl_from_for_scalar!(i32, Int32);
 impl_from_for_scalar!(i64, Int64);
 impl_from_for_scalar!(u64, UInt64);
+impl_from_for_scalar!(f32, Float32);
+impl_from_for_scalar!(f64, Float64);
 impl_from_for_scalar!(bool, Boolean);
+impl_from_for_scalar!(String, Varchar);
+
+impl From<&str> for ScalarValue {
+    fn from(value: &str) -> Self {
+        ScalarValue::Varchar(Some(value.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::DataType;
+    use crate::common::temporal::IntervalValue;
+    use crate::common::ScalarValue;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn float_total_order() {
+        let nan = ScalarValue::Float64(Some(f64::NAN));
+        assert_eq!(nan, ScalarValue::Float64(Some(f64::NAN)));
+        assert_eq!(
+            nan.partial_cmp(&ScalarValue::Float64(Some(f64::INFINITY))),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            ScalarValue::Float64(None).partial_cmp(&ScalarValue::Float64(Some(-1.0))),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            ScalarValue::Float32(Some(-0.0)),
+            ScalarValue::Float32(Some(0.0))
+        );
+        assert_eq!(
+            ScalarValue::Float32(Some(1.5)).partial_cmp(&ScalarValue::Float32(Some(2.0))),
+            Some(Ordering::Less)
+        );
+    }
+
+    #[test]
+    fn interval_compare_by_length() {
+        let month = ScalarValue::Interval(Some(IntervalValue::new(1, 0, 0)));
+        let days = ScalarValue::Interval(Some(IntervalValue::new(0, 30, 0)));
+        assert_eq!(month, days);
+        assert_eq!(month.partial_cmp(&days), Some(Ordering::Equal));
+        assert_eq!(
+            days.partial_cmp(&ScalarValue::Interval(Some(IntervalValue::new(0, 31, 0)))),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            ScalarValue::Interval(None).partial_cmp(&month),
+            Some(Ordering::Less)
+        );
+    }
+
+    #[test]
+    fn float_cast() {
+        assert_eq!(
+            ScalarValue::Int64(Some(3))
+                .cast_to(&DataType::Float64)
+                .unwrap(),
+            ScalarValue::Float64(Some(3.0))
+        );
+        assert_eq!(
+            ScalarValue::Float64(Some(2.5))
+                .cast_to(&DataType::Int32)
+                .unwrap(),
+            ScalarValue::Int32(Some(2))
+        );
+        assert_eq!(
+            ScalarValue::Float32(Some(-1.5))
+                .cast_to(&DataType::Int64)
+                .unwrap(),
+            ScalarValue::Int64(Some(-2))
+        );
+        assert_eq!(
+            ScalarValue::Float64(None).cast_to(&DataType::Int8).unwrap(),
+            ScalarValue::Int8(None)
+        );
+        assert!(ScalarValue::Float64(Some(f64::NAN))
+            .cast_to(&DataType::Int32)
+            .is_err());
+        assert!(ScalarValue::Float64(Some(1e10))
+            .cast_to(&DataType::Int32)
+            .is_err());
+        assert!(ScalarValue::Float64(Some(1e300))
+            .cast_to(&DataType::Float32)
+            .is_err());
+    }
+
+    #[test]
+    fn decimal_cast() {
+        assert_eq!(
+            ScalarValue::Int64(Some(12))
+                .cast_to(&DataType::Decimal128(5, 2))
+                .unwrap(),
+            ScalarValue::Decimal128(Some(1200), 5, 2)
+        );
+        assert_eq!(
+            ScalarValue::Float64(Some(0.1))
+                .cast_to(&DataType::Decimal128(5, 2))
+                .unwrap(),
+            ScalarValue::Decimal128(Some(10), 5, 2)
+        );
+        assert_eq!(
+            ScalarValue::Decimal128(Some(12345), 10, 3)
+                .cast_to(&DataType::Decimal128(10, 2))
+                .unwrap(),
+            ScalarValue::Decimal128(Some(1235), 10, 2)
+        );
+        assert_eq!(
+            ScalarValue::Decimal128(Some(-250), 5, 2)
+                .cast_to(&DataType::Int32)
+                .unwrap(),
+            ScalarValue::Int32(Some(-3))
+        );
+        assert_eq!(
+            ScalarValue::Decimal128(Some(-250), 5, 2)
+                .cast_to(&DataType::Float64)
+                .unwrap(),
+            ScalarValue::Float64(Some(-2.5))
+        );
+        assert!(ScalarValue::Int64(Some(1000))
+            .cast_to(&DataType::Decimal128(5, 2))
+            .is_err());
+        assert_eq!(
+            ScalarValue::Decimal128(Some(100), 5, 2),
+            ScalarValue::Decimal128(Some(1), 3, 0)
+        );
+        assert_eq!(ScalarValue::Decimal128(Some(-5), 5, 2).to_string(), "-0.05");
+    }
+
+    #[test]
+    fn text_cast() {
+        let text = |v: &str| ScalarValue::Varchar(Some(v.to_string()));
+        assert_eq!(
+            text(" 42 ").cast_to(&DataType::Int16).unwrap(),
+            ScalarValue::Int16(Some(42))
+        );
+        assert!(text("4.2").cast_to(&DataType::Int32).is_err());
+        assert!(text("70000").cast_to(&DataType::Int16).is_err());
+        assert_eq!(
+            text("-Infinity").cast_to(&DataType::Float64).unwrap(),
+            ScalarValue::Float64(Some(f64::NEG_INFINITY))
+        );
+        assert_eq!(
+            text("1.255").cast_to(&DataType::Decimal128(4, 2)).unwrap(),
+            ScalarValue::Decimal128(Some(126), 4, 2)
+        );
+        assert_eq!(
+            text("yes").cast_to(&DataType::Boolean).unwrap(),
+            ScalarValue::Boolean(Some(true))
+        );
+        assert!(text("maybe").cast_to(&DataType::Boolean).is_err());
+        assert_eq!(
+            ScalarValue::Float64(Some(1.5))
+                .cast_to(&DataType::Varchar(None))
+                .unwrap(),
+            text("1.5")
+        );
+        assert!(ScalarValue::Int64(Some(12345))
+            .cast_to(&DataType::Varchar(Some(3)))
+            .is_err());
+        assert_eq!(
+            ScalarValue::Varchar(None)
+                .cast_to(&DataType::Int64)
+                .unwrap(),
+            ScalarValue::Int64(None)
+        );
+        assert_eq!(
+            ScalarValue::Boolean(Some(true))
+                .cast_to(&DataType::Int32)
+                .unwrap(),
+            ScalarValue::Int32(Some(1))
+        );
+        assert_eq!(
+            ScalarValue::Int8(Some(0))
+                .cast_to(&DataType::Boolean)
+                .unwrap(),
+            ScalarValue::Boolean(Some(false))
+        );
+    }
+
+    #[test]
+    fn null_cast() {
+        assert_eq!(
+            ScalarValue::Null.cast_to(&DataType::Int64).unwrap(),
+            ScalarValue::Int64(None)
+        );
+        assert_eq!(
+            ScalarValue::Null
+                .cast_to(&DataType::Decimal128(5, 2))
+                .unwrap(),
+            ScalarValue::Decimal128(None, 5, 2)
+        );
+        assert_eq!(
+            ScalarValue::Int32(None).cast_to(&DataType::Null).unwrap(),
+            ScalarValue::Null
+        );
+        assert!(ScalarValue::Int32(Some(1))
+            .cast_to(&DataType::Null)
+            .is_err());
+        assert_eq!(
+            ScalarValue::Null.partial_cmp(&ScalarValue::Int8(None)),
+            None
+        );
+    }
+}
@@ -0,0 +1,485 @@
+//! Helpers for `DATE`, `TIME`, `TIMESTAMP` and `INTERVAL` values.
+//!
+//! Dates are days since 1970-01-01, times are microseconds since midnight and timestamps are
+//! microseconds since 1970-01-01 00:00:00 without time zone.
+
+use crate::{BustubxError, BustubxResult};
+use chrono::{
+    DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc,
+};
+use std::cmp::Ordering;
+
+pub const MICROS_PER_SECOND: i64 = 1_000_000;
+pub const MICROS_PER_DAY: i64 = 86_400 * MICROS_PER_SECOND;
+/// Days from 0001-01-01 to 1970-01-01, chrono counts days from the former.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+/// A month is 30 days when comparing intervals, like postgres does.
+const DAYS_PER_MONTH: i64 = 30;
+
+/// A span of time. Months and days are kept apart from the rest because their length in
+/// microseconds depends on the timestamp the interval is added to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct IntervalValue {
+    pub months: i32,
+    pub days: i32,
+    pub micros: i64,
+}
+
+impl IntervalValue {
+    pub fn new(months: i32, days: i32, micros: i64) -> Self {
+        Self {
+            months,
+            days,
+            micros,
+        }
+    }
+
+    pub fn negate(&self) -> BustubxResult<Self> {
+        Ok(Self {
+            months: self.months.checked_neg().ok_or_else(out_of_range)?,
+            days: self.days.checked_neg().ok_or_else(out_of_range)?,
+            micros: self.micros.checked_neg().ok_or_else(out_of_range)?,
+        })
+    }
+
+    pub fn checked_add(&self, other: &Self) -> BustubxResult<Self> {
+        Ok(Self {
+            months: self
+                .months
+                .checked_add(other.months)
+                .ok_or_else(out_of_range)?,
+            days: self.days.checked_add(other.days).ok_or_else(out_of_range)?,
+            micros: self
+                .micros
+                .checked_add(other.micros)
+                .ok_or_else(out_of_range)?,
+        })
+    }
+
+    pub(crate) fn total_micros(&self) -> i128 {
+        (self.months as i128 * DAYS_PER_MONTH as i128 + self.days as i128) * MICROS_PER_DAY as i128
+            + self.micros as i128
+    }
+}
+
+/// Orders by length, intervals of the same length like '1 mon' and '30 days' by their fields so
+/// that only equal values are `Equal`. SQL comparisons use `total_micros` alone.
+impl Ord for IntervalValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total_micros()
+            .cmp(&other.total_micros())
+            .then_with(|| {
+                (self.months, self.days, self.micros).cmp(&(other.months, other.days, other.micros))
+            })
+    }
+}
+
+impl PartialOrd for IntervalValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for IntervalValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let plural = |n: i64| if n.abs() == 1 { "" } else { "s" };
+        let mut parts = vec![];
+        let (years, months) = (self.months / 12, self.months % 12);
+        if years != 0 {
+            parts.push(format!("{} year{}", years, plural(years as i64)));
+        }
+        if months != 0 {
+            parts.push(format!("{} mon{}", months, plural(months as i64)));
+        }
+        if self.days != 0 {
+            parts.push(format!("{} day{}", self.days, plural(self.days as i64)));
+        }
+        if self.micros != 0 || parts.is_empty() {
+            let sign = if self.micros < 0 { "-" } else { "" };
+            let micros = self.micros.unsigned_abs();
+            let seconds = micros / MICROS_PER_SECOND as u64;
+            let mut time = format!(
+                "{}{:02}:{:02}:{:02}",
+                sign,
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            );
+            let fraction = micros % MICROS_PER_SECOND as u64;
+            if fraction != 0 {
+                time.push_str(format!(".{:06}", fraction).trim_end_matches('0'));
+            }
+            parts.push(time);
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+fn out_of_range() -> BustubxError {
+    BustubxError::Execution("date/time value out of range".to_string())
+}
+
+fn invalid_input(type_name: &str, value: &str) -> BustubxError {
+    BustubxError::Execution(format!(
+        "invalid input syntax for type {}: '{}'",
+        type_name, value
+    ))
+}
+
+fn date_to_naive(days: i32) -> BustubxResult<NaiveDate> {
+    days.checked_add(UNIX_EPOCH_DAYS_FROM_CE)
+        .and_then(NaiveDate::from_num_days_from_ce_opt)
+        .ok_or_else(out_of_range)
+}
+
+fn naive_to_date(date: NaiveDate) -> i32 {
+    date.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE
+}
+
+fn timestamp_to_naive(micros: i64) -> BustubxResult<NaiveDateTime> {
+    DateTime::from_timestamp_micros(micros)
+        .map(|t| t.naive_utc())
+        .ok_or_else(out_of_range)
+}
+
+fn naive_to_timestamp(datetime: NaiveDateTime) -> i64 {
+    datetime.and_utc().timestamp_micros()
+}
+
+fn time_to_naive(micros: i64) -> BustubxResult<NaiveTime> {
+    let seconds = micros.div_euclid(MICROS_PER_SECOND);
+    let nanos = micros.rem_euclid(MICROS_PER_SECOND) * 1000;
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds as u32, nanos as u32)
+        .ok_or_else(out_of_range)
+}
+
+fn naive_to_time(time: NaiveTime) -> i64 {
+    time.num_seconds_from_midnight() as i64 * MICROS_PER_SECOND + time.nanosecond() as i64 / 1000
+}
+
+pub fn parse_date(value: &str) -> BustubxResult<i32> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map(naive_to_date)
+        .map_err(|_| invalid_input("date", value))
+}
+
+pub fn parse_time(value: &str) -> BustubxResult<i64> {
+    let trimmed = value.trim();
+    NaiveTime::parse_from_str(trimmed, "%H:%M:%S%.f")
+        .or_else(|_| NaiveTime::parse_from_str(trimmed, "%H:%M"))
+        .map(naive_to_time)
+        .map_err(|_| invalid_input("time", value))
+}
+
+/// Accepts `YYYY-MM-DD[( |T)HH:MM[:SS[.ffffff]]]`.
+pub fn parse_timestamp(value: &str) -> BustubxResult<i64> {
+    let trimmed = value.trim();
+    [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(trimmed, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+            .ok()
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+    })
+    .map(naive_to_timestamp)
+    .ok_or_else(|| invalid_input("timestamp", value))
+}
+
+/// Parse postgres style intervals like `1 year 2 months 3 days 04:05:06` or `90 minutes`.
+pub fn parse_interval(value: &str) -> BustubxResult<IntervalValue> {
+    let invalid = || invalid_input("interval", value);
+    let mut interval = IntervalValue::default();
+    let mut tokens = value.split_whitespace().peekable();
+    if tokens.peek().is_none() {
+        return Err(invalid());
+    }
+    while let Some(token) = tokens.next() {
+        if token.contains(':') {
+            let negative = token.starts_with('-');
+            let micros = parse_time(token.trim_start_matches(['-', '+']))?;
+            let micros = if negative { -micros } else { micros };
+            interval = interval.checked_add(&IntervalValue::new(0, 0, micros))?;
+            continue;
+        }
+        let quantity: f64 = token.parse().map_err(|_| invalid())?;
+        let unit = tokens.next().ok_or_else(invalid)?.to_lowercase();
+        let part = match unit.as_str() {
+            "year" | "years" | "yr" | "yrs" | "y" => {
+                IntervalValue::new((quantity * 12.0) as i32, 0, 0)
+            }
+            "month" | "months" | "mon" | "mons" => IntervalValue::new(quantity as i32, 0, 0),
+            "week" | "weeks" | "w" => IntervalValue::new(0, (quantity * 7.0) as i32, 0),
+            "day" | "days" | "d" => IntervalValue::new(0, quantity as i32, 0),
+            "hour" | "hours" | "hr" | "hrs" | "h" => interval_from_seconds(quantity * 3600.0),
+            "minute" | "minutes" | "min" | "mins" | "m" => interval_from_seconds(quantity * 60.0),
+            "second" | "seconds" | "sec" | "secs" | "s" => interval_from_seconds(quantity),
+            "millisecond" | "milliseconds" | "ms" => interval_from_seconds(quantity / 1000.0),
+            "microsecond" | "microseconds" | "us" => interval_from_seconds(quantity / 1_000_000.0),
+            _ => return Err(invalid()),
+        };
+        interval = interval.checked_add(&part)?;
+    }
+    Ok(interval)
+}
+
+fn interval_from_seconds(seconds: f64) -> IntervalValue {
+    IntervalValue::new(0, 0, (seconds * MICROS_PER_SECOND as f64).round() as i64)
+}
+
+pub fn format_date(days: i32) -> String {
+    match date_to_naive(days) {
+        Ok(date) => date.format("%Y-%m-%d").to_string(),
+        Err(_) => format!("<invalid date {days}>"),
+    }
+}
+
+pub fn format_time(micros: i64) -> String {
+    match time_to_naive(micros) {
+        Ok(time) => time.format("%H:%M:%S%.f").to_string(),
+        Err(_) => format!("<invalid time {micros}>"),
+    }
+}
+
+pub fn format_timestamp(micros: i64) -> String {
+    match timestamp_to_naive(micros) {
+        Ok(datetime) => datetime.format("%Y-%m-%d %H:%M:%S%.f").to_string(),
+        Err(_) => format!("<invalid timestamp {micros}>"),
+    }
+}
+
+pub fn now() -> i64 {
+    naive_to_timestamp(Utc::now().naive_utc())
+}
+
+pub fn date_to_timestamp(days: i32) -> i64 {
+    days as i64 * MICROS_PER_DAY
+}
+
+pub fn timestamp_to_date(micros: i64) -> BustubxResult<i32> {
+    i32::try_from(micros.div_euclid(MICROS_PER_DAY)).map_err(|_| out_of_range())
+}
+
+pub fn timestamp_to_time(micros: i64) -> i64 {
+    micros.rem_euclid(MICROS_PER_DAY)
+}
+
+/// Add months first (clamping to the last day of the month), then days, then the rest.
+pub fn timestamp_add_interval(micros: i64, interval: &IntervalValue) -> BustubxResult<i64> {
+    let mut datetime = timestamp_to_naive(micros)?;
+    datetime = if interval.months >= 0 {
+        datetime.checked_add_months(Months::new(interval.months as u32))
+    } else {
+        datetime.checked_sub_months(Months::new(interval.months.unsigned_abs()))
+    }
+    .ok_or_else(out_of_range)?;
+    datetime = datetime
+        .checked_add_signed(Duration::days(interval.days as i64))
+        .ok_or_else(out_of_range)?;
+    naive_to_timestamp(datetime)
+        .checked_add(interval.micros)
+        .ok_or_else(out_of_range)
+}
+
+/// Time of day arithmetic wraps around midnight and ignores months and days.
+pub fn time_add_interval(micros: i64, interval: &IntervalValue) -> i64 {
+    (micros + interval.micros % MICROS_PER_DAY).rem_euclid(MICROS_PER_DAY)
+}
+
+/// The difference between two timestamps as days and time.
+pub fn timestamp_diff(left: i64, right: i64) -> BustubxResult<IntervalValue> {
+    let micros = left.checked_sub(right).ok_or_else(out_of_range)?;
+    let days = i32::try_from(micros / MICROS_PER_DAY).map_err(|_| out_of_range())?;
+    Ok(IntervalValue::new(0, days, micros % MICROS_PER_DAY))
+}
+
+/// Truncate a timestamp to the precision of `field`, e.g. `month`.
+pub fn date_trunc(field: &str, micros: i64) -> BustubxResult<i64> {
+    let datetime = timestamp_to_naive(micros)?;
+    let date = datetime.date();
+    let truncated = match field.to_lowercase().as_str() {
+        "microsecond" | "microseconds" => datetime,
+        "millisecond" | "milliseconds" => datetime
+            .with_nanosecond(datetime.nanosecond() / 1_000_000 * 1_000_000)
+            .ok_or_else(out_of_range)?,
+        "second" => datetime.with_nanosecond(0).ok_or_else(out_of_range)?,
+        "minute" => date
+            .and_hms_opt(datetime.hour(), datetime.minute(), 0)
+            .ok_or_else(out_of_range)?,
+        "hour" => date
+            .and_hms_opt(datetime.hour(), 0, 0)
+            .ok_or_else(out_of_range)?,
+        "day" => start_of_day(date)?,
+        "week" => {
+            start_of_day(date - Duration::days(date.weekday().num_days_from_monday() as i64))?
+        }
+        "month" => start_of_day(first_of_month(date.year(), date.month())?)?,
+        "quarter" => start_of_day(first_of_month(date.year(), (date.month() - 1) / 3 * 3 + 1)?)?,
+        "year" => start_of_day(first_of_month(date.year(), 1)?)?,
+        "decade" => start_of_day(first_of_month(date.year().div_euclid(10) * 10, 1)?)?,
+        "century" => start_of_day(first_of_month(
+            (date.year() - 1).div_euclid(100) * 100 + 1,
+            1,
+        )?)?,
+        "millennium" => start_of_day(first_of_month(
+            (date.year() - 1).div_euclid(1000) * 1000 + 1,
+            1,
+        )?)?,
+        _ => {
+            return Err(BustubxError::Execution(format!(
+                "date_trunc unit '{}' not recognized",
+                field
+            )))
+        }
+    };
+    Ok(naive_to_timestamp(truncated))
+}
+
+fn first_of_month(year: i32, month: u32) -> BustubxResult<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(out_of_range)
+}
+
+fn start_of_day(date: NaiveDate) -> BustubxResult<NaiveDateTime> {
+    date.and_hms_opt(0, 0, 0).ok_or_else(out_of_range)
+}
+
+/// Extract `field` from a timestamp like postgres `date_part`.
+pub fn timestamp_part(field: &str, micros: i64) -> BustubxResult<f64> {
+    let datetime = timestamp_to_naive(micros)?;
+    let date = datetime.date();
+    let value = match field.to_lowercase().as_str() {
+        "year" => date.year() as f64,
+        "month" => date.month() as f64,
+        "day" => date.day() as f64,
+        "quarter" => ((date.month() - 1) / 3 + 1) as f64,
+        "week" => date.iso_week().week() as f64,
+        "isoyear" => date.iso_week().year() as f64,
+        "dow" => date.weekday().num_days_from_sunday() as f64,
+        "isodow" => date.weekday().number_from_monday() as f64,
+        "doy" => date.ordinal() as f64,
+        "decade" => date.year().div_euclid(10) as f64,
+        "century" => ((date.year() - 1).div_euclid(100) + 1) as f64,
+        "millennium" => ((date.year() - 1).div_euclid(1000) + 1) as f64,
+        "epoch" => micros as f64 / MICROS_PER_SECOND as f64,
+        _ => return time_part(field, timestamp_to_time(micros)),
+    };
+    Ok(value)
+}
+
+/// Extract `field` from a time of day.
+pub fn time_part(field: &str, micros: i64) -> BustubxResult<f64> {
+    let time = time_to_naive(micros)?;
+    let second_micros = (micros % (60 * MICROS_PER_SECOND)) as f64;
+    let value = match field.to_lowercase().as_str() {
+        "hour" => time.hour() as f64,
+        "minute" => time.minute() as f64,
+        "second" => second_micros / MICROS_PER_SECOND as f64,
+        "millisecond" | "milliseconds" => second_micros / 1000.0,
+        "microsecond" | "microseconds" => second_micros,
+        "epoch" => micros as f64 / MICROS_PER_SECOND as f64,
+        _ => {
+            return Err(BustubxError::Execution(format!(
+                "unit '{}' not recognized for date_part",
+                field
+            )))
+        }
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::temporal::{self, IntervalValue, MICROS_PER_DAY, MICROS_PER_SECOND};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn temporal_parse_and_format() {
+        assert_eq!(temporal::parse_date("1970-01-02").unwrap(), 1);
+        assert_eq!(temporal::parse_date("1969-12-31").unwrap(), -1);
+        assert_eq!(
+            temporal::format_date(temporal::parse_date("2024-02-29").unwrap()),
+            "2024-02-29"
+        );
+        assert!(temporal::parse_date("2023-02-29").is_err());
+
+        let ts = temporal::parse_timestamp("2024-01-15 10:30:00.25").unwrap();
+        assert_eq!(temporal::format_timestamp(ts), "2024-01-15 10:30:00.250");
+        assert_eq!(
+            temporal::parse_timestamp("2024-01-15").unwrap(),
+            temporal::parse_timestamp("2024-01-15T00:00:00").unwrap()
+        );
+        assert_eq!(
+            temporal::parse_time("01:02:03").unwrap(),
+            3723 * MICROS_PER_SECOND
+        );
+
+        assert_eq!(
+            temporal::parse_interval("1 year 2 months 3 days").unwrap(),
+            IntervalValue::new(14, 3, 0)
+        );
+        assert_eq!(
+            temporal::parse_interval("90 minutes").unwrap(),
+            IntervalValue::new(0, 0, 5400 * MICROS_PER_SECOND)
+        );
+        assert_eq!(
+            temporal::parse_interval("1 day 01:00:00.5")
+                .unwrap()
+                .to_string(),
+            "1 day 01:00:00.5"
+        );
+        assert_eq!(
+            temporal::parse_interval("-2 mons").unwrap().to_string(),
+            "-2 mons"
+        );
+        assert!(temporal::parse_interval("3 fortnights").is_err());
+        assert!(IntervalValue::new(1, 0, 0) > IntervalValue::new(0, 29, MICROS_PER_DAY - 1));
+        assert_ne!(
+            IntervalValue::new(1, 0, 0).cmp(&IntervalValue::new(0, 30, 0)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn temporal_arithmetic() {
+        let ts = temporal::parse_timestamp("2024-01-31 12:00:00").unwrap();
+        let one_month = IntervalValue::new(1, 0, 0);
+        assert_eq!(
+            temporal::format_timestamp(temporal::timestamp_add_interval(ts, &one_month).unwrap()),
+            "2024-02-29 12:00:00"
+        );
+        assert_eq!(
+            temporal::format_timestamp(
+                temporal::timestamp_add_interval(ts, &one_month.negate().unwrap()).unwrap()
+            ),
+            "2023-12-31 12:00:00"
+        );
+        let later = temporal::parse_timestamp("2024-02-02 18:00:00").unwrap();
+        assert_eq!(
+            temporal::timestamp_diff(later, ts).unwrap().to_string(),
+            "2 days 06:00:00"
+        );
+        assert_eq!(
+            temporal::format_timestamp(temporal::date_trunc("month", ts).unwrap()),
+            "2024-01-01 00:00:00"
+        );
+        assert_eq!(
+            temporal::format_timestamp(temporal::date_trunc("week", ts).unwrap()),
+            "2024-01-29 00:00:00"
+        );
+        assert!(temporal::date_trunc("fortnight", ts).is_err());
+        assert_eq!(temporal::timestamp_part("year", ts).unwrap(), 2024.0);
+        assert_eq!(temporal::timestamp_part("dow", ts).unwrap(), 3.0);
+        assert_eq!(temporal::timestamp_part("hour", ts).unwrap(), 12.0);
+        assert_eq!(
+            temporal::time_add_interval(
+                temporal::parse_time("23:00:00").unwrap(),
+                &IntervalValue::new(0, 0, 2 * 3600 * MICROS_PER_SECOND)
+            ),
+            3600 * MICROS_PER_SECOND
+        );
+    }
+}
@@ -5,6 +5,7 @@ use tempfile::TempDir;
 use crate::buffer::TABLE_HEAP_BUFFER_POOL_SIZE;
 use crate::common::util::{pretty_format_logical_plan, pretty_format_physical_plan};
 use crate::error::{BustubxError, BustubxResult};
+use crate::expression::{FunctionRegistry, ScalarUdf};
 use crate::optimizer::LogicalOptimizer;
 use crate::planner::logical_plan::LogicalPlan;
 use crate::planner::PhysicalPlanner;
@@ -19,6 +20,7 @@ use crate::{
 pub struct Database {
     disk_manager: Arc<DiskManager>,
     catalog: Catalog,
+    function_registry: FunctionRegistry,
     temp_dir: Option<TempDir>,
 }
 impl Database {
@@ -31,6 +33,7 @@ impl Database {
         Ok(Self {
             disk_manager,
             catalog,
+            function_registry: FunctionRegistry::default(),
             temp_dir: None,
         })
     }
@@ -48,10 +51,16 @@ impl Database {
         Ok(Self {
             disk_manager,
             catalog,
+            function_registry: FunctionRegistry::default(),
             temp_dir: Some(temp_dir),
         })
     }
 
+    /// Register a user defined scalar function that can be called by name in SQL.
+    pub fn register_udf(&mut self, udf: ScalarUdf) -> BustubxResult<()> {
+        self.function_registry.register_udf(udf)
+    }
+
     pub fn run(&mut self, sql: &str) -> BustubxResult<Vec<Tuple>> {
         // 把sql转换为逻辑计划
         let v: Vec<Tuple> = vec![];
@@ -140,6 +149,8 @@ impl Database {
         let mut planner = LogicalPlanner {
             context: PlannerContext {
                 catalog: &self.catalog,
+                function_registry: &self.function_registry,
+                outer_query_schema: None,
             },
         };
         // 把抽象语法树转换为逻辑计划
@@ -147,3 +158,83 @@ impl Database {
         planner.plan(&stmt)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::rid::Rid;
+    use crate::storage::Tuple;
+    use crate::{Database, ScalarValue};
+
+    // CREATE INDEX does not build the index yet, so fill it from the rows of the table
+    fn create_index(db: &mut Database, table_name: &str, key_attrs: Vec<usize>) {
+        db.catalog.create_index(
+            format!("{}_idx", table_name),
+            table_name.to_string(),
+            key_attrs.clone(),
+        );
+        let table_info = db.catalog.get_mut_table_by_name(table_name).unwrap();
+        let mut rows = vec![];
+        let mut iterator = table_info.table.iter(None, None);
+        while let Some((rid, meta, tuple)) = iterator.next_with_rid(&mut table_info.table) {
+            if !meta.is_deleted {
+                rows.push((rid, tuple));
+            }
+        }
+        let index_info = db.catalog.get_mut_table_indexes(table_name).pop().unwrap();
+        for (rid, tuple) in rows {
+            let key = Tuple::new(
+                index_info.key_schema.clone(),
+                key_attrs.iter().map(|i| tuple.data[*i].clone()).collect(),
+            );
+            index_info.index.insert(&key, rid);
+        }
+    }
+
+    fn index_get(db: &mut Database, table_name: &str, key: ScalarValue) -> Option<Tuple> {
+        let index_info = db.catalog.get_mut_table_indexes(table_name).pop().unwrap();
+        let key = Tuple::new(index_info.key_schema.clone(), vec![key]);
+        let rid: Rid = index_info.index.get(&key)?;
+        let table_info = db.catalog.get_mut_table_by_name(table_name).unwrap();
+        let (meta, tuple) = table_info.table.get_tuple(rid);
+        assert!(!meta.is_deleted);
+        Some(tuple)
+    }
+
+    #[test]
+    fn delete_maintains_index() {
+        let mut db = Database::new_temp().unwrap();
+        db.run("create table t (a int, b int)").unwrap();
+        db.run("insert into t values (1, 10), (2, 20), (2, 30)")
+            .unwrap();
+        create_index(&mut db, "t", vec![0]);
+
+        db.run("delete from t where a = 1").unwrap();
+        assert_eq!(index_get(&mut db, "t", 1i32.into()), None);
+
+        // the other row with the same key keeps its entry
+        db.run("delete from t where b = 30").unwrap();
+        let tuple = index_get(&mut db, "t", 2i32.into()).unwrap();
+        assert_eq!(tuple.data, vec![2i32.into(), 20i32.into()]);
+    }
+
+    #[test]
+    fn update_maintains_index() {
+        let mut db = Database::new_temp().unwrap();
+        db.run("create table t (a int, b int)").unwrap();
+        db.run("insert into t values (1, 10), (2, 20), (2, 30)")
+            .unwrap();
+        create_index(&mut db, "t", vec![0]);
+
+        db.run("update t set a = 3 where a = 1").unwrap();
+        assert_eq!(index_get(&mut db, "t", 1i32.into()), None);
+        let tuple = index_get(&mut db, "t", 3i32.into()).unwrap();
+        assert_eq!(tuple.data, vec![3i32.into(), 10i32.into()]);
+
+        // the entry of the other row with the same key still points at it
+        db.run("update t set a = 4, b = 40 where b = 30").unwrap();
+        let tuple = index_get(&mut db, "t", 2i32.into()).unwrap();
+        assert_eq!(tuple.data, vec![2i32.into(), 20i32.into()]);
+        let tuple = index_get(&mut db, "t", 4i32.into()).unwrap();
+        assert_eq!(tuple.data, vec![4i32.into(), 40i32.into()]);
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+use crate::catalog::SchemaRef;
+use crate::common::ScalarValue;
+use crate::expression::{Accumulator, AggregateFunction, Expr, ExprTrait};
+use crate::{
+    execution::{ExecutionContext, VolcanoExecutor},
+    storage::Tuple,
+    BustubxError, BustubxResult,
+};
+
+use super::PhysicalPlan;
+
+/// The key of a group and the accumulators of its aggregate functions
+type Group = (Vec<ScalarValue>, Vec<Box<dyn Accumulator>>);
+
+/// Hash aggregation, all input rows are consumed by the first `next` call. Groups are emitted
+/// in the order they were first seen.
+#[derive(Debug)]
+pub struct PhysicalAggregate {
+    pub input: Arc<PhysicalPlan>,
+    pub group_exprs: Vec<Expr>,
+    pub aggr_exprs: Vec<Expr>,
+    pub schema: SchemaRef,
+
+    output: Mutex<Option<VecDeque<Tuple>>>,
+}
+
+impl PhysicalAggregate {
+    pub fn new(
+        input: Arc<PhysicalPlan>,
+        group_exprs: Vec<Expr>,
+        aggr_exprs: Vec<Expr>,
+        schema: SchemaRef,
+    ) -> Self {
+        PhysicalAggregate {
+            input,
+            group_exprs,
+            aggr_exprs,
+            schema,
+            output: Mutex::new(None),
+        }
+    }
+
+    fn aggregate_functions(&self) -> BustubxResult<Vec<&AggregateFunction>> {
+        self.aggr_exprs
+            .iter()
+            .map(|expr| match expr {
+                Expr::AggregateFunction(aggregate) => Ok(aggregate),
+                expr => Err(BustubxError::Internal(format!(
+                    "{} is not an aggregate function",
+                    expr
+                ))),
+            })
+            .collect()
+    }
+
+    fn create_accumulators(
+        &self,
+        aggregates: &[&AggregateFunction],
+    ) -> BustubxResult<Vec<Box<dyn Accumulator>>> {
+        let input_schema = self.input.output_schema();
+        aggregates
+            .iter()
+            .map(|aggregate| aggregate.create_accumulator(&input_schema))
+            .collect()
+    }
+
+    fn compute_groups(&self, context: &mut ExecutionContext) -> BustubxResult<VecDeque<Tuple>> {
+        let aggregates = self.aggregate_functions()?;
+        let mut group_index: HashMap<Vec<ScalarValue>, usize> = HashMap::new();
+        let mut groups: Vec<Group> = vec![];
+        // without GROUP BY there is exactly one group, even for no input rows
+        if self.group_exprs.is_empty() {
+            group_index.insert(vec![], 0);
+            groups.push((vec![], self.create_accumulators(&aggregates)?));
+        }
+
+        while let Some(tuple) = self.input.next(context)? {
+            let key = self
+                .group_exprs
+                .iter()
+                .map(|expr| expr.evaluate(&tuple))
+                .collect::<BustubxResult<Vec<ScalarValue>>>()?;
+            let index = match group_index.get(&key) {
+                Some(index) => *index,
+                None => {
+                    group_index.insert(key.clone(), groups.len());
+                    groups.push((key, self.create_accumulators(&aggregates)?));
+                    groups.len() - 1
+                }
+            };
+            let accumulators = &mut groups[index].1;
+            for (aggregate, accumulator) in aggregates.iter().zip(accumulators.iter_mut()) {
+                accumulator.update(aggregate.evaluate_arg(&tuple)?)?;
+            }
+        }
+
+        let mut output = VecDeque::with_capacity(groups.len());
+        for (mut values, accumulators) in groups {
+            for accumulator in accumulators.iter() {
+                values.push(accumulator.evaluate()?);
+            }
+            output.push_back(Tuple::new(self.schema.clone(), values));
+        }
+        Ok(output)
+    }
+}
+
+impl VolcanoExecutor for PhysicalAggregate {
+    fn init(&self, context: &mut ExecutionContext) -> BustubxResult<()> {
+        debug!("init aggregate executor");
+        *self.output.lock().unwrap() = None;
+        self.input.init(context)
+    }
+
+    fn next(&self, context: &mut ExecutionContext) -> BustubxResult<Option<Tuple>> {
+        let mut output = self.output.lock().unwrap();
+        if output.is_none() {
+            *output = Some(self.compute_groups(context)?);
+        }
+        Ok(output.as_mut().and_then(|output| output.pop_front()))
+    }
+
+    fn output_schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl std::fmt::Display for PhysicalAggregate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HashAggregate")
+    }
+}
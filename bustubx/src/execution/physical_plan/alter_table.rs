@@ -0,0 +1,54 @@
+use crate::catalog::{SchemaRef, EMPTY_SCHEMA_REF};
+use crate::common::TableReference;
+use crate::expression::ExprTrait;
+use crate::planner::logical_plan::AlterTableOperation;
+use crate::{
+    execution::{ExecutionContext, VolcanoExecutor},
+    storage::Tuple,
+    BustubxResult,
+};
+
+#[derive(derive_new::new, Debug)]
+pub struct PhysicalAlterTable {
+    pub table: TableReference,
+    pub operation: AlterTableOperation,
+}
+
+impl VolcanoExecutor for PhysicalAlterTable {
+    fn next(&self, context: &mut ExecutionContext) -> BustubxResult<Option<Tuple>> {
+        let table_name = self.table.table();
+        match &self.operation {
+            AlterTableOperation::AddColumn { column, default } => {
+                let value = default
+                    .evaluate(&Tuple::empty(EMPTY_SCHEMA_REF.clone()))?
+                    .cast_to(&column.data_type)?;
+                context
+                    .catalog
+                    .add_column(table_name, column.clone(), value)?;
+            }
+            AlterTableOperation::DropColumn { index } => {
+                context.catalog.drop_column(table_name, *index)?;
+            }
+            AlterTableOperation::RenameColumn { index, new_name } => {
+                context
+                    .catalog
+                    .rename_column(table_name, *index, new_name.clone())?;
+            }
+            AlterTableOperation::RenameTable { new_table } => {
+                context
+                    .catalog
+                    .rename_table(table_name, new_table.clone())?;
+            }
+        }
+        Ok(None)
+    }
+    fn output_schema(&self) -> SchemaRef {
+        EMPTY_SCHEMA_REF.clone()
+    }
+}
+
+impl std::fmt::Display for PhysicalAlterTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AlterTable")
+    }
+}
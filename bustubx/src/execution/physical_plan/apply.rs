@@ -0,0 +1,192 @@
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+use crate::catalog::SchemaRef;
+use crate::common::ScalarValue;
+use crate::expression::type_coercion::common_type;
+use crate::expression::{Cast, Exists, Expr, ExprTrait, InList, InSubquery, Literal, Subquery};
+use crate::planner::build_plan;
+use crate::planner::logical_plan::{
+    plan_has_outer_references, replace_outer_references, LogicalPlan,
+};
+use crate::{
+    execution::{ExecutionContext, VolcanoExecutor},
+    storage::Tuple,
+    BustubxError, BustubxResult,
+};
+
+use super::PhysicalPlan;
+
+/// Appends the value of a subquery expression to every input row. A correlated subquery is
+/// planned and executed again for every row with its outer references replaced by the values
+/// of that row, the rows of an uncorrelated one are computed once.
+#[derive(Debug)]
+pub struct PhysicalApply {
+    pub input: Arc<PhysicalPlan>,
+    pub subquery: Expr,
+    pub schema: SchemaRef,
+
+    correlated: bool,
+    cached_rows: Mutex<Option<Arc<Vec<Tuple>>>>,
+    /// The IN list of an uncorrelated IN subquery, so its values are cast and hashed once
+    cached_in_list: Mutex<Option<Arc<InList>>>,
+}
+
+impl PhysicalApply {
+    pub fn new(input: Arc<PhysicalPlan>, subquery: Expr, schema: SchemaRef) -> Self {
+        let correlated = match &subquery {
+            Expr::ScalarSubquery(subquery)
+            | Expr::Exists(Exists { subquery, .. })
+            | Expr::InSubquery(InSubquery { subquery, .. }) => {
+                plan_has_outer_references(&subquery.subquery)
+            }
+            _ => false,
+        };
+        PhysicalApply {
+            input,
+            subquery,
+            schema,
+            correlated,
+            cached_rows: Mutex::new(None),
+            cached_in_list: Mutex::new(None),
+        }
+    }
+
+    /// The rows of `subquery` for the outer row `tuple`, at most `limit` of a correlated one.
+    fn subquery_rows(
+        &self,
+        subquery: &Subquery,
+        tuple: &Tuple,
+        limit: Option<usize>,
+        context: &mut ExecutionContext,
+    ) -> BustubxResult<Arc<Vec<Tuple>>> {
+        if self.correlated {
+            let plan = replace_outer_references(&subquery.subquery, tuple)?;
+            return Ok(Arc::new(execute_subquery(plan, limit, context)?));
+        }
+        let mut cached_rows = self.cached_rows.lock().unwrap();
+        if cached_rows.is_none() {
+            let plan = subquery.subquery.as_ref().clone();
+            *cached_rows = Some(Arc::new(execute_subquery(plan, None, context)?));
+        }
+        Ok(cached_rows.clone().unwrap())
+    }
+
+    fn evaluate_subquery(
+        &self,
+        tuple: &Tuple,
+        context: &mut ExecutionContext,
+    ) -> BustubxResult<ScalarValue> {
+        match &self.subquery {
+            Expr::ScalarSubquery(subquery) => {
+                let rows = self.subquery_rows(subquery, tuple, Some(2), context)?;
+                match rows.as_slice() {
+                    [] => Ok(ScalarValue::new_empty(subquery.output_type()?)),
+                    [row] => row.value(0).cloned(),
+                    _ => Err(BustubxError::Execution(
+                        "more than one row returned by a subquery used as an expression"
+                            .to_string(),
+                    )),
+                }
+            }
+            Expr::Exists(Exists { subquery, negated }) => {
+                let rows = self.subquery_rows(subquery, tuple, Some(1), context)?;
+                Ok(ScalarValue::Boolean(Some(rows.is_empty() == *negated)))
+            }
+            Expr::InSubquery(in_subquery) => {
+                if self.correlated {
+                    let rows = self.subquery_rows(&in_subquery.subquery, tuple, None, context)?;
+                    return self.in_list(in_subquery, &rows)?.evaluate(tuple);
+                }
+                let mut cached_in_list = self.cached_in_list.lock().unwrap();
+                if cached_in_list.is_none() {
+                    let plan = in_subquery.subquery.subquery.as_ref().clone();
+                    let rows = execute_subquery(plan, None, context)?;
+                    *cached_in_list = Some(Arc::new(self.in_list(in_subquery, &rows)?));
+                }
+                cached_in_list.as_ref().unwrap().evaluate(tuple)
+            }
+            expr => Err(BustubxError::Internal(format!(
+                "apply expects a subquery but got {}",
+                expr
+            ))),
+        }
+    }
+
+    /// `expr [NOT] IN` the values of `rows`, both sides cast to their common type.
+    fn in_list(&self, in_subquery: &InSubquery, rows: &[Tuple]) -> BustubxResult<InList> {
+        let InSubquery {
+            expr,
+            subquery,
+            negated,
+        } = in_subquery;
+        let value_type = expr.data_type(&self.input.output_schema())?;
+        let subquery_type = subquery.output_type()?;
+        let compare_type = common_type(value_type, subquery_type).ok_or_else(|| {
+            BustubxError::Execution(format!(
+                "operator = is not supported for {} and {}",
+                value_type, subquery_type
+            ))
+        })?;
+        let list = rows
+            .iter()
+            .map(|row| {
+                Ok(Expr::Literal(Literal {
+                    value: row.value(0)?.cast_to(&compare_type)?,
+                }))
+            })
+            .collect::<BustubxResult<Vec<Expr>>>()?;
+        let expr = Expr::Cast(Cast {
+            expr: expr.clone(),
+            data_type: compare_type,
+        });
+        // same NULL semantics as an IN list of the subquery values
+        Ok(InList::new(Box::new(expr), list, *negated))
+    }
+}
+
+fn execute_subquery(
+    plan: LogicalPlan,
+    limit: Option<usize>,
+    context: &mut ExecutionContext,
+) -> BustubxResult<Vec<Tuple>> {
+    let plan = build_plan(Arc::new(plan));
+    plan.init(context)?;
+    let mut rows = vec![];
+    while limit.is_none_or(|limit| rows.len() < limit) {
+        match plan.next(context)? {
+            Some(tuple) => rows.push(tuple),
+            None => break,
+        }
+    }
+    Ok(rows)
+}
+
+impl VolcanoExecutor for PhysicalApply {
+    fn init(&self, context: &mut ExecutionContext) -> BustubxResult<()> {
+        debug!("init apply executor");
+        *self.cached_rows.lock().unwrap() = None;
+        *self.cached_in_list.lock().unwrap() = None;
+        self.input.init(context)
+    }
+
+    fn next(&self, context: &mut ExecutionContext) -> BustubxResult<Option<Tuple>> {
+        let Some(tuple) = self.input.next(context)? else {
+            return Ok(None);
+        };
+        let value = self.evaluate_subquery(&tuple, context)?;
+        let mut data = tuple.data;
+        data.push(value);
+        Ok(Some(Tuple::new(self.schema.clone(), data)))
+    }
+
+    fn output_schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl std::fmt::Display for PhysicalApply {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Apply")
+    }
+}
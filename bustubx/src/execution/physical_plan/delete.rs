@@ -0,0 +1,99 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::catalog::{SchemaRef, DELETE_OUTPUT_SCHEMA_REF};
+use crate::common::rid::Rid;
+use crate::common::TableReference;
+use crate::{
+    common::ScalarValue,
+    execution::{ExecutionContext, VolcanoExecutor},
+    storage::{Tuple, TupleMeta},
+    BustubxError, BustubxResult,
+};
+
+use super::PhysicalPlan;
+
+/// Marks the rows produced by the input deleted and removes them from the indexes of the
+/// table. Rows are identified by the rid the input carries in its last column. All input rows
+/// are collected before the table is changed, so the input never sees its own deletes.
+#[derive(Debug)]
+pub struct PhysicalDelete {
+    pub table: TableReference,
+    pub input: Arc<PhysicalPlan>,
+
+    done: AtomicBool,
+}
+
+impl PhysicalDelete {
+    pub fn new(table: TableReference, input: Arc<PhysicalPlan>) -> Self {
+        Self {
+            table,
+            input,
+            done: AtomicBool::new(false),
+        }
+    }
+}
+
+impl VolcanoExecutor for PhysicalDelete {
+    fn init(&self, context: &mut ExecutionContext) -> BustubxResult<()> {
+        debug!("init delete executor");
+        self.input.init(context)?;
+        self.done.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn next(&self, context: &mut ExecutionContext) -> BustubxResult<Option<Tuple>> {
+        if self.done.swap(true, Ordering::SeqCst) {
+            return Ok(None);
+        }
+
+        // the input yields the rows of the table followed by their rid
+        let mut deleted = vec![];
+        while let Some(mut tuple) = self.input.next(context)? {
+            let rid_value = tuple
+                .data
+                .pop()
+                .ok_or_else(|| BustubxError::Internal("Delete input row has no rid".to_string()))?;
+            deleted.push((Rid::try_from_scalar(&rid_value)?, tuple));
+        }
+
+        let table_info = context
+            .catalog
+            .get_mut_table_by_name(self.table.table())
+            .ok_or_else(|| BustubxError::Execution(format!("table {} not found", self.table)))?;
+        for (rid, _) in deleted.iter() {
+            let meta = TupleMeta {
+                is_deleted: true,
+                ..table_info.table.get_tuple_meta(*rid)
+            };
+            table_info.table.update_tuple_meta(&meta, *rid);
+        }
+
+        for index_info in context.catalog.get_mut_table_indexes(self.table.table()) {
+            let key_attrs = index_info.index.index_metadata.key_attrs.clone();
+            for (rid, tuple) in deleted.iter() {
+                let key = Tuple::new(
+                    index_info.key_schema.clone(),
+                    key_attrs.iter().map(|i| tuple.data[*i].clone()).collect(),
+                );
+                index_info.index.delete_entry(&key, *rid);
+            }
+        }
+
+        Ok(Some(Tuple::new(
+            self.output_schema(),
+            vec![ScalarValue::Int32(Some(deleted.len() as i32))],
+        )))
+    }
+
+    fn output_schema(&self) -> SchemaRef {
+        DELETE_OUTPUT_SCHEMA_REF.clone()
+    }
+}
+
+impl std::fmt::Display for PhysicalDelete {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Delete")
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+use crate::catalog::SchemaRef;
+use crate::common::ScalarValue;
+use crate::{
+    execution::{ExecutionContext, VolcanoExecutor},
+    storage::Tuple,
+    BustubxResult,
+};
+
+use super::PhysicalPlan;
+
+/// Hash based duplicate elimination, a row is emitted the first time it is seen.
+#[derive(Debug)]
+pub struct PhysicalDistinct {
+    pub input: Arc<PhysicalPlan>,
+
+    seen: Mutex<HashSet<Vec<ScalarValue>>>,
+}
+
+impl PhysicalDistinct {
+    pub fn new(input: Arc<PhysicalPlan>) -> Self {
+        PhysicalDistinct {
+            input,
+            seen: Mutex::new(HashSet::new()),
+        }
+    }
+}
+
+impl VolcanoExecutor for PhysicalDistinct {
+    fn init(&self, context: &mut ExecutionContext) -> BustubxResult<()> {
+        debug!("init distinct executor");
+        self.seen.lock().unwrap().clear();
+        self.input.init(context)
+    }
+
+    fn next(&self, context: &mut ExecutionContext) -> BustubxResult<Option<Tuple>> {
+        while let Some(tuple) = self.input.next(context)? {
+            if self.seen.lock().unwrap().insert(tuple.data.clone()) {
+                return Ok(Some(tuple));
+            }
+        }
+        Ok(None)
+    }
+
+    fn output_schema(&self) -> SchemaRef {
+        self.input.output_schema()
+    }
+}
+
+impl std::fmt::Display for PhysicalDistinct {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Distinct")
+    }
+}
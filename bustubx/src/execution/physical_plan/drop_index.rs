@@ -0,0 +1,33 @@
+use crate::catalog::{SchemaRef, EMPTY_SCHEMA_REF};
+use crate::common::TableReference;
+use crate::{
+    execution::{ExecutionContext, VolcanoExecutor},
+    storage::Tuple,
+    BustubxError, BustubxResult,
+};
+
+#[derive(derive_new::new, Debug)]
+pub struct PhysicalDropIndex {
+    pub indexes: Vec<(TableReference, String)>,
+    pub if_exists: bool,
+}
+
+impl VolcanoExecutor for PhysicalDropIndex {
+    fn next(&self, context: &mut ExecutionContext) -> BustubxResult<Option<Tuple>> {
+        for (table, name) in self.indexes.iter() {
+            if !context.catalog.drop_index(table.table(), name) && !self.if_exists {
+                return Err(BustubxError::Execution(format!("index {} not found", name)));
+            }
+        }
+        Ok(None)
+    }
+    fn output_schema(&self) -> SchemaRef {
+        EMPTY_SCHEMA_REF.clone()
+    }
+}
+
+impl std::fmt::Display for PhysicalDropIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DropIndex")
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
     common::ScalarValue,
     execution::{ExecutionContext, VolcanoExecutor},
     storage::Tuple,
-    BustubxResult,
+    BustubxError, BustubxResult,
 };
 
 use super::PhysicalPlan;
@@ -31,13 +31,16 @@ impl VolcanoExecutor for PhysicalFilter {
                 return Ok(None);
             }
             let tuple = next_tuple.unwrap();
-            let compare_res = self.predicate.evaluate(&tuple)?;
-            if let ScalarValue::Boolean(Some(v)) = compare_res {
-                if v {
-                    return Ok(Some(tuple));
+            // NULL is treated as false
+            match self.predicate.evaluate(&tuple)? {
+                ScalarValue::Boolean(Some(true)) => return Ok(Some(tuple)),
+                ScalarValue::Boolean(_) => {}
+                value => {
+                    return Err(BustubxError::Execution(format!(
+                        "filter predicate should be boolean but got {}",
+                        value
+                    )))
                 }
-            } else {
-                panic!("filter predicate should be boolean")
             }
         }
     }
@@ -7,7 +7,7 @@ use crate::{
     common::ScalarValue,
     execution::{ExecutionContext, VolcanoExecutor},
     storage::{Tuple, TupleMeta},
-    BustubxResult,
+    BustubxError, BustubxResult,
 };
 
 use super::PhysicalPlan;
@@ -16,22 +16,15 @@ use super::PhysicalPlan;
 pub struct PhysicalInsert {
     pub table: TableReference,
     pub table_schema: SchemaRef,
-    pub projected_schema: SchemaRef,
     pub input: Arc<PhysicalPlan>,
 
     insert_rows: AtomicU32,
 }
 impl PhysicalInsert {
-    pub fn new(
-        table: TableReference,
-        table_schema: SchemaRef,
-        projected_schema: SchemaRef,
-        input: Arc<PhysicalPlan>,
-    ) -> Self {
+    pub fn new(table: TableReference, table_schema: SchemaRef, input: Arc<PhysicalPlan>) -> Self {
         Self {
             table,
             table_schema,
-            projected_schema,
             input,
             insert_rows: AtomicU32::new(0),
         }
@@ -67,11 +60,17 @@ impl VolcanoExecutor for PhysicalInsert {
             // cast values
             let mut casted_data = vec![];
             for (idx, value) in tuple.data.iter().enumerate() {
-                casted_data
-                    .push(value.cast_to(&self.projected_schema.column_with_index(idx)?.data_type)?);
+                let column = self.table_schema.column_with_index(idx)?;
+                if value.is_null() && !column.nullable {
+                    return Err(BustubxError::Execution(format!(
+                        "null value in column {} violates not-null constraint",
+                        column.name
+                    )));
+                }
+                casted_data.push(value.cast_to(&column.data_type)?);
             }
             let tuple = Tuple {
-                schema: self.projected_schema.clone(),
+                schema: self.table_schema.clone(),
                 data: casted_data,
             };
 
@@ -86,8 +85,7 @@ impl VolcanoExecutor for PhysicalInsert {
                 delete_txn_id: 0,
                 is_deleted: false,
             };
-            // TODO check result
-            table_heap.insert_tuple(&tuple_meta, &tuple);
+            table_heap.insert_tuple(&tuple_meta, &tuple)?;
             self.insert_rows
                 .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         }
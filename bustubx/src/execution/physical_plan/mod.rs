@@ -1,5 +1,12 @@
+mod aggregate;
+mod alter_table;
+mod apply;
 mod create_index;
 mod create_table;
+mod delete;
+mod distinct;
+mod drop_index;
+mod drop_table;
 mod empty;
 mod filter;
 mod insert;
@@ -7,11 +14,20 @@ mod limit;
 mod nested_loop_join;
 mod project;
 mod seq_scan;
+mod set_operation;
 mod sort;
+mod update;
 mod values;
 
+pub use aggregate::PhysicalAggregate;
+pub use alter_table::PhysicalAlterTable;
+pub use apply::PhysicalApply;
 pub use create_index::PhysicalCreateIndex;
 pub use create_table::PhysicalCreateTable;
+pub use delete::PhysicalDelete;
+pub use distinct::PhysicalDistinct;
+pub use drop_index::PhysicalDropIndex;
+pub use drop_table::PhysicalDropTable;
 pub use empty::PhysicalEmpty;
 pub use filter::PhysicalFilter;
 pub use insert::PhysicalInsert;
@@ -19,7 +35,9 @@ pub use limit::PhysicalLimit;
 pub use nested_loop_join::PhysicalNestedLoopJoin;
 pub use project::PhysicalProject;
 pub use seq_scan::PhysicalSeqScan;
+pub use set_operation::PhysicalSetOperation;
 pub use sort::PhysicalSort;
+pub use update::PhysicalUpdate;
 pub use values::PhysicalValues;
 
 use crate::catalog::SchemaRef;
@@ -31,9 +49,16 @@ use crate::{
 
 #[derive(Debug)]
 pub enum PhysicalPlan {
+    Aggregate(PhysicalAggregate),
+    Apply(PhysicalApply),
+    Delete(PhysicalDelete),
+    Distinct(PhysicalDistinct),
     Empty(PhysicalEmpty),
     CreateTable(PhysicalCreateTable),
     CreateIndex(PhysicalCreateIndex),
+    DropTable(PhysicalDropTable),
+    DropIndex(PhysicalDropIndex),
+    AlterTable(PhysicalAlterTable),
     Project(PhysicalProject),
     Filter(PhysicalFilter),
     TableScan(PhysicalSeqScan),
@@ -42,11 +67,17 @@ pub enum PhysicalPlan {
     Values(PhysicalValues),
     NestedLoopJoin(PhysicalNestedLoopJoin),
     Sort(PhysicalSort),
+    SetOperation(PhysicalSetOperation),
+    Update(PhysicalUpdate),
 }
 
 impl PhysicalPlan {
     pub fn inputs(&self) -> Vec<&PhysicalPlan> {
         match self {
+            PhysicalPlan::Aggregate(PhysicalAggregate { input, .. }) => vec![input],
+            PhysicalPlan::Apply(PhysicalApply { input, .. }) => vec![input],
+            PhysicalPlan::Delete(PhysicalDelete { input, .. }) => vec![input],
+            PhysicalPlan::Distinct(PhysicalDistinct { input, .. }) => vec![input],
             PhysicalPlan::Project(PhysicalProject { input, .. }) => vec![input],
             PhysicalPlan::Filter(PhysicalFilter { input, .. }) => vec![input],
             PhysicalPlan::Limit(PhysicalLimit { input, .. }) => vec![input],
@@ -57,9 +88,18 @@ impl PhysicalPlan {
                 ..
             }) => vec![left_input, right_input],
             PhysicalPlan::Sort(PhysicalSort { input, .. }) => vec![input],
+            PhysicalPlan::Update(PhysicalUpdate { input, .. }) => vec![input],
+            PhysicalPlan::SetOperation(PhysicalSetOperation {
+                left_input,
+                right_input,
+                ..
+            }) => vec![left_input, right_input],
             PhysicalPlan::Empty(_)
             | PhysicalPlan::CreateTable(_)
             | PhysicalPlan::CreateIndex(_)
+            | PhysicalPlan::DropTable(_)
+            | PhysicalPlan::DropIndex(_)
+            | PhysicalPlan::AlterTable(_)
             | PhysicalPlan::TableScan(_)
             | PhysicalPlan::Values(_) => vec![],
         }
@@ -69,9 +109,16 @@ impl PhysicalPlan {
 impl VolcanoExecutor for PhysicalPlan {
     fn init(&self, context: &mut ExecutionContext) -> BustubxResult<()> {
         match self {
+            PhysicalPlan::Aggregate(op) => op.init(context),
+            PhysicalPlan::Apply(op) => op.init(context),
+            PhysicalPlan::Delete(op) => op.init(context),
+            PhysicalPlan::Distinct(op) => op.init(context),
             PhysicalPlan::Empty(op) => op.init(context),
             PhysicalPlan::CreateTable(op) => op.init(context),
             PhysicalPlan::CreateIndex(op) => op.init(context),
+            PhysicalPlan::DropTable(op) => op.init(context),
+            PhysicalPlan::DropIndex(op) => op.init(context),
+            PhysicalPlan::AlterTable(op) => op.init(context),
             PhysicalPlan::Insert(op) => op.init(context),
             PhysicalPlan::Values(op) => op.init(context),
             PhysicalPlan::Project(op) => op.init(context),
@@ -80,14 +127,23 @@ impl VolcanoExecutor for PhysicalPlan {
             PhysicalPlan::Limit(op) => op.init(context),
             PhysicalPlan::NestedLoopJoin(op) => op.init(context),
             PhysicalPlan::Sort(op) => op.init(context),
+            PhysicalPlan::SetOperation(op) => op.init(context),
+            PhysicalPlan::Update(op) => op.init(context),
         }
     }
 
     fn next(&self, context: &mut ExecutionContext) -> BustubxResult<Option<Tuple>> {
         match self {
+            PhysicalPlan::Aggregate(op) => op.next(context),
+            PhysicalPlan::Apply(op) => op.next(context),
+            PhysicalPlan::Delete(op) => op.next(context),
+            PhysicalPlan::Distinct(op) => op.next(context),
             PhysicalPlan::Empty(op) => op.next(context),
             PhysicalPlan::CreateTable(op) => op.next(context),
             PhysicalPlan::CreateIndex(op) => op.next(context),
+            PhysicalPlan::DropTable(op) => op.next(context),
+            PhysicalPlan::DropIndex(op) => op.next(context),
+            PhysicalPlan::AlterTable(op) => op.next(context),
             PhysicalPlan::Insert(op) => op.next(context),
             PhysicalPlan::Values(op) => op.next(context),
             PhysicalPlan::Project(op) => op.next(context),
@@ -96,14 +152,23 @@ impl VolcanoExecutor for PhysicalPlan {
             PhysicalPlan::Limit(op) => op.next(context),
             PhysicalPlan::NestedLoopJoin(op) => op.next(context),
             PhysicalPlan::Sort(op) => op.next(context),
+            PhysicalPlan::SetOperation(op) => op.next(context),
+            PhysicalPlan::Update(op) => op.next(context),
         }
     }
 
     fn output_schema(&self) -> SchemaRef {
         match self {
+            Self::Aggregate(op) => op.output_schema(),
+            Self::Apply(op) => op.output_schema(),
+            Self::Delete(op) => op.output_schema(),
+            Self::Distinct(op) => op.output_schema(),
             Self::Empty(op) => op.output_schema(),
             Self::CreateTable(op) => op.output_schema(),
             Self::CreateIndex(op) => op.output_schema(),
+            Self::DropTable(op) => op.output_schema(),
+            Self::DropIndex(op) => op.output_schema(),
+            Self::AlterTable(op) => op.output_schema(),
             Self::Insert(op) => op.output_schema(),
             Self::Values(op) => op.output_schema(),
             Self::Project(op) => op.output_schema(),
@@ -112,6 +177,8 @@ impl VolcanoExecutor for PhysicalPlan {
             Self::Limit(op) => op.output_schema(),
             Self::NestedLoopJoin(op) => op.output_schema(),
             Self::Sort(op) => op.output_schema(),
+            Self::SetOperation(op) => op.output_schema(),
+            Self::Update(op) => op.output_schema(),
         }
     }
 }
@@ -119,9 +186,16 @@ impl VolcanoExecutor for PhysicalPlan {
 impl std::fmt::Display for PhysicalPlan {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::Aggregate(op) => write!(f, "{op}"),
+            Self::Apply(op) => write!(f, "{op}"),
+            Self::Delete(op) => write!(f, "{op}"),
+            Self::Distinct(op) => write!(f, "{op}"),
             Self::Empty(op) => write!(f, "{op}"),
             Self::CreateTable(op) => write!(f, "{op}"),
             Self::CreateIndex(op) => write!(f, "{op}"),
+            Self::DropTable(op) => write!(f, "{op}"),
+            Self::DropIndex(op) => write!(f, "{op}"),
+            Self::AlterTable(op) => write!(f, "{op}"),
             Self::Insert(op) => write!(f, "{op}"),
             Self::Values(op) => write!(f, "{op}"),
             Self::Project(op) => write!(f, "{op}"),
@@ -130,6 +204,8 @@ impl std::fmt::Display for PhysicalPlan {
             Self::Limit(op) => write!(f, "{op}"),
             Self::NestedLoopJoin(op) => write!(f, "{op}"),
             Self::Sort(op) => write!(f, "{op}"),
+            Self::SetOperation(op) => write!(f, "{op}"),
+            Self::Update(op) => write!(f, "{op}"),
         }
     }
 }
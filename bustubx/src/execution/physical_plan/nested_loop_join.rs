@@ -9,7 +9,7 @@ use crate::{
     execution::{ExecutionContext, VolcanoExecutor},
     planner::logical_plan::JoinType,
     storage::Tuple,
-    BustubxResult,
+    BustubxError, BustubxResult,
 };
 
 use super::PhysicalPlan;
@@ -22,8 +22,22 @@ pub struct PhysicalNestedLoopJoin {
     pub right_input: Arc<PhysicalPlan>,
     pub schema: SchemaRef,
 
-    left_tuple: Mutex<Option<Tuple>>,
+    state: Mutex<JoinState>,
 }
+
+#[derive(Debug, Default)]
+struct JoinState {
+    /// The current left row and whether a right row matched it so far
+    left_tuple: Option<(Tuple, bool)>,
+    /// The position of the next right row for the current left row
+    right_index: usize,
+    /// Whether the right row at each position matched any left row, for right and full joins
+    right_matched: Vec<bool>,
+    /// The position of the next right row to check for NULL padding, set once the left input
+    /// is exhausted
+    unmatched_right_index: Option<usize>,
+}
+
 impl PhysicalNestedLoopJoin {
     pub fn new(
         join_type: JoinType,
@@ -38,67 +52,141 @@ impl PhysicalNestedLoopJoin {
             left_input,
             right_input,
             schema,
-            left_tuple: Mutex::new(None),
+            state: Mutex::new(JoinState::default()),
+        }
+    }
+}
+impl PhysicalNestedLoopJoin {
+    /// Semi and anti joins emit each left row at most once, without any right columns.
+    fn next_semi_anti(&self, context: &mut ExecutionContext) -> BustubxResult<Option<Tuple>> {
+        while let Some(left_tuple) = self.left_input.next(context)? {
+            let mut matched = false;
+            while let Some(right_tuple) = self.right_input.next(context)? {
+                if self.matches(&left_tuple, right_tuple)? {
+                    matched = true;
+                    break;
+                }
+            }
+            // reset right executor
+            self.right_input.init(context)?;
+            if matched == (self.join_type == JoinType::LeftSemi) {
+                return Ok(Some(left_tuple));
+            }
+        }
+        Ok(None)
+    }
+
+    fn matches(&self, left_tuple: &Tuple, right_tuple: Tuple) -> BustubxResult<bool> {
+        let Some(condition) = self.condition.as_ref() else {
+            return Ok(true);
+        };
+        let merged_tuple = Tuple::try_merge(vec![left_tuple.clone(), right_tuple])?;
+        // NULL is treated as false
+        match condition.evaluate(&merged_tuple)? {
+            ScalarValue::Boolean(v) => Ok(v == Some(true)),
+            value => Err(BustubxError::Execution(format!(
+                "nested loop join condition should be boolean but got {}",
+                value
+            ))),
+        }
+    }
+
+    /// Build an output row, a missing side is padded with NULL values.
+    fn output_tuple(&self, left: Option<&Tuple>, right: Option<&Tuple>) -> Tuple {
+        let nulls = |schema: SchemaRef| {
+            schema
+                .columns
+                .iter()
+                .map(|col| ScalarValue::new_empty(col.data_type))
+                .collect::<Vec<ScalarValue>>()
+        };
+        let mut data = match left {
+            Some(tuple) => tuple.data.clone(),
+            None => nulls(self.left_input.output_schema()),
+        };
+        match right {
+            Some(tuple) => data.extend(tuple.data.iter().cloned()),
+            None => data.extend(nulls(self.right_input.output_schema())),
+        }
+        Tuple::new(self.schema.clone(), data)
+    }
+
+    /// The right rows which did not match any left row, once the left input is exhausted.
+    fn next_unmatched_right(
+        &self,
+        context: &mut ExecutionContext,
+        state: &mut JoinState,
+    ) -> BustubxResult<Option<Tuple>> {
+        while let Some(right_tuple) = self.right_input.next(context)? {
+            let index = state.unmatched_right_index.unwrap_or(0);
+            state.unmatched_right_index = Some(index + 1);
+            if !state.right_matched.get(index).copied().unwrap_or(false) {
+                return Ok(Some(self.output_tuple(None, Some(&right_tuple))));
+            }
         }
+        Ok(None)
     }
 }
+
 impl VolcanoExecutor for PhysicalNestedLoopJoin {
     fn init(&self, context: &mut ExecutionContext) -> BustubxResult<()> {
         debug!("init nested loop join executor");
         self.left_input.init(context)?;
         self.right_input.init(context)?;
-        *self.left_tuple.lock().unwrap() = None;
+        *self.state.lock().unwrap() = JoinState::default();
         Ok(())
     }
     fn next(&self, context: &mut ExecutionContext) -> BustubxResult<Option<Tuple>> {
-        let left_tuple = self.left_tuple.lock().unwrap();
-        let mut left_next_tuple = if left_tuple.is_none() {
-            self.left_input.next(context)?
-        } else {
-            Some(left_tuple.clone().unwrap())
-        };
-        // release mutex
-        drop(left_tuple);
-
-        while left_next_tuple.is_some() {
-            let left_tuple = left_next_tuple.clone().unwrap();
-
-            let mut right_next_tuple = self.right_input.next(context)?;
-            while right_next_tuple.is_some() {
-                let right_tuple = right_next_tuple.unwrap();
-
-                // TODO judge if matches
-                if self.condition.is_none() {
-                    // save latest left_next_result before return
-                    *self.left_tuple.lock().unwrap() = Some(left_tuple.clone());
-
-                    return Ok(Some(Tuple::try_merge(vec![left_tuple, right_tuple])?));
-                } else {
-                    let condition = self.condition.clone().unwrap();
-                    let merged_tuple =
-                        Tuple::try_merge(vec![left_tuple.clone(), right_tuple.clone()])?;
-                    let evaluate_res = condition.evaluate(&merged_tuple)?;
-                    // TODO support left/right join after null support added
-                    if let ScalarValue::Boolean(Some(v)) = evaluate_res {
-                        if v {
-                            // save latest left_next_result before return
-                            *self.left_tuple.lock().unwrap() = Some(left_tuple.clone());
+        if matches!(self.join_type, JoinType::LeftSemi | JoinType::LeftAnti) {
+            return self.next_semi_anti(context);
+        }
+        let pad_left = matches!(self.join_type, JoinType::LeftOuter | JoinType::FullOuter);
+        let pad_right = matches!(self.join_type, JoinType::RightOuter | JoinType::FullOuter);
 
-                            return Ok(Some(Tuple::try_merge(vec![left_tuple, right_tuple])?));
-                        }
-                    } else {
-                        panic!("nested loop join condition should be boolean")
+        let mut state = self.state.lock().unwrap();
+        if state.unmatched_right_index.is_some() {
+            return self.next_unmatched_right(context, &mut state);
+        }
+        loop {
+            let Some((left_tuple, left_matched)) = state.left_tuple.clone() else {
+                match self.left_input.next(context)? {
+                    Some(left_tuple) => {
+                        state.left_tuple = Some((left_tuple, false));
+                        state.right_index = 0;
+                        continue;
                     }
+                    None if pad_right => {
+                        state.unmatched_right_index = Some(0);
+                        return self.next_unmatched_right(context, &mut state);
+                    }
+                    None => return Ok(None),
                 }
+            };
 
-                right_next_tuple = self.right_input.next(context)?;
+            let Some(right_tuple) = self.right_input.next(context)? else {
+                // reset right executor
+                self.right_input.init(context)?;
+                state.left_tuple = None;
+                if pad_left && !left_matched {
+                    return Ok(Some(self.output_tuple(Some(&left_tuple), None)));
+                }
+                continue;
+            };
+            let index = state.right_index;
+            state.right_index += 1;
+            if self.matches(&left_tuple, right_tuple.clone())? {
+                state.left_tuple = Some((left_tuple.clone(), true));
+                if pad_right {
+                    if state.right_matched.len() <= index {
+                        state.right_matched.resize(index + 1, false);
+                    }
+                    state.right_matched[index] = true;
+                }
+                return Ok(Some(
+                    self.output_tuple(Some(&left_tuple), Some(&right_tuple)),
+                ));
             }
-
-            // reset right executor
-            self.right_input.init(context)?;
-            left_next_tuple = self.left_input.next(context)?;
         }
-        return Ok(None);
     }
 
     fn output_schema(&self) -> SchemaRef {
@@ -13,15 +13,18 @@ use crate::{
 pub struct PhysicalSeqScan {
     pub table: TableReference,
     pub table_schema: SchemaRef,
+    /// Appends the rid of every row, `table_schema` then ends with the rid column
+    pub with_rid: bool,
 
     iterator: Mutex<TableIterator>,
 }
 
 impl PhysicalSeqScan {
-    pub fn new(table: TableReference, table_schema: SchemaRef) -> Self {
+    pub fn new(table: TableReference, table_schema: SchemaRef, with_rid: bool) -> Self {
         PhysicalSeqScan {
             table,
             table_schema,
+            with_rid,
             iterator: Mutex::new(TableIterator::new(None, None)),
         }
     }
@@ -46,8 +49,16 @@ impl VolcanoExecutor for PhysicalSeqScan {
             .get_mut_table_by_name(self.table.table())
             .unwrap();
         let mut iterator = self.iterator.lock().unwrap();
-        let full_tuple = iterator.next(&mut table_info.table);
-        return Ok(full_tuple.map(|t| t.1));
+        while let Some((rid, meta, mut tuple)) = iterator.next_with_rid(&mut table_info.table) {
+            if !meta.is_deleted {
+                if self.with_rid {
+                    tuple.data.push(rid.to_scalar());
+                    tuple.schema = self.table_schema.clone();
+                }
+                return Ok(Some(tuple));
+            }
+        }
+        Ok(None)
     }
 
     fn output_schema(&self) -> SchemaRef {
@@ -0,0 +1,168 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+use crate::catalog::SchemaRef;
+use crate::common::ScalarValue;
+use crate::planner::logical_plan::SetOperator;
+use crate::{
+    execution::{ExecutionContext, VolcanoExecutor},
+    storage::Tuple,
+    BustubxResult,
+};
+
+use super::PhysicalPlan;
+
+/// Hash based UNION, INTERSECT and EXCEPT. UNION streams the left input and then the right
+/// one, INTERSECT and EXCEPT count the rows of the right input on the first `next` call and
+/// stream the left input against the counts.
+#[derive(Debug)]
+pub struct PhysicalSetOperation {
+    pub op: SetOperator,
+    pub all: bool,
+    pub left_input: Arc<PhysicalPlan>,
+    pub right_input: Arc<PhysicalPlan>,
+    pub schema: SchemaRef,
+
+    state: Mutex<SetOperationState>,
+}
+
+#[derive(Debug, Default)]
+struct SetOperationState {
+    left_done: bool,
+    /// How often every row occurs in the right input, not yet matched by a left row
+    right_counts: Option<HashMap<Vec<ScalarValue>, usize>>,
+    /// Rows emitted so far, only kept without ALL
+    emitted: HashSet<Vec<ScalarValue>>,
+}
+
+impl PhysicalSetOperation {
+    pub fn new(
+        op: SetOperator,
+        all: bool,
+        left_input: Arc<PhysicalPlan>,
+        right_input: Arc<PhysicalPlan>,
+        schema: SchemaRef,
+    ) -> Self {
+        PhysicalSetOperation {
+            op,
+            all,
+            left_input,
+            right_input,
+            schema,
+            state: Mutex::new(SetOperationState::default()),
+        }
+    }
+
+    /// Cast the values of an input row to the common column types.
+    fn cast_row(&self, tuple: Tuple) -> BustubxResult<Vec<ScalarValue>> {
+        tuple
+            .data
+            .into_iter()
+            .zip(self.schema.columns.iter())
+            .map(|(value, column)| value.cast_to(&column.data_type))
+            .collect()
+    }
+
+    fn count_right_rows(
+        &self,
+        context: &mut ExecutionContext,
+    ) -> BustubxResult<HashMap<Vec<ScalarValue>, usize>> {
+        let mut counts = HashMap::new();
+        while let Some(tuple) = self.right_input.next(context)? {
+            *counts.entry(self.cast_row(tuple)?).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    fn next_union(
+        &self,
+        context: &mut ExecutionContext,
+        state: &mut SetOperationState,
+    ) -> BustubxResult<Option<Vec<ScalarValue>>> {
+        loop {
+            let tuple = if state.left_done {
+                self.right_input.next(context)?
+            } else {
+                self.left_input.next(context)?
+            };
+            let Some(tuple) = tuple else {
+                if state.left_done {
+                    return Ok(None);
+                }
+                state.left_done = true;
+                continue;
+            };
+            let row = self.cast_row(tuple)?;
+            if self.all || state.emitted.insert(row.clone()) {
+                return Ok(Some(row));
+            }
+        }
+    }
+
+    fn next_intersect_or_except(
+        &self,
+        context: &mut ExecutionContext,
+        state: &mut SetOperationState,
+    ) -> BustubxResult<Option<Vec<ScalarValue>>> {
+        if state.right_counts.is_none() {
+            state.right_counts = Some(self.count_right_rows(context)?);
+        }
+        let right_counts = state.right_counts.as_mut().unwrap();
+        while let Some(tuple) = self.left_input.next(context)? {
+            let row = self.cast_row(tuple)?;
+            // with ALL every right row matches at most one left row
+            let matched = match right_counts.get_mut(&row) {
+                Some(count) if *count > 0 => {
+                    if self.all {
+                        *count -= 1;
+                    }
+                    true
+                }
+                _ => false,
+            };
+            let keep = match self.op {
+                SetOperator::Intersect => matched,
+                _ => !matched,
+            };
+            if keep && (self.all || state.emitted.insert(row.clone())) {
+                return Ok(Some(row));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl VolcanoExecutor for PhysicalSetOperation {
+    fn init(&self, context: &mut ExecutionContext) -> BustubxResult<()> {
+        debug!("init set operation executor");
+        *self.state.lock().unwrap() = SetOperationState::default();
+        self.left_input.init(context)?;
+        self.right_input.init(context)
+    }
+
+    fn next(&self, context: &mut ExecutionContext) -> BustubxResult<Option<Tuple>> {
+        let mut state = self.state.lock().unwrap();
+        let row = match self.op {
+            SetOperator::Union => self.next_union(context, &mut state)?,
+            SetOperator::Intersect | SetOperator::Except => {
+                self.next_intersect_or_except(context, &mut state)?
+            }
+        };
+        Ok(row.map(|row| Tuple::new(self.schema.clone(), row)))
+    }
+
+    fn output_schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl std::fmt::Display for PhysicalSetOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Hash{}", self.op)?;
+        if self.all {
+            write!(f, " All")?;
+        }
+        Ok(())
+    }
+}
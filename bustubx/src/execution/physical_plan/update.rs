@@ -0,0 +1,129 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::catalog::{SchemaRef, UPDATE_OUTPUT_SCHEMA_REF};
+use crate::common::rid::Rid;
+use crate::common::TableReference;
+use crate::{
+    common::ScalarValue,
+    execution::{ExecutionContext, VolcanoExecutor},
+    storage::Tuple,
+    BustubxError, BustubxResult,
+};
+
+use super::PhysicalPlan;
+
+/// Replaces the rows produced by the input with their new values and updates the indexes of
+/// the table. Rows are identified by the rid the input carries in its last column. The input is
+/// collected before the table is changed, so no row is updated twice even though new versions
+/// are appended to the table.
+#[derive(Debug)]
+pub struct PhysicalUpdate {
+    pub table: TableReference,
+    pub table_schema: SchemaRef,
+    pub input: Arc<PhysicalPlan>,
+
+    done: AtomicBool,
+}
+
+impl PhysicalUpdate {
+    pub fn new(table: TableReference, table_schema: SchemaRef, input: Arc<PhysicalPlan>) -> Self {
+        Self {
+            table,
+            table_schema,
+            input,
+            done: AtomicBool::new(false),
+        }
+    }
+
+    /// Cast the new values of a row to the column types, checking not-null constraints.
+    fn new_row(&self, values: Vec<ScalarValue>) -> BustubxResult<Vec<ScalarValue>> {
+        values
+            .into_iter()
+            .zip(self.table_schema.columns.iter())
+            .map(|(value, column)| {
+                if value.is_null() && !column.nullable {
+                    return Err(BustubxError::Execution(format!(
+                        "null value in column {} violates not-null constraint",
+                        column.name
+                    )));
+                }
+                value.cast_to(&column.data_type)
+            })
+            .collect()
+    }
+}
+
+impl VolcanoExecutor for PhysicalUpdate {
+    fn init(&self, context: &mut ExecutionContext) -> BustubxResult<()> {
+        debug!("init update executor");
+        self.input.init(context)?;
+        self.done.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn next(&self, context: &mut ExecutionContext) -> BustubxResult<Option<Tuple>> {
+        if self.done.swap(true, Ordering::SeqCst) {
+            return Ok(None);
+        }
+
+        // the old row, its new values and its rid
+        let column_count = self.table_schema.column_count();
+        let mut targets = vec![];
+        while let Some(tuple) = self.input.next(context)? {
+            let mut old_row = tuple.data;
+            let rid_value = old_row
+                .pop()
+                .ok_or_else(|| BustubxError::Internal("Update input row has no rid".to_string()))?;
+            let new_row = self.new_row(old_row.split_off(column_count))?;
+            targets.push((
+                Rid::try_from_scalar(&rid_value)?,
+                Tuple::new(self.table_schema.clone(), old_row),
+                Tuple::new(self.table_schema.clone(), new_row),
+            ));
+        }
+
+        let table_info = context
+            .catalog
+            .get_mut_table_by_name(self.table.table())
+            .ok_or_else(|| BustubxError::Execution(format!("table {} not found", self.table)))?;
+        let mut updated = vec![];
+        for (rid, old_tuple, new_tuple) in targets {
+            let new_rid = table_info.table.update_tuple(rid, &new_tuple)?;
+            updated.push((rid, old_tuple, new_tuple, new_rid));
+        }
+
+        // the new versions have new rids, so every index changes
+        for index_info in context.catalog.get_mut_table_indexes(self.table.table()) {
+            let key_attrs = index_info.index.index_metadata.key_attrs.clone();
+            let key = |tuple: &Tuple| {
+                Tuple::new(
+                    index_info.key_schema.clone(),
+                    key_attrs.iter().map(|i| tuple.data[*i].clone()).collect(),
+                )
+            };
+            for (rid, old_tuple, new_tuple, new_rid) in updated.iter() {
+                let old_key = key(old_tuple);
+                let new_key = key(new_tuple);
+                index_info.index.delete_entry(&old_key, *rid);
+                index_info.index.insert(&new_key, *new_rid);
+            }
+        }
+
+        Ok(Some(Tuple::new(
+            self.output_schema(),
+            vec![ScalarValue::Int32(Some(updated.len() as i32))],
+        )))
+    }
+
+    fn output_schema(&self) -> SchemaRef {
+        UPDATE_OUTPUT_SCHEMA_REF.clone()
+    }
+}
+
+impl std::fmt::Display for PhysicalUpdate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Update")
+    }
+}
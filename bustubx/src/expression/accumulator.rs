@@ -0,0 +1,268 @@
+use crate::catalog::DataType;
+use crate::common::{decimal, ScalarValue};
+use crate::{BustubxError, BustubxResult};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// The running state of an aggregate function for one group of rows.
+pub trait Accumulator: std::fmt::Debug + Send {
+    /// Add the value of the next row of the group.
+    fn update(&mut self, value: ScalarValue) -> BustubxResult<()>;
+
+    /// The result for the rows added so far.
+    fn evaluate(&self) -> BustubxResult<ScalarValue>;
+}
+
+#[derive(Debug, Default)]
+pub struct CountAccumulator {
+    count: i64,
+}
+
+impl Accumulator for CountAccumulator {
+    fn update(&mut self, value: ScalarValue) -> BustubxResult<()> {
+        if !value.is_null() {
+            self.count += 1;
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self) -> BustubxResult<ScalarValue> {
+        Ok(ScalarValue::Int64(Some(self.count)))
+    }
+}
+
+/// Sums values of an Int64, Float64 or Decimal128 result type, NULL for no values.
+#[derive(Debug)]
+pub struct SumAccumulator {
+    data_type: DataType,
+    sum: ScalarValue,
+}
+
+impl SumAccumulator {
+    pub fn new(data_type: DataType) -> Self {
+        Self {
+            data_type,
+            sum: ScalarValue::new_empty(data_type),
+        }
+    }
+}
+
+fn out_of_range(data_type: DataType) -> BustubxError {
+    BustubxError::Execution(format!("{} out of range", data_type))
+}
+
+impl Accumulator for SumAccumulator {
+    fn update(&mut self, value: ScalarValue) -> BustubxResult<()> {
+        if value.is_null() {
+            return Ok(());
+        }
+        let value = value.cast_to(&self.data_type)?;
+        self.sum = match (&self.sum, value) {
+            (ScalarValue::Int64(None), value) | (ScalarValue::Float64(None), value) => value,
+            (ScalarValue::Decimal128(None, _, _), value) => value,
+            (ScalarValue::Int64(Some(sum)), ScalarValue::Int64(Some(v))) => {
+                ScalarValue::Int64(Some(
+                    sum.checked_add(v)
+                        .ok_or_else(|| out_of_range(self.data_type))?,
+                ))
+            }
+            (ScalarValue::Float64(Some(sum)), ScalarValue::Float64(Some(v))) => {
+                ScalarValue::Float64(Some(sum + v))
+            }
+            (
+                ScalarValue::Decimal128(Some(sum), precision, scale),
+                ScalarValue::Decimal128(Some(v), _, _),
+            ) => {
+                let sum = sum
+                    .checked_add(v)
+                    .ok_or_else(|| out_of_range(self.data_type))?;
+                ScalarValue::Decimal128(
+                    Some(decimal::validate_precision(sum, *precision)?),
+                    *precision,
+                    *scale,
+                )
+            }
+            (sum, value) => {
+                return Err(BustubxError::Internal(format!(
+                    "can not add {} to sum {}",
+                    value, sum
+                )))
+            }
+        };
+        Ok(())
+    }
+
+    fn evaluate(&self) -> BustubxResult<ScalarValue> {
+        Ok(self.sum.clone())
+    }
+}
+
+/// The average as Float64, or as Decimal128 for decimal values.
+#[derive(Debug)]
+pub struct AvgAccumulator {
+    data_type: DataType,
+    sum: SumAccumulator,
+    count: i64,
+}
+
+impl AvgAccumulator {
+    /// Decimal values are summed with their own scale, `sum_type` is the type of `sum(x)`.
+    pub fn new(data_type: DataType, sum_type: DataType) -> Self {
+        let sum_type = match sum_type {
+            DataType::Decimal128(_, _) => sum_type,
+            _ => DataType::Float64,
+        };
+        Self {
+            data_type,
+            sum: SumAccumulator::new(sum_type),
+            count: 0,
+        }
+    }
+}
+
+impl Accumulator for AvgAccumulator {
+    fn update(&mut self, value: ScalarValue) -> BustubxResult<()> {
+        if value.is_null() {
+            return Ok(());
+        }
+        self.count += 1;
+        self.sum.update(value)
+    }
+
+    fn evaluate(&self) -> BustubxResult<ScalarValue> {
+        if self.count == 0 {
+            return Ok(ScalarValue::new_empty(self.data_type));
+        }
+        match (self.sum.evaluate()?, self.data_type) {
+            (ScalarValue::Float64(Some(sum)), _) => {
+                Ok(ScalarValue::Float64(Some(sum / self.count as f64)))
+            }
+            (ScalarValue::Decimal128(Some(sum), _, sum_scale), DataType::Decimal128(p, s)) => {
+                let avg = decimal::div(sum, sum_scale, self.count as i128, 0, s)?;
+                Ok(ScalarValue::Decimal128(Some(avg), p, s))
+            }
+            (sum, data_type) => Err(BustubxError::Internal(format!(
+                "can not compute the {} average of sum {}",
+                data_type, sum
+            ))),
+        }
+    }
+}
+
+/// The smallest or largest value, NULL values are ignored.
+#[derive(Debug)]
+pub struct MinMaxAccumulator {
+    value: ScalarValue,
+    is_min: bool,
+}
+
+impl MinMaxAccumulator {
+    pub fn new(data_type: DataType, is_min: bool) -> Self {
+        Self {
+            value: ScalarValue::new_empty(data_type),
+            is_min,
+        }
+    }
+}
+
+impl Accumulator for MinMaxAccumulator {
+    fn update(&mut self, value: ScalarValue) -> BustubxResult<()> {
+        if value.is_null() {
+            return Ok(());
+        }
+        let replace = self.value.is_null()
+            || match value.partial_cmp(&self.value) {
+                Some(Ordering::Less) => self.is_min,
+                Some(Ordering::Greater) => !self.is_min,
+                Some(Ordering::Equal) => false,
+                None => {
+                    return Err(BustubxError::Execution(format!(
+                        "can not compare {} with {}",
+                        value, self.value
+                    )))
+                }
+            };
+        if replace {
+            self.value = value;
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self) -> BustubxResult<ScalarValue> {
+        Ok(self.value.clone())
+    }
+}
+
+/// Passes only the first occurrence of every value to `inner`, for `sum(DISTINCT x)` and the
+/// like. NULL values are ignored.
+#[derive(Debug)]
+pub struct DistinctAccumulator {
+    inner: Box<dyn Accumulator>,
+    seen: HashSet<ScalarValue>,
+}
+
+impl DistinctAccumulator {
+    pub fn new(inner: Box<dyn Accumulator>) -> Self {
+        Self {
+            inner,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl Accumulator for DistinctAccumulator {
+    fn update(&mut self, value: ScalarValue) -> BustubxResult<()> {
+        if value.is_null() || self.seen.contains(&value) {
+            return Ok(());
+        }
+        self.seen.insert(value.clone());
+        self.inner.update(value)
+    }
+
+    fn evaluate(&self) -> BustubxResult<ScalarValue> {
+        self.inner.evaluate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Accumulator, AvgAccumulator, CountAccumulator, DistinctAccumulator, MinMaxAccumulator,
+        SumAccumulator,
+    };
+    use crate::catalog::DataType;
+    use crate::common::ScalarValue;
+
+    #[test]
+    fn accumulators() {
+        let mut sum = SumAccumulator::new(DataType::Int64);
+        assert_eq!(sum.evaluate().unwrap(), ScalarValue::Int64(None));
+        for v in [Some(1), None, Some(2)] {
+            sum.update(ScalarValue::Int32(v)).unwrap();
+        }
+        assert_eq!(sum.evaluate().unwrap(), ScalarValue::Int64(Some(3)));
+        sum.update(ScalarValue::Int64(Some(i64::MAX))).unwrap_err();
+
+        let mut avg = AvgAccumulator::new(DataType::Decimal128(38, 6), DataType::Decimal128(38, 2));
+        avg.update(ScalarValue::Decimal128(Some(150), 5, 2))
+            .unwrap();
+        avg.update(ScalarValue::Decimal128(Some(200), 5, 2))
+            .unwrap();
+        avg.update(ScalarValue::Decimal128(Some(300), 5, 2))
+            .unwrap();
+        assert_eq!(avg.evaluate().unwrap().to_string(), "2.166667");
+
+        let mut max = MinMaxAccumulator::new(DataType::Varchar(None), false);
+        for v in ["b", "c", "a"] {
+            max.update(v.into()).unwrap();
+        }
+        max.update(ScalarValue::Varchar(None)).unwrap();
+        assert_eq!(max.evaluate().unwrap(), "c".into());
+
+        let mut count = DistinctAccumulator::new(Box::new(CountAccumulator::default()));
+        for v in [Some(1), Some(2), None, Some(1), None] {
+            count.update(ScalarValue::Int32(v)).unwrap();
+        }
+        assert_eq!(count.evaluate().unwrap(), ScalarValue::Int64(Some(2)));
+    }
+}
@@ -0,0 +1,216 @@
+use crate::catalog::{Column, DataType, Schema};
+use crate::common::decimal::DECIMAL128_MAX_PRECISION;
+use crate::common::ScalarValue;
+use crate::expression::accumulator::{
+    Accumulator, AvgAccumulator, CountAccumulator, DistinctAccumulator, MinMaxAccumulator,
+    SumAccumulator,
+};
+use crate::expression::{Expr, ExprTrait};
+use crate::{BustubxError, BustubxResult, Tuple};
+
+/// Built-in aggregate functions
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BuiltinAggregateFunction {
+    /// `count(x)` counts the values which are not NULL, `count(*)` counts all rows
+    Count,
+    /// `sum(x)`
+    Sum,
+    /// `min(x)`
+    Min,
+    /// `max(x)`
+    Max,
+    /// `avg(x)`
+    Avg,
+}
+
+impl BuiltinAggregateFunction {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "count" => Some(Self::Count),
+            "sum" => Some(Self::Sum),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "avg" => Some(Self::Avg),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Sum => "sum",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Avg => "avg",
+        }
+    }
+
+    /// The type of the result for an argument of type `arg_type`.
+    pub fn return_type(&self, arg_type: DataType) -> BustubxResult<DataType> {
+        let return_type = match (self, arg_type) {
+            (Self::Count, _) => Some(DataType::Int64),
+            (Self::Min | Self::Max, _) => Some(arg_type),
+            (Self::Sum, DataType::UInt64) => {
+                Some(DataType::Decimal128(DECIMAL128_MAX_PRECISION, 0))
+            }
+            (Self::Sum, data_type) if data_type.is_integer() => Some(DataType::Int64),
+            (Self::Sum, DataType::Decimal128(_, scale)) => {
+                Some(DataType::Decimal128(DECIMAL128_MAX_PRECISION, scale))
+            }
+            // at least 6 fractional digits, like the result of a decimal division
+            (Self::Avg, DataType::Decimal128(_, scale)) => {
+                Some(DataType::Decimal128(DECIMAL128_MAX_PRECISION, scale.max(6)))
+            }
+            (Self::Sum | Self::Avg, data_type) if data_type.is_numeric() => Some(DataType::Float64),
+            _ => None,
+        };
+        return_type.ok_or_else(|| {
+            BustubxError::Plan(format!(
+                "function {}({}) does not exist",
+                self.name(),
+                arg_type
+            ))
+        })
+    }
+}
+
+/// A call of an aggregate function. It is computed by an `Aggregate` plan, which replaces it
+/// by a column of its output.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AggregateFunction {
+    pub func: BuiltinAggregateFunction,
+    /// No arguments for `count(*)`
+    pub args: Vec<Expr>,
+    /// Whether duplicate argument values are aggregated once, e.g. `count(DISTINCT x)`
+    pub distinct: bool,
+}
+
+impl AggregateFunction {
+    pub fn try_new(
+        func: BuiltinAggregateFunction,
+        args: Vec<Expr>,
+        distinct: bool,
+    ) -> BustubxResult<Self> {
+        let valid = match func {
+            BuiltinAggregateFunction::Count if !distinct => args.len() <= 1,
+            _ => args.len() == 1,
+        };
+        if !valid {
+            return Err(BustubxError::Plan(format!(
+                "function {} does not accept {} arguments",
+                func.name(),
+                args.len()
+            )));
+        }
+        Ok(Self {
+            func,
+            args,
+            distinct,
+        })
+    }
+
+    fn arg_type(&self, input_schema: &Schema) -> BustubxResult<DataType> {
+        match self.args.first() {
+            Some(arg) => arg.data_type(input_schema),
+            None => Ok(DataType::Int64),
+        }
+    }
+
+    /// The value of a row passed to the accumulator, any non NULL value for `count(*)`.
+    pub fn evaluate_arg(&self, tuple: &Tuple) -> BustubxResult<ScalarValue> {
+        match self.args.first() {
+            Some(arg) => arg.evaluate(tuple),
+            None => Ok(ScalarValue::Boolean(Some(true))),
+        }
+    }
+
+    pub fn create_accumulator(&self, input_schema: &Schema) -> BustubxResult<Box<dyn Accumulator>> {
+        let arg_type = self.arg_type(input_schema)?;
+        let return_type = self.func.return_type(arg_type)?;
+        let accumulator: Box<dyn Accumulator> = match self.func {
+            BuiltinAggregateFunction::Count => Box::new(CountAccumulator::default()),
+            BuiltinAggregateFunction::Sum => Box::new(SumAccumulator::new(return_type)),
+            BuiltinAggregateFunction::Min => Box::new(MinMaxAccumulator::new(return_type, true)),
+            BuiltinAggregateFunction::Max => Box::new(MinMaxAccumulator::new(return_type, false)),
+            BuiltinAggregateFunction::Avg => Box::new(AvgAccumulator::new(
+                return_type,
+                BuiltinAggregateFunction::Sum.return_type(arg_type)?,
+            )),
+        };
+        Ok(if self.distinct {
+            Box::new(DistinctAccumulator::new(accumulator))
+        } else {
+            accumulator
+        })
+    }
+}
+
+impl ExprTrait for AggregateFunction {
+    fn data_type(&self, input_schema: &Schema) -> BustubxResult<DataType> {
+        self.func.return_type(self.arg_type(input_schema)?)
+    }
+
+    fn nullable(&self, _input_schema: &Schema) -> BustubxResult<bool> {
+        // the other functions are NULL for no rows
+        Ok(self.func != BuiltinAggregateFunction::Count)
+    }
+
+    fn evaluate(&self, _tuple: &Tuple) -> BustubxResult<ScalarValue> {
+        Err(BustubxError::Internal(format!(
+            "aggregate function {} can not be evaluated for a single row",
+            self
+        )))
+    }
+
+    fn to_column(&self, input_schema: &Schema) -> BustubxResult<Column> {
+        Ok(Column::new(
+            format!("{self}"),
+            self.data_type(input_schema)?,
+            self.nullable(input_schema)?,
+        ))
+    }
+}
+
+impl std::fmt::Display for AggregateFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.args.first() {
+            Some(arg) if self.distinct => write!(f, "{}(DISTINCT {})", self.func.name(), arg),
+            Some(arg) => write!(f, "{}({})", self.func.name(), arg),
+            None => write!(f, "{}(*)", self.func.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BuiltinAggregateFunction;
+    use crate::catalog::DataType;
+
+    #[test]
+    fn aggregate_return_types() {
+        let sum = BuiltinAggregateFunction::Sum;
+        assert_eq!(sum.return_type(DataType::Int32).unwrap(), DataType::Int64);
+        assert_eq!(
+            sum.return_type(DataType::Decimal128(5, 2)).unwrap(),
+            DataType::Decimal128(38, 2)
+        );
+        assert_eq!(
+            sum.return_type(DataType::Float32).unwrap(),
+            DataType::Float64
+        );
+        assert!(sum.return_type(DataType::Varchar(None)).is_err());
+
+        let avg = BuiltinAggregateFunction::Avg;
+        assert_eq!(avg.return_type(DataType::Int64).unwrap(), DataType::Float64);
+        assert_eq!(
+            avg.return_type(DataType::Decimal128(10, 2)).unwrap(),
+            DataType::Decimal128(38, 6)
+        );
+        assert_eq!(
+            BuiltinAggregateFunction::Max
+                .return_type(DataType::Date)
+                .unwrap(),
+            DataType::Date
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use crate::catalog::{Column, DataType, Schema};
+use crate::common::ScalarValue;
+use crate::expression::binary::{evaluate_comparison, evaluate_logical};
+use crate::expression::{BinaryOp, Expr, ExprTrait};
+use crate::{BustubxResult, Tuple};
+use std::cmp::Ordering;
+
+/// `expr [NOT] BETWEEN low AND high`, the same as `expr >= low AND expr <= high`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Between {
+    pub expr: Box<Expr>,
+    pub negated: bool,
+    pub low: Box<Expr>,
+    pub high: Box<Expr>,
+}
+
+impl ExprTrait for Between {
+    fn data_type(&self, _input_schema: &Schema) -> BustubxResult<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, input_schema: &Schema) -> BustubxResult<bool> {
+        Ok(self.expr.nullable(input_schema)?
+            || self.low.nullable(input_schema)?
+            || self.high.nullable(input_schema)?)
+    }
+
+    fn evaluate(&self, tuple: &Tuple) -> BustubxResult<ScalarValue> {
+        let value = self.expr.evaluate(tuple)?;
+        let low = self.low.evaluate(tuple)?;
+        let high = self.high.evaluate(tuple)?;
+        let above_low =
+            evaluate_comparison(value.clone(), low, &[Ordering::Greater, Ordering::Equal])?;
+        let below_high = evaluate_comparison(value, high, &[Ordering::Less, Ordering::Equal])?;
+        match evaluate_logical(above_low, BinaryOp::And, below_high)? {
+            ScalarValue::Boolean(v) if self.negated => Ok(ScalarValue::Boolean(v.map(|v| !v))),
+            result => Ok(result),
+        }
+    }
+
+    fn to_column(&self, input_schema: &Schema) -> BustubxResult<Column> {
+        Ok(Column::new(
+            format!("{self}"),
+            self.data_type(input_schema)?,
+            self.nullable(input_schema)?,
+        ))
+    }
+}
+
+impl std::fmt::Display for Between {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let not = if self.negated { "NOT " } else { "" };
+        write!(
+            f,
+            "{} {}BETWEEN {} AND {}",
+            self.expr, not, self.low, self.high
+        )
+    }
+}
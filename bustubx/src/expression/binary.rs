@@ -1,7 +1,8 @@
 use crate::catalog::Schema;
 use crate::catalog::{Column, DataType};
-use crate::common::ScalarValue;
+use crate::common::{decimal, temporal, ScalarValue};
 use crate::error::BustubxResult;
+use crate::expression::type_coercion::numeric_coercion;
 use crate::expression::{Expr, ExprTrait};
 use crate::storage::Tuple;
 use crate::BustubxError;
@@ -31,10 +32,11 @@ impl ExprTrait for BinaryExpr {
             | BinaryOp::NotEq
             | BinaryOp::And
             | BinaryOp::Or => Ok(DataType::Boolean),
-            BinaryOp::Plus => todo!(),
-            BinaryOp::Minus => todo!(),
-            BinaryOp::Multiply => todo!(),
-            BinaryOp::Divide => todo!(),
+            BinaryOp::Plus
+            | BinaryOp::Minus
+            | BinaryOp::Multiply
+            | BinaryOp::Divide
+            | BinaryOp::Modulo => arithmetic_result_type(self.op, left_type, right_type),
         }
     }
 
@@ -52,26 +54,32 @@ impl ExprTrait for BinaryExpr {
             BinaryOp::LtEq => evaluate_comparison(l, r, &vec![Ordering::Less, Ordering::Equal]),
             BinaryOp::Eq => evaluate_comparison(l, r, &vec![Ordering::Equal]),
             BinaryOp::NotEq => evaluate_comparison(l, r, &vec![Ordering::Greater, Ordering::Less]),
-            _ => Err(BustubxError::NotSupport(format!(
-                "binary operator {:?} not support evaluating yet",
-                self.op
-            ))),
+            BinaryOp::Plus
+            | BinaryOp::Minus
+            | BinaryOp::Multiply
+            | BinaryOp::Divide
+            | BinaryOp::Modulo => evaluate_arithmetic(l, self.op, r),
+            BinaryOp::And | BinaryOp::Or => evaluate_logical(l, self.op, r),
         }
     }
 
     fn to_column(&self, input_schema: &Schema) -> BustubxResult<Column> {
-        Err(BustubxError::Plan(format!(
-            "expr {:?} as column not supported",
-            self
-        )))
+        Ok(Column::new(
+            format!("{self}"),
+            self.data_type(input_schema)?,
+            self.nullable(input_schema)?,
+        ))
     }
 }
 
-fn evaluate_comparison(
+pub(crate) fn evaluate_comparison(
     left: ScalarValue,
     right: ScalarValue,
     accepted_orderings: &[Ordering],
 ) -> BustubxResult<ScalarValue> {
+    if left.is_null() || right.is_null() {
+        return Ok(ScalarValue::Boolean(None));
+    }
     let order = left
         .partial_cmp(&right)
         .ok_or(BustubxError::Execution(format!(
@@ -83,12 +91,225 @@ fn evaluate_comparison(
     )))
 }
 
+/// Kleene logic, NULL means unknown: `NULL AND false` is false and `NULL OR true` is true.
+pub(crate) fn evaluate_logical(
+    left: ScalarValue,
+    op: BinaryOp,
+    right: ScalarValue,
+) -> BustubxResult<ScalarValue> {
+    let (ScalarValue::Boolean(l), ScalarValue::Boolean(r)) = (&left, &right) else {
+        return Err(BustubxError::Execution(format!(
+            "operator {} expects booleans but got {} and {}",
+            op, left, right
+        )));
+    };
+    let value = match (op, *l, *r) {
+        (BinaryOp::And, Some(false), _) | (BinaryOp::And, _, Some(false)) => Some(false),
+        (BinaryOp::And, Some(true), Some(true)) => Some(true),
+        (BinaryOp::Or, Some(true), _) | (BinaryOp::Or, _, Some(true)) => Some(true),
+        (BinaryOp::Or, Some(false), Some(false)) => Some(false),
+        _ => None,
+    };
+    Ok(ScalarValue::Boolean(value))
+}
+
+fn division_by_zero() -> BustubxError {
+    BustubxError::Execution("division by zero".to_string())
+}
+
+/// Checked integer arithmetic, overflow is an error instead of wrapping around.
+macro_rules! integer_arithmetic {
+    ($left:expr, $op:expr, $right:expr, $data_type:expr) => {{
+        let (left, right) = ($left, $right);
+        if matches!($op, BinaryOp::Divide | BinaryOp::Modulo) && right == 0 {
+            return Err(division_by_zero());
+        }
+        match $op {
+            BinaryOp::Plus => left.checked_add(right),
+            BinaryOp::Minus => left.checked_sub(right),
+            BinaryOp::Multiply => left.checked_mul(right),
+            BinaryOp::Divide => left.checked_div(right),
+            // MIN % -1 is 0 mathematically, only the intermediate division overflows
+            _ => Some(left.wrapping_rem(right)),
+        }
+        .ok_or_else(|| BustubxError::Execution(format!("{} out of range", $data_type)))?
+    }};
+}
+
+/// Float arithmetic that reports overflow to infinity instead of returning it.
+macro_rules! float_arithmetic {
+    ($left:expr, $op:expr, $right:expr, $data_type:expr) => {{
+        let (left, right) = ($left, $right);
+        if matches!($op, BinaryOp::Divide | BinaryOp::Modulo) && right == 0.0 {
+            return Err(division_by_zero());
+        }
+        let value = match $op {
+            BinaryOp::Plus => left + right,
+            BinaryOp::Minus => left - right,
+            BinaryOp::Multiply => left * right,
+            BinaryOp::Divide => left / right,
+            _ => left % right,
+        };
+        if value.is_infinite() && left.is_finite() && right.is_finite() {
+            return Err(BustubxError::Execution(format!(
+                "{} out of range: overflow",
+                $data_type
+            )));
+        }
+        value
+    }};
+}
+
+fn arithmetic_result_type(
+    op: BinaryOp,
+    left_type: DataType,
+    right_type: DataType,
+) -> BustubxResult<DataType> {
+    use BinaryOp::{Minus, Plus};
+    use DataType::{Date, Interval, Time, Timestamp};
+    if let Some(operand_types) = numeric_coercion(left_type, right_type) {
+        return match operand_types {
+            (DataType::Decimal128(p1, s1), DataType::Decimal128(p2, s2)) => {
+                let (precision, scale) = decimal_result_type(op, p1, s1, p2, s2);
+                Ok(DataType::Decimal128(precision, scale))
+            }
+            (data_type, _) => Ok(data_type),
+        };
+    }
+    match (left_type, op, right_type) {
+        (DataType::Null, _, DataType::Null) => Ok(DataType::Null),
+        (Timestamp | Date, Plus | Minus, Interval) | (Interval, Plus, Timestamp | Date) => {
+            Ok(Timestamp)
+        }
+        (Time, Plus | Minus, Interval) | (Interval, Plus, Time) => Ok(Time),
+        (Timestamp, Minus, Timestamp) | (Interval, Plus | Minus, Interval) => Ok(Interval),
+        (Date, Minus, Date) => Ok(DataType::Int32),
+        _ => Err(BustubxError::Plan(format!(
+            "operator {} is not supported for {} and {}",
+            op, left_type, right_type
+        ))),
+    }
+}
+
+fn evaluate_arithmetic(
+    left: ScalarValue,
+    op: BinaryOp,
+    right: ScalarValue,
+) -> BustubxResult<ScalarValue> {
+    let result_type = arithmetic_result_type(op, left.data_type(), right.data_type())?;
+    if left.is_null() || right.is_null() {
+        return Ok(ScalarValue::new_empty(result_type));
+    }
+    let (left, right) = match numeric_coercion(left.data_type(), right.data_type()) {
+        Some((left_type, right_type)) => (left.cast_to(&left_type)?, right.cast_to(&right_type)?),
+        None => (left, right),
+    };
+    match (left, right) {
+        (ScalarValue::Int8(Some(v1)), ScalarValue::Int8(Some(v2))) => Ok(ScalarValue::Int8(Some(
+            integer_arithmetic!(v1, op, v2, result_type),
+        ))),
+        (ScalarValue::Int16(Some(v1)), ScalarValue::Int16(Some(v2))) => Ok(ScalarValue::Int16(
+            Some(integer_arithmetic!(v1, op, v2, result_type)),
+        )),
+        (ScalarValue::Int32(Some(v1)), ScalarValue::Int32(Some(v2))) => Ok(ScalarValue::Int32(
+            Some(integer_arithmetic!(v1, op, v2, result_type)),
+        )),
+        (ScalarValue::Int64(Some(v1)), ScalarValue::Int64(Some(v2))) => Ok(ScalarValue::Int64(
+            Some(integer_arithmetic!(v1, op, v2, result_type)),
+        )),
+        (ScalarValue::UInt64(Some(v1)), ScalarValue::UInt64(Some(v2))) => Ok(ScalarValue::UInt64(
+            Some(integer_arithmetic!(v1, op, v2, result_type)),
+        )),
+        (ScalarValue::Float32(Some(v1)), ScalarValue::Float32(Some(v2))) => Ok(
+            ScalarValue::Float32(Some(float_arithmetic!(v1, op, v2, result_type))),
+        ),
+        (ScalarValue::Float64(Some(v1)), ScalarValue::Float64(Some(v2))) => Ok(
+            ScalarValue::Float64(Some(float_arithmetic!(v1, op, v2, result_type))),
+        ),
+        (ScalarValue::Decimal128(Some(v1), _, s1), ScalarValue::Decimal128(Some(v2), _, s2)) => {
+            let DataType::Decimal128(precision, scale) = result_type else {
+                unreachable!()
+            };
+            let value = match op {
+                BinaryOp::Plus => decimal::add(v1, s1, v2, s2, scale)?,
+                BinaryOp::Minus => decimal::sub(v1, s1, v2, s2, scale)?,
+                BinaryOp::Multiply => decimal::mul(v1, s1, v2, s2, scale)?,
+                BinaryOp::Divide => decimal::div(v1, s1, v2, s2, scale)?,
+                _ => decimal::rem(v1, s1, v2, s2, scale)?,
+            };
+            Ok(ScalarValue::Decimal128(
+                Some(decimal::validate_precision(value, precision)?),
+                precision,
+                scale,
+            ))
+        }
+        (ScalarValue::Date(Some(date)), ScalarValue::Interval(Some(interval))) => {
+            evaluate_arithmetic(
+                ScalarValue::Timestamp(Some(temporal::date_to_timestamp(date))),
+                op,
+                ScalarValue::Interval(Some(interval)),
+            )
+        }
+        (ScalarValue::Timestamp(Some(ts)), ScalarValue::Interval(Some(interval))) => {
+            let interval = match op {
+                BinaryOp::Minus => interval.negate()?,
+                _ => interval,
+            };
+            Ok(ScalarValue::Timestamp(Some(
+                temporal::timestamp_add_interval(ts, &interval)?,
+            )))
+        }
+        (ScalarValue::Time(Some(time)), ScalarValue::Interval(Some(interval))) => {
+            let interval = match op {
+                BinaryOp::Minus => interval.negate()?,
+                _ => interval,
+            };
+            Ok(ScalarValue::Time(Some(temporal::time_add_interval(
+                time, &interval,
+            ))))
+        }
+        // interval + timestamp/date/time
+        (interval @ ScalarValue::Interval(_), other @ ScalarValue::Timestamp(_))
+        | (interval @ ScalarValue::Interval(_), other @ ScalarValue::Date(_))
+        | (interval @ ScalarValue::Interval(_), other @ ScalarValue::Time(_)) => {
+            evaluate_arithmetic(other, op, interval)
+        }
+        (ScalarValue::Interval(Some(v1)), ScalarValue::Interval(Some(v2))) => {
+            let v2 = match op {
+                BinaryOp::Minus => v2.negate()?,
+                _ => v2,
+            };
+            Ok(ScalarValue::Interval(Some(v1.checked_add(&v2)?)))
+        }
+        (ScalarValue::Timestamp(Some(v1)), ScalarValue::Timestamp(Some(v2))) => Ok(
+            ScalarValue::Interval(Some(temporal::timestamp_diff(v1, v2)?)),
+        ),
+        (ScalarValue::Date(Some(v1)), ScalarValue::Date(Some(v2))) => {
+            Ok(ScalarValue::Int32(Some(v1 - v2)))
+        }
+        (left, right) => Err(BustubxError::Internal(format!(
+            "binary operator {} on {} and {} has no implementation",
+            op, left, right
+        ))),
+    }
+}
+
+fn decimal_result_type(op: BinaryOp, p1: u8, s1: u8, p2: u8, s2: u8) -> (u8, u8) {
+    match op {
+        BinaryOp::Multiply => decimal::mul_type(p1, s1, p2, s2),
+        BinaryOp::Divide => decimal::div_type(p1, s1, p2, s2),
+        BinaryOp::Modulo => decimal::rem_type(p1, s1, p2, s2),
+        _ => decimal::add_type(p1, s1, p2, s2),
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Hash)]
 pub enum BinaryOp {
     Plus,
     Minus,
     Multiply,
     Divide,
+    Modulo,
     Gt,
     Lt,
     GtEq,
@@ -108,6 +329,7 @@ impl TryFrom<&sqlparser::ast::BinaryOperator> for BinaryOp {
             sqlparser::ast::BinaryOperator::Minus => Ok(BinaryOp::Minus),
             sqlparser::ast::BinaryOperator::Multiply => Ok(BinaryOp::Multiply),
             sqlparser::ast::BinaryOperator::Divide => Ok(BinaryOp::Divide),
+            sqlparser::ast::BinaryOperator::Modulo => Ok(BinaryOp::Modulo),
             sqlparser::ast::BinaryOperator::Gt => Ok(BinaryOp::Gt),
             sqlparser::ast::BinaryOperator::Lt => Ok(BinaryOp::Lt),
             sqlparser::ast::BinaryOperator::GtEq => Ok(BinaryOp::GtEq),
@@ -135,3 +357,109 @@ impl std::fmt::Display for BinaryOp {
         write!(f, "{self:?}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluate_arithmetic, evaluate_logical, BinaryOp};
+    use crate::common::ScalarValue;
+
+    #[test]
+    fn arithmetic_promotion() {
+        let v =
+            evaluate_arithmetic(ScalarValue::Int32(Some(7)), BinaryOp::Plus, 1i64.into()).unwrap();
+        assert_eq!(v, ScalarValue::Int64(Some(8)));
+        let v = evaluate_arithmetic(
+            ScalarValue::Int16(Some(3)),
+            BinaryOp::Multiply,
+            ScalarValue::Float32(Some(0.5)),
+        )
+        .unwrap();
+        assert_eq!(v, ScalarValue::Float32(Some(1.5)));
+        let v = evaluate_arithmetic(
+            ScalarValue::Decimal128(Some(55), 2, 1),
+            BinaryOp::Modulo,
+            ScalarValue::Int32(Some(2)),
+        )
+        .unwrap();
+        assert_eq!(v, ScalarValue::Decimal128(Some(15), 2, 1));
+        let v =
+            evaluate_arithmetic(ScalarValue::Int32(None), BinaryOp::Minus, 1i64.into()).unwrap();
+        assert_eq!(v, ScalarValue::Int64(None));
+        let v = evaluate_arithmetic(
+            ScalarValue::Int64(Some(-7)),
+            BinaryOp::Modulo,
+            ScalarValue::Int64(Some(3)),
+        )
+        .unwrap();
+        assert_eq!(v, ScalarValue::Int64(Some(-1)));
+    }
+
+    #[test]
+    fn arithmetic_errors() {
+        assert!(evaluate_arithmetic(
+            ScalarValue::Int32(Some(1)),
+            BinaryOp::Divide,
+            ScalarValue::Int32(Some(0))
+        )
+        .is_err());
+        assert!(evaluate_arithmetic(
+            ScalarValue::Float64(Some(1.0)),
+            BinaryOp::Modulo,
+            ScalarValue::Float64(Some(0.0))
+        )
+        .is_err());
+        assert!(evaluate_arithmetic(
+            ScalarValue::Int8(Some(100)),
+            BinaryOp::Plus,
+            ScalarValue::Int8(Some(100))
+        )
+        .is_err());
+        assert!(evaluate_arithmetic(
+            ScalarValue::Int64(Some(i64::MIN)),
+            BinaryOp::Divide,
+            ScalarValue::Int64(Some(-1))
+        )
+        .is_err());
+        assert!(evaluate_arithmetic(
+            ScalarValue::Float64(Some(f64::MAX)),
+            BinaryOp::Multiply,
+            ScalarValue::Float64(Some(2.0))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn three_valued_logic() {
+        let values = [Some(true), Some(false), None];
+        // expected results for every (left, right) pair in `values` order
+        let and = [
+            [Some(true), Some(false), None],
+            [Some(false), Some(false), Some(false)],
+            [None, Some(false), None],
+        ];
+        let or = [
+            [Some(true), Some(true), Some(true)],
+            [Some(true), Some(false), None],
+            [Some(true), None, None],
+        ];
+        for (i, l) in values.iter().enumerate() {
+            for (j, r) in values.iter().enumerate() {
+                let (left, right) = (ScalarValue::Boolean(*l), ScalarValue::Boolean(*r));
+                assert_eq!(
+                    evaluate_logical(left.clone(), BinaryOp::And, right.clone()).unwrap(),
+                    ScalarValue::Boolean(and[i][j])
+                );
+                assert_eq!(
+                    evaluate_logical(left, BinaryOp::Or, right).unwrap(),
+                    ScalarValue::Boolean(or[i][j])
+                );
+            }
+        }
+        assert!(evaluate_logical(
+            ScalarValue::Int32(Some(1)),
+            BinaryOp::And,
+            ScalarValue::Boolean(Some(true))
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::catalog::{Column, DataType, Schema};
+use crate::common::ScalarValue;
+use crate::expression::type_coercion::common_type;
+use crate::expression::{Expr, ExprTrait};
+use crate::{BustubxError, BustubxResult, Tuple};
+use std::cmp::Ordering;
+
+/// `CASE [operand] WHEN ... THEN ... [ELSE ...] END`
+///
+/// Without an operand each WHEN is a boolean condition, with an operand the first WHEN value
+/// equal to the operand is chosen. Only the THEN of the chosen branch is evaluated.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Case {
+    /// The operand of the simple form `CASE x WHEN 1 THEN ...`
+    pub expr: Option<Box<Expr>>,
+    pub when_then_expr: Vec<(Box<Expr>, Box<Expr>)>,
+    pub else_expr: Option<Box<Expr>>,
+}
+
+impl Case {
+    /// The THEN and ELSE expressions, which all produce the result of the CASE.
+    pub fn result_exprs(&self) -> impl Iterator<Item = &Expr> {
+        self.when_then_expr
+            .iter()
+            .map(|(_, then)| then.as_ref())
+            .chain(self.else_expr.as_deref())
+    }
+
+    fn matches(&self, operand: &Option<ScalarValue>, when: ScalarValue) -> BustubxResult<bool> {
+        match operand {
+            // a NULL operand or WHEN value never matches
+            Some(operand) => Ok(!operand.is_null()
+                && !when.is_null()
+                && operand.partial_cmp(&when) == Some(Ordering::Equal)),
+            None => match when {
+                ScalarValue::Boolean(v) => Ok(v.unwrap_or(false)),
+                ScalarValue::Null => Ok(false),
+                _ => Err(BustubxError::Execution(format!(
+                    "CASE condition must be a boolean but got {}",
+                    when
+                ))),
+            },
+        }
+    }
+}
+
+impl ExprTrait for Case {
+    fn data_type(&self, input_schema: &Schema) -> BustubxResult<DataType> {
+        let mut data_type = DataType::Null;
+        for expr in self.result_exprs() {
+            let expr_type = expr.data_type(input_schema)?;
+            data_type = common_type(data_type, expr_type).ok_or_else(|| {
+                BustubxError::Plan(format!(
+                    "CASE types {} and {} cannot be matched",
+                    data_type, expr_type
+                ))
+            })?;
+        }
+        Ok(data_type)
+    }
+
+    fn nullable(&self, input_schema: &Schema) -> BustubxResult<bool> {
+        if self.else_expr.is_none() {
+            return Ok(true);
+        }
+        for expr in self.result_exprs() {
+            if expr.nullable(input_schema)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn evaluate(&self, tuple: &Tuple) -> BustubxResult<ScalarValue> {
+        let operand = self
+            .expr
+            .as_ref()
+            .map(|expr| expr.evaluate(tuple))
+            .transpose()?;
+        for (when, then) in self.when_then_expr.iter() {
+            if self.matches(&operand, when.evaluate(tuple)?)? {
+                return then.evaluate(tuple);
+            }
+        }
+        match &self.else_expr {
+            Some(else_expr) => else_expr.evaluate(tuple),
+            None => Ok(ScalarValue::Null),
+        }
+    }
+
+    fn to_column(&self, input_schema: &Schema) -> BustubxResult<Column> {
+        Ok(Column::new(
+            format!("{self}"),
+            self.data_type(input_schema)?,
+            self.nullable(input_schema)?,
+        ))
+    }
+}
+
+impl std::fmt::Display for Case {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CASE")?;
+        if let Some(expr) = &self.expr {
+            write!(f, " {}", expr)?;
+        }
+        for (when, then) in self.when_then_expr.iter() {
+            write!(f, " WHEN {} THEN {}", when, then)?;
+        }
+        if let Some(else_expr) = &self.else_expr {
+            write!(f, " ELSE {}", else_expr)?;
+        }
+        write!(f, " END")
+    }
+}
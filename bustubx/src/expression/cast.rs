@@ -1,7 +1,7 @@
 use crate::catalog::{Column, DataType, Schema};
 use crate::common::ScalarValue;
 use crate::expression::{Expr, ExprTrait};
-use crate::{BustubxError, BustubxResult, Tuple};
+use crate::{BustubxResult, Tuple};
 
 /// Cast expression
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -27,10 +27,11 @@ impl ExprTrait for Cast {
     }
 
     fn to_column(&self, input_schema: &Schema) -> BustubxResult<Column> {
-        Err(BustubxError::Plan(format!(
-            "expr {:?} as column not supported",
-            self
-        )))
+        Ok(Column::new(
+            format!("{self}"),
+            self.data_type,
+            self.nullable(input_schema)?,
+        ))
     }
 }
 
@@ -0,0 +1,174 @@
+use crate::catalog::{Column, DataType, Schema};
+use crate::common::ScalarValue;
+use crate::expression::{Expr, ExprTrait, Literal};
+use crate::{BustubxResult, Tuple};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// `expr [NOT] IN (value, ...)`
+///
+/// The result is NULL if `expr` is NULL, or if nothing matched and the list holds a NULL.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InList {
+    pub expr: Box<Expr>,
+    pub list: Vec<Expr>,
+    pub negated: bool,
+    /// The values of a list of literals, looked up instead of comparing one by one
+    static_set: Option<Arc<StaticSet>>,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+struct StaticSet {
+    values: HashSet<ScalarValue>,
+    has_null: bool,
+}
+
+impl InList {
+    pub fn new(expr: Box<Expr>, list: Vec<Expr>, negated: bool) -> Self {
+        let mut values = HashSet::new();
+        let mut has_null = false;
+        for item in list.iter() {
+            match item {
+                Expr::Literal(Literal { value }) if value.is_null() => has_null = true,
+                Expr::Literal(Literal { value }) => {
+                    values.insert(value.clone());
+                }
+                _ => {
+                    return Self {
+                        expr,
+                        list,
+                        negated,
+                        static_set: None,
+                    }
+                }
+            }
+        }
+        Self {
+            expr,
+            list,
+            negated,
+            static_set: Some(Arc::new(StaticSet { values, has_null })),
+        }
+    }
+
+    fn contains(&self, value: &ScalarValue, tuple: &Tuple) -> BustubxResult<Option<bool>> {
+        if let Some(set) = &self.static_set {
+            return Ok(if set.values.contains(value) {
+                Some(true)
+            } else if set.has_null {
+                None
+            } else {
+                Some(false)
+            });
+        }
+        let mut has_null = false;
+        for item in self.list.iter() {
+            let item = item.evaluate(tuple)?;
+            if item.is_null() {
+                has_null = true;
+            } else if item == *value {
+                return Ok(Some(true));
+            }
+        }
+        Ok(if has_null { None } else { Some(false) })
+    }
+}
+
+impl ExprTrait for InList {
+    fn data_type(&self, _input_schema: &Schema) -> BustubxResult<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, input_schema: &Schema) -> BustubxResult<bool> {
+        if self.expr.nullable(input_schema)? {
+            return Ok(true);
+        }
+        for item in self.list.iter() {
+            if item.nullable(input_schema)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn evaluate(&self, tuple: &Tuple) -> BustubxResult<ScalarValue> {
+        let value = self.expr.evaluate(tuple)?;
+        if value.is_null() {
+            return Ok(ScalarValue::Boolean(None));
+        }
+        let found = self.contains(&value, tuple)?;
+        Ok(ScalarValue::Boolean(if self.negated {
+            found.map(|found| !found)
+        } else {
+            found
+        }))
+    }
+
+    fn to_column(&self, input_schema: &Schema) -> BustubxResult<Column> {
+        Ok(Column::new(
+            format!("{self}"),
+            self.data_type(input_schema)?,
+            self.nullable(input_schema)?,
+        ))
+    }
+}
+
+impl std::fmt::Display for InList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = self
+            .list
+            .iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let not = if self.negated { "NOT " } else { "" };
+        write!(f, "{} {}IN ({})", self.expr, not, list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InList;
+    use crate::catalog::Schema;
+    use crate::common::ScalarValue;
+    use crate::expression::{Expr, ExprTrait, Literal};
+    use crate::Tuple;
+    use std::sync::Arc;
+
+    #[test]
+    fn in_list_null_semantics() {
+        let literal = |value: ScalarValue| Expr::Literal(Literal { value });
+        let tuple = Tuple::empty(Arc::new(Schema::empty()));
+        let in_list = |value: Option<i64>, list: Vec<Option<i64>>, negated: bool| {
+            let expr = InList::new(
+                Box::new(literal(ScalarValue::Int64(value))),
+                list.into_iter()
+                    .map(|v| literal(ScalarValue::Int64(v)))
+                    .collect(),
+                negated,
+            );
+            assert!(expr.static_set.is_some());
+            expr.evaluate(&tuple).unwrap()
+        };
+        assert_eq!(
+            in_list(Some(2), vec![Some(1), Some(2)], false),
+            ScalarValue::Boolean(Some(true))
+        );
+        assert_eq!(
+            in_list(Some(3), vec![Some(1), Some(2)], true),
+            ScalarValue::Boolean(Some(true))
+        );
+        assert_eq!(
+            in_list(Some(3), vec![Some(1), None], false),
+            ScalarValue::Boolean(None)
+        );
+        assert_eq!(
+            in_list(Some(1), vec![Some(1), None], true),
+            ScalarValue::Boolean(Some(false))
+        );
+        assert_eq!(
+            in_list(None, vec![Some(1)], false),
+            ScalarValue::Boolean(None)
+        );
+    }
+}
@@ -1,14 +1,36 @@
+mod accumulator;
+mod aggregate;
 mod alias;
+mod between;
 mod binary;
+mod case;
 mod cast;
 mod column;
+mod in_list;
 mod literal;
+mod scalar_function;
+mod subquery;
+mod transform;
+mod try_cast;
+pub mod type_coercion;
+mod udf;
+mod unary;
 
+pub use accumulator::Accumulator;
+pub use aggregate::{AggregateFunction, BuiltinAggregateFunction};
 pub use alias::Alias;
+pub use between::Between;
 pub use binary::{BinaryExpr, BinaryOp};
+pub use case::Case;
 pub use cast::Cast;
 pub use column::ColumnExpr;
+pub use in_list::InList;
 pub use literal::Literal;
+pub use scalar_function::{BuiltinScalarFunction, ScalarFunction, ScalarFunctionDefinition};
+pub use subquery::{Exists, InSubquery, OuterReferenceColumn, Subquery};
+pub use try_cast::TryCast;
+pub use udf::{FunctionRegistry, ScalarFunctionImplementation, ScalarUdf};
+pub use unary::{UnaryExpr, UnaryOp};
 
 use crate::catalog::Schema;
 use crate::catalog::{Column, DataType};
@@ -40,9 +62,31 @@ pub enum Expr {
     Literal(Literal),
     /// A binary expression such as "age > 21"
     BinaryExpr(BinaryExpr),
+    /// A unary expression such as "NOT flag" or "age IS NULL"
+    UnaryExpr(UnaryExpr),
     /// Casts the expression to a given type and will return a runtime error if the expression cannot be cast.
     /// This expression is guaranteed to have a fixed type.
     Cast(Cast),
+    /// Casts the expression to a given type and will return NULL if the value can not be cast.
+    TryCast(TryCast),
+    /// `expr [NOT] IN (value, ...)`
+    InList(InList),
+    /// `expr [NOT] BETWEEN low AND high`
+    Between(Between),
+    /// `CASE [operand] WHEN ... THEN ... [ELSE ...] END`
+    Case(Case),
+    /// A call to a built-in or user defined scalar function such as `date_trunc('day', ts)`
+    ScalarFunction(ScalarFunction),
+    /// A call of an aggregate function, computed by an `Aggregate` plan
+    AggregateFunction(AggregateFunction),
+    /// A subquery that returns at most one row with one column
+    ScalarSubquery(Subquery),
+    /// `[NOT] EXISTS (subquery)`
+    Exists(Exists),
+    /// `expr [NOT] IN (subquery)`
+    InSubquery(InSubquery),
+    /// A column of the enclosing query referenced inside a subquery
+    OuterReferenceColumn(OuterReferenceColumn),
 }
 
 impl ExprTrait for Expr {
@@ -52,7 +96,18 @@ impl ExprTrait for Expr {
             Expr::Column(column) => column.data_type(input_schema),
             Expr::Literal(literal) => literal.data_type(input_schema),
             Expr::BinaryExpr(binary) => binary.data_type(input_schema),
+            Expr::UnaryExpr(unary) => unary.data_type(input_schema),
             Expr::Cast(cast) => cast.data_type(input_schema),
+            Expr::TryCast(try_cast) => try_cast.data_type(input_schema),
+            Expr::InList(in_list) => in_list.data_type(input_schema),
+            Expr::Between(between) => between.data_type(input_schema),
+            Expr::Case(case) => case.data_type(input_schema),
+            Expr::ScalarFunction(function) => function.data_type(input_schema),
+            Expr::AggregateFunction(aggregate) => aggregate.data_type(input_schema),
+            Expr::ScalarSubquery(subquery) => subquery.data_type(input_schema),
+            Expr::Exists(exists) => exists.data_type(input_schema),
+            Expr::InSubquery(in_subquery) => in_subquery.data_type(input_schema),
+            Expr::OuterReferenceColumn(outer_ref) => outer_ref.data_type(input_schema),
         }
     }
 
@@ -62,7 +117,18 @@ impl ExprTrait for Expr {
             Expr::Column(column) => column.nullable(input_schema),
             Expr::Literal(literal) => literal.nullable(input_schema),
             Expr::BinaryExpr(binary) => binary.nullable(input_schema),
+            Expr::UnaryExpr(unary) => unary.nullable(input_schema),
             Expr::Cast(cast) => cast.nullable(input_schema),
+            Expr::TryCast(try_cast) => try_cast.nullable(input_schema),
+            Expr::InList(in_list) => in_list.nullable(input_schema),
+            Expr::Between(between) => between.nullable(input_schema),
+            Expr::Case(case) => case.nullable(input_schema),
+            Expr::ScalarFunction(function) => function.nullable(input_schema),
+            Expr::AggregateFunction(aggregate) => aggregate.nullable(input_schema),
+            Expr::ScalarSubquery(subquery) => subquery.nullable(input_schema),
+            Expr::Exists(exists) => exists.nullable(input_schema),
+            Expr::InSubquery(in_subquery) => in_subquery.nullable(input_schema),
+            Expr::OuterReferenceColumn(outer_ref) => outer_ref.nullable(input_schema),
         }
     }
 
@@ -72,7 +138,18 @@ impl ExprTrait for Expr {
             Expr::Column(column) => column.evaluate(tuple),
             Expr::Literal(literal) => literal.evaluate(tuple),
             Expr::BinaryExpr(binary) => binary.evaluate(tuple),
+            Expr::UnaryExpr(unary) => unary.evaluate(tuple),
             Expr::Cast(cast) => cast.evaluate(tuple),
+            Expr::TryCast(try_cast) => try_cast.evaluate(tuple),
+            Expr::InList(in_list) => in_list.evaluate(tuple),
+            Expr::Between(between) => between.evaluate(tuple),
+            Expr::Case(case) => case.evaluate(tuple),
+            Expr::ScalarFunction(function) => function.evaluate(tuple),
+            Expr::AggregateFunction(aggregate) => aggregate.evaluate(tuple),
+            Expr::ScalarSubquery(subquery) => subquery.evaluate(tuple),
+            Expr::Exists(exists) => exists.evaluate(tuple),
+            Expr::InSubquery(in_subquery) => in_subquery.evaluate(tuple),
+            Expr::OuterReferenceColumn(outer_ref) => outer_ref.evaluate(tuple),
         }
     }
 
@@ -82,7 +159,18 @@ impl ExprTrait for Expr {
             Expr::Column(column) => column.to_column(input_schema),
             Expr::Literal(literal) => literal.to_column(input_schema),
             Expr::BinaryExpr(binary) => binary.to_column(input_schema),
+            Expr::UnaryExpr(unary) => unary.to_column(input_schema),
             Expr::Cast(cast) => cast.to_column(input_schema),
+            Expr::TryCast(try_cast) => try_cast.to_column(input_schema),
+            Expr::InList(in_list) => in_list.to_column(input_schema),
+            Expr::Between(between) => between.to_column(input_schema),
+            Expr::Case(case) => case.to_column(input_schema),
+            Expr::ScalarFunction(function) => function.to_column(input_schema),
+            Expr::AggregateFunction(aggregate) => aggregate.to_column(input_schema),
+            Expr::ScalarSubquery(subquery) => subquery.to_column(input_schema),
+            Expr::Exists(exists) => exists.to_column(input_schema),
+            Expr::InSubquery(in_subquery) => in_subquery.to_column(input_schema),
+            Expr::OuterReferenceColumn(outer_ref) => outer_ref.to_column(input_schema),
         }
     }
 }
@@ -94,7 +182,18 @@ impl std::fmt::Display for Expr {
             Expr::Column(e) => write!(f, "{e}"),
             Expr::Literal(e) => write!(f, "{e}"),
             Expr::BinaryExpr(e) => write!(f, "{e}"),
+            Expr::UnaryExpr(e) => write!(f, "{e}"),
             Expr::Cast(e) => write!(f, "{e}"),
+            Expr::TryCast(e) => write!(f, "{e}"),
+            Expr::InList(e) => write!(f, "{e}"),
+            Expr::Between(e) => write!(f, "{e}"),
+            Expr::Case(e) => write!(f, "{e}"),
+            Expr::ScalarFunction(e) => write!(f, "{e}"),
+            Expr::AggregateFunction(e) => write!(f, "{e}"),
+            Expr::ScalarSubquery(e) => write!(f, "{e}"),
+            Expr::Exists(e) => write!(f, "{e}"),
+            Expr::InSubquery(e) => write!(f, "{e}"),
+            Expr::OuterReferenceColumn(e) => write!(f, "{e}"),
         }
     }
 }
@@ -0,0 +1,616 @@
+use crate::catalog::{Column, DataType, Schema};
+use crate::common::{decimal, temporal, ScalarValue};
+use crate::expression::type_coercion::{common_type, comparison_coercion};
+use crate::expression::{Expr, ExprTrait, ScalarUdf};
+use crate::{BustubxError, BustubxResult, Tuple};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// Built-in scalar functions
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BuiltinScalarFunction {
+    /// `abs(x)`
+    Abs,
+    /// `coalesce(x, ...)`, the first argument that is not NULL
+    Coalesce,
+    /// `nullif(x, y)`, NULL if both arguments are equal, otherwise `x`
+    NullIf,
+    /// `greatest(x, ...)`, NULL arguments are ignored
+    Greatest,
+    /// `least(x, ...)`, NULL arguments are ignored
+    Least,
+    /// `round(x)` or `round(x, digits)`, halves are rounded away from zero
+    Round,
+    /// `sign(x)`, -1, 0 or 1
+    Sign,
+    /// `date_trunc(field, source)`
+    DateTrunc,
+    /// `date_part(field, source)`, also used for `EXTRACT(field FROM source)`
+    DatePart,
+}
+
+impl BuiltinScalarFunction {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "abs" => Some(Self::Abs),
+            "coalesce" => Some(Self::Coalesce),
+            "nullif" => Some(Self::NullIf),
+            "greatest" => Some(Self::Greatest),
+            "least" => Some(Self::Least),
+            "round" => Some(Self::Round),
+            "sign" => Some(Self::Sign),
+            "date_trunc" => Some(Self::DateTrunc),
+            "date_part" => Some(Self::DatePart),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Abs => "abs",
+            Self::Coalesce => "coalesce",
+            Self::NullIf => "nullif",
+            Self::Greatest => "greatest",
+            Self::Least => "least",
+            Self::Round => "round",
+            Self::Sign => "sign",
+            Self::DateTrunc => "date_trunc",
+            Self::DatePart => "date_part",
+        }
+    }
+
+    fn check_arg_count(&self, count: usize) -> BustubxResult<()> {
+        let valid = match self {
+            Self::Abs | Self::Sign => count == 1,
+            Self::Round => count == 1 || count == 2,
+            Self::NullIf | Self::DateTrunc | Self::DatePart => count == 2,
+            Self::Coalesce | Self::Greatest | Self::Least => count >= 1,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(BustubxError::Plan(format!(
+                "function {} does not accept {} arguments",
+                self.name(),
+                count
+            )))
+        }
+    }
+
+    fn coerce_arg_types(&self, arg_types: &[DataType]) -> BustubxResult<Vec<DataType>> {
+        match self {
+            Self::Coalesce | Self::Greatest | Self::Least => {
+                let mut data_type = DataType::Null;
+                for arg_type in arg_types {
+                    data_type = common_type(data_type, *arg_type)
+                        .ok_or_else(|| self.unsupported_arg_types(arg_types))?;
+                }
+                Ok(vec![data_type; arg_types.len()])
+            }
+            Self::NullIf => {
+                let (left, right) = comparison_coercion(arg_types[0], arg_types[1])
+                    .ok_or_else(|| self.unsupported_arg_types(arg_types))?;
+                Ok(vec![left, right])
+            }
+            // the number of digits is always read as an Int32
+            Self::Round if arg_types.len() == 2 => Ok(vec![arg_types[0], DataType::Int32]),
+            _ => Ok(arg_types.to_vec()),
+        }
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> BustubxResult<DataType> {
+        let numeric_or_null =
+            |data_type: DataType| data_type.is_numeric() || data_type == DataType::Null;
+        match self {
+            Self::Abs | Self::Sign if numeric_or_null(arg_types[0]) => Ok(arg_types[0]),
+            Self::Round
+                if numeric_or_null(arg_types[0])
+                    && arg_types.get(1).is_none_or(|t| numeric_or_null(*t)) =>
+            {
+                match arg_types[0] {
+                    // rounding up may need one more integer digit, e.g. 9.9 to 10
+                    DataType::Decimal128(precision, scale) => Ok(DataType::Decimal128(
+                        (precision + 1).min(decimal::DECIMAL128_MAX_PRECISION),
+                        scale,
+                    )),
+                    data_type => Ok(data_type),
+                }
+            }
+            Self::Coalesce | Self::Greatest | Self::Least | Self::NullIf => Ok(arg_types[0]),
+            Self::DateTrunc
+                if matches!(
+                    arg_types[1],
+                    DataType::Date | DataType::Timestamp | DataType::Varchar(_) | DataType::Null
+                ) =>
+            {
+                Ok(DataType::Timestamp)
+            }
+            Self::DatePart
+                if matches!(
+                    arg_types[1],
+                    DataType::Date
+                        | DataType::Time
+                        | DataType::Timestamp
+                        | DataType::Varchar(_)
+                        | DataType::Null
+                ) =>
+            {
+                Ok(DataType::Float64)
+            }
+            _ => Err(self.unsupported_arg_types(arg_types)),
+        }
+    }
+
+    fn unsupported_arg_types(&self, arg_types: &[DataType]) -> BustubxError {
+        let arg_types = arg_types
+            .iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        BustubxError::Plan(format!(
+            "function {}({}) does not exist",
+            self.name(),
+            arg_types
+        ))
+    }
+
+    fn invoke(&self, args: &[ScalarValue]) -> BustubxResult<ScalarValue> {
+        let arg_types = args.iter().map(|v| v.data_type()).collect::<Vec<_>>();
+        let return_type = self.return_type(&arg_types)?;
+        match self {
+            Self::Abs => abs(&args[0]),
+            Self::Sign => sign(&args[0]),
+            Self::Round => {
+                let digits = match args.get(1) {
+                    None => 0,
+                    Some(ScalarValue::Int32(Some(digits))) => *digits,
+                    Some(_) => return Ok(ScalarValue::new_empty(return_type)),
+                };
+                round(&args[0], digits, return_type)
+            }
+            Self::Coalesce => Ok(args
+                .iter()
+                .find(|v| !v.is_null())
+                .cloned()
+                .unwrap_or_else(|| ScalarValue::new_empty(return_type))),
+            Self::NullIf => {
+                let equal = !args[0].is_null()
+                    && !args[1].is_null()
+                    && args[0].partial_cmp(&args[1]) == Some(Ordering::Equal);
+                if equal {
+                    Ok(ScalarValue::new_empty(return_type))
+                } else {
+                    Ok(args[0].clone())
+                }
+            }
+            Self::Greatest => extremum(args, Ordering::Greater, return_type),
+            Self::Least => extremum(args, Ordering::Less, return_type),
+            Self::DateTrunc | Self::DatePart => self.invoke_temporal(&args[0], &args[1]),
+        }
+    }
+
+    fn invoke_temporal(
+        &self,
+        field: &ScalarValue,
+        source: &ScalarValue,
+    ) -> BustubxResult<ScalarValue> {
+        let field = match field {
+            ScalarValue::Varchar(Some(field)) => Some(field.clone()),
+            ScalarValue::Varchar(None) | ScalarValue::Null => None,
+            other => {
+                return Err(BustubxError::Execution(format!(
+                    "function {} expects a text field name but got {}",
+                    self.name(),
+                    other
+                )))
+            }
+        };
+        // a text source is read as a timestamp, like a postgres unknown-typed literal
+        let source = match source {
+            ScalarValue::Varchar(_) | ScalarValue::Null => source.cast_to(&DataType::Timestamp)?,
+            _ => source.clone(),
+        };
+        let unsupported_source = |source: &ScalarValue| {
+            BustubxError::Execution(format!(
+                "function {} does not support argument {}",
+                self.name(),
+                source
+            ))
+        };
+        if *self == Self::DateTrunc {
+            let micros = match source {
+                ScalarValue::Timestamp(v) => v,
+                ScalarValue::Date(v) => v.map(temporal::date_to_timestamp),
+                _ => return Err(unsupported_source(&source)),
+            };
+            let value = match (field, micros) {
+                (Some(field), Some(micros)) => Some(temporal::date_trunc(&field, micros)?),
+                _ => None,
+            };
+            return Ok(ScalarValue::Timestamp(value));
+        }
+        let value = match (field, source) {
+            (Some(field), ScalarValue::Timestamp(Some(v))) => {
+                Some(temporal::timestamp_part(&field, v)?)
+            }
+            (Some(field), ScalarValue::Date(Some(v))) => Some(temporal::timestamp_part(
+                &field,
+                temporal::date_to_timestamp(v),
+            )?),
+            (Some(field), ScalarValue::Time(Some(v))) => Some(temporal::time_part(&field, v)?),
+            (_, ScalarValue::Timestamp(_) | ScalarValue::Date(_) | ScalarValue::Time(_)) => None,
+            (_, source) => return Err(unsupported_source(&source)),
+        };
+        Ok(ScalarValue::Float64(value))
+    }
+}
+
+fn out_of_range(value: &ScalarValue) -> BustubxError {
+    BustubxError::Execution(format!("{} out of range", value.data_type()))
+}
+
+fn abs(value: &ScalarValue) -> BustubxResult<ScalarValue> {
+    let oor = || out_of_range(value);
+    match value {
+        ScalarValue::Int8(v) => Ok(ScalarValue::Int8(
+            v.map(|v| v.checked_abs().ok_or_else(oor)).transpose()?,
+        )),
+        ScalarValue::Int16(v) => Ok(ScalarValue::Int16(
+            v.map(|v| v.checked_abs().ok_or_else(oor)).transpose()?,
+        )),
+        ScalarValue::Int32(v) => Ok(ScalarValue::Int32(
+            v.map(|v| v.checked_abs().ok_or_else(oor)).transpose()?,
+        )),
+        ScalarValue::Int64(v) => Ok(ScalarValue::Int64(
+            v.map(|v| v.checked_abs().ok_or_else(oor)).transpose()?,
+        )),
+        ScalarValue::Float32(v) => Ok(ScalarValue::Float32(v.map(f32::abs))),
+        ScalarValue::Float64(v) => Ok(ScalarValue::Float64(v.map(f64::abs))),
+        ScalarValue::Decimal128(v, precision, scale) => Ok(ScalarValue::Decimal128(
+            v.map(i128::abs),
+            *precision,
+            *scale,
+        )),
+        _ => Ok(value.clone()),
+    }
+}
+
+fn sign(value: &ScalarValue) -> BustubxResult<ScalarValue> {
+    // f64::signum is 1 for +0.0, keep zeros and NaN as they are
+    let float_sign = |v: f64| {
+        if v == 0.0 || v.is_nan() {
+            v
+        } else {
+            v.signum()
+        }
+    };
+    match value {
+        ScalarValue::Int8(v) => Ok(ScalarValue::Int8(v.map(i8::signum))),
+        ScalarValue::Int16(v) => Ok(ScalarValue::Int16(v.map(i16::signum))),
+        ScalarValue::Int32(v) => Ok(ScalarValue::Int32(v.map(i32::signum))),
+        ScalarValue::Int64(v) => Ok(ScalarValue::Int64(v.map(i64::signum))),
+        ScalarValue::UInt64(v) => Ok(ScalarValue::UInt64(v.map(|v| v.min(1)))),
+        ScalarValue::Float32(v) => Ok(ScalarValue::Float32(
+            v.map(|v| float_sign(f64::from(v)) as f32),
+        )),
+        ScalarValue::Float64(v) => Ok(ScalarValue::Float64(v.map(float_sign))),
+        ScalarValue::Decimal128(v, precision, scale) => {
+            let one = decimal::pow10(*scale)?;
+            Ok(ScalarValue::Decimal128(
+                v.map(|v| v.signum() * one),
+                *precision,
+                *scale,
+            ))
+        }
+        _ => Ok(value.clone()),
+    }
+}
+
+fn round(value: &ScalarValue, digits: i32, return_type: DataType) -> BustubxResult<ScalarValue> {
+    let round_float = |v: f64| {
+        let factor = 10f64.powi(digits);
+        let scaled = v * factor;
+        if scaled.is_finite() && factor != 0.0 {
+            scaled.round() / factor
+        } else {
+            v
+        }
+    };
+    match value {
+        v if v.is_null() => Ok(ScalarValue::new_empty(return_type)),
+        ScalarValue::Float32(v) => Ok(ScalarValue::Float32(
+            v.map(|v| round_float(f64::from(v)) as f32),
+        )),
+        ScalarValue::Float64(v) => Ok(ScalarValue::Float64(v.map(round_float))),
+        ScalarValue::Decimal128(Some(v), _, scale) => {
+            let rounded = decimal::round(*v, *scale, digits)?;
+            ScalarValue::Decimal128(Some(rounded), decimal::DECIMAL128_MAX_PRECISION, *scale)
+                .cast_to(&return_type)
+        }
+        // integers are rounded as decimals with scale 0 and cast back with a range check
+        _ => match value.cast_to(&DataType::Decimal128(20, 0))? {
+            ScalarValue::Decimal128(Some(v), _, _) => {
+                let rounded = decimal::round(v, 0, digits)?;
+                ScalarValue::Decimal128(Some(rounded), decimal::DECIMAL128_MAX_PRECISION, 0)
+                    .cast_to(&return_type)
+            }
+            _ => Ok(ScalarValue::new_empty(return_type)),
+        },
+    }
+}
+
+/// `greatest` and `least`, the arguments all have the same type after coercion.
+fn extremum(
+    args: &[ScalarValue],
+    wanted: Ordering,
+    return_type: DataType,
+) -> BustubxResult<ScalarValue> {
+    let mut result: Option<&ScalarValue> = None;
+    for arg in args.iter().filter(|v| !v.is_null()) {
+        result = match result {
+            None => Some(arg),
+            Some(current) => {
+                let order = arg.partial_cmp(current).ok_or_else(|| {
+                    BustubxError::Execution(format!("Can not compare {} and {}", arg, current))
+                })?;
+                if order == wanted {
+                    Some(arg)
+                } else {
+                    Some(current)
+                }
+            }
+        };
+    }
+    Ok(result
+        .cloned()
+        .unwrap_or_else(|| ScalarValue::new_empty(return_type)))
+}
+
+/// A built-in function or a user defined function registered on the database
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ScalarFunctionDefinition {
+    Builtin(BuiltinScalarFunction),
+    Udf(Arc<ScalarUdf>),
+}
+
+impl ScalarFunctionDefinition {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Builtin(func) => func.name(),
+            Self::Udf(udf) => &udf.name,
+        }
+    }
+
+    /// The types the arguments are cast to before the function is called.
+    pub fn coerce_arg_types(&self, arg_types: &[DataType]) -> BustubxResult<Vec<DataType>> {
+        match self {
+            Self::Builtin(func) => func.coerce_arg_types(arg_types),
+            Self::Udf(udf) => udf.coerce_arg_types(arg_types),
+        }
+    }
+
+    pub fn return_type(&self, arg_types: &[DataType]) -> BustubxResult<DataType> {
+        match self {
+            Self::Builtin(func) => func.return_type(arg_types),
+            Self::Udf(udf) => Ok(udf.return_type),
+        }
+    }
+
+    fn check_arg_count(&self, count: usize) -> BustubxResult<()> {
+        match self {
+            Self::Builtin(func) => func.check_arg_count(count),
+            Self::Udf(udf) => udf.check_arg_count(count),
+        }
+    }
+
+    fn invoke(&self, args: &[ScalarValue]) -> BustubxResult<ScalarValue> {
+        match self {
+            Self::Builtin(func) => func.invoke(args),
+            Self::Udf(udf) => udf.invoke(args),
+        }
+    }
+}
+
+/// Scalar function call such as `date_trunc('day', ts)`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ScalarFunction {
+    pub func: ScalarFunctionDefinition,
+    pub args: Vec<Expr>,
+}
+
+impl ScalarFunction {
+    pub fn try_new(func: ScalarFunctionDefinition, args: Vec<Expr>) -> BustubxResult<Self> {
+        func.check_arg_count(args.len())?;
+        Ok(Self { func, args })
+    }
+}
+
+impl ExprTrait for ScalarFunction {
+    fn data_type(&self, input_schema: &Schema) -> BustubxResult<DataType> {
+        let arg_types = self
+            .args
+            .iter()
+            .map(|arg| arg.data_type(input_schema))
+            .collect::<BustubxResult<Vec<DataType>>>()?;
+        self.func.return_type(&arg_types)
+    }
+
+    fn nullable(&self, input_schema: &Schema) -> BustubxResult<bool> {
+        let mut args_nullable = vec![];
+        for arg in self.args.iter() {
+            args_nullable.push(arg.nullable(input_schema)?);
+        }
+        match self.func {
+            ScalarFunctionDefinition::Builtin(
+                BuiltinScalarFunction::Coalesce
+                | BuiltinScalarFunction::Greatest
+                | BuiltinScalarFunction::Least,
+            ) => Ok(args_nullable.iter().all(|nullable| *nullable)),
+            ScalarFunctionDefinition::Builtin(BuiltinScalarFunction::NullIf)
+            | ScalarFunctionDefinition::Udf(_) => Ok(true),
+            ScalarFunctionDefinition::Builtin(_) => {
+                Ok(args_nullable.iter().any(|nullable| *nullable))
+            }
+        }
+    }
+
+    fn evaluate(&self, tuple: &Tuple) -> BustubxResult<ScalarValue> {
+        // coalesce stops at the first value that is not NULL
+        if self.func == ScalarFunctionDefinition::Builtin(BuiltinScalarFunction::Coalesce) {
+            let mut value = ScalarValue::Null;
+            for arg in self.args.iter() {
+                value = arg.evaluate(tuple)?;
+                if !value.is_null() {
+                    break;
+                }
+            }
+            return Ok(value);
+        }
+        let args = self
+            .args
+            .iter()
+            .map(|arg| arg.evaluate(tuple))
+            .collect::<BustubxResult<Vec<ScalarValue>>>()?;
+        self.func.invoke(&args)
+    }
+
+    fn to_column(&self, input_schema: &Schema) -> BustubxResult<Column> {
+        Ok(Column::new(
+            format!("{self}"),
+            self.data_type(input_schema)?,
+            self.nullable(input_schema)?,
+        ))
+    }
+}
+
+impl std::fmt::Display for ScalarFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let args = self
+            .args
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{}({})", self.func.name(), args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BuiltinScalarFunction;
+    use crate::catalog::DataType;
+    use crate::common::ScalarValue;
+
+    #[test]
+    fn math_functions() {
+        let invoke = |func: BuiltinScalarFunction, args: Vec<ScalarValue>| func.invoke(&args);
+        assert_eq!(
+            invoke(
+                BuiltinScalarFunction::Abs,
+                vec![ScalarValue::Int32(Some(-3))]
+            )
+            .unwrap(),
+            ScalarValue::Int32(Some(3))
+        );
+        assert!(invoke(
+            BuiltinScalarFunction::Abs,
+            vec![ScalarValue::Int8(Some(i8::MIN))]
+        )
+        .is_err());
+        assert_eq!(
+            invoke(
+                BuiltinScalarFunction::Sign,
+                vec![ScalarValue::Float64(Some(-0.5))]
+            )
+            .unwrap(),
+            ScalarValue::Float64(Some(-1.0))
+        );
+        assert_eq!(
+            invoke(
+                BuiltinScalarFunction::Sign,
+                vec![ScalarValue::Decimal128(Some(-250), 5, 2)]
+            )
+            .unwrap(),
+            ScalarValue::Decimal128(Some(-100), 5, 2)
+        );
+        assert_eq!(
+            invoke(
+                BuiltinScalarFunction::Round,
+                vec![ScalarValue::Float64(Some(2.5))]
+            )
+            .unwrap(),
+            ScalarValue::Float64(Some(3.0))
+        );
+        assert_eq!(
+            invoke(
+                BuiltinScalarFunction::Round,
+                vec![ScalarValue::Int64(Some(1250)), ScalarValue::Int32(Some(-2))]
+            )
+            .unwrap(),
+            ScalarValue::Int64(Some(1300))
+        );
+        assert_eq!(
+            invoke(
+                BuiltinScalarFunction::Round,
+                vec![
+                    ScalarValue::Decimal128(Some(999), 3, 2),
+                    ScalarValue::Int32(Some(0))
+                ]
+            )
+            .unwrap(),
+            ScalarValue::Decimal128(Some(1000), 4, 2)
+        );
+        assert!(invoke(
+            BuiltinScalarFunction::Round,
+            vec![ScalarValue::Int8(Some(125)), ScalarValue::Int32(Some(-1))]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn conditional_functions() {
+        let invoke =
+            |func: BuiltinScalarFunction, args: Vec<ScalarValue>| func.invoke(&args).unwrap();
+        assert_eq!(
+            invoke(
+                BuiltinScalarFunction::Greatest,
+                vec![
+                    ScalarValue::Int64(Some(1)),
+                    ScalarValue::Int64(None),
+                    ScalarValue::Int64(Some(7))
+                ]
+            ),
+            ScalarValue::Int64(Some(7))
+        );
+        assert_eq!(
+            invoke(
+                BuiltinScalarFunction::Least,
+                vec![ScalarValue::Int64(None), ScalarValue::Int64(None)]
+            ),
+            ScalarValue::Int64(None)
+        );
+        assert_eq!(
+            invoke(
+                BuiltinScalarFunction::NullIf,
+                vec![ScalarValue::Int64(Some(1)), ScalarValue::Int64(Some(1))]
+            ),
+            ScalarValue::Int64(None)
+        );
+        assert_eq!(
+            invoke(
+                BuiltinScalarFunction::Coalesce,
+                vec![ScalarValue::Null, ScalarValue::from("a")]
+            ),
+            ScalarValue::from("a")
+        );
+        assert_eq!(
+            BuiltinScalarFunction::Coalesce
+                .coerce_arg_types(&[DataType::Null, DataType::Int32, DataType::Int64])
+                .unwrap(),
+            vec![DataType::Int64; 3]
+        );
+        assert!(BuiltinScalarFunction::Greatest
+            .coerce_arg_types(&[DataType::Int32, DataType::Date])
+            .is_err());
+    }
+}
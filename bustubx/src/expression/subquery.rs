@@ -0,0 +1,181 @@
+use crate::catalog::{Column, DataType, Schema};
+use crate::common::ScalarValue;
+use crate::expression::{ColumnExpr, Expr, ExprTrait};
+use crate::planner::logical_plan::LogicalPlan;
+use crate::{BustubxError, BustubxResult, Tuple};
+use std::sync::Arc;
+
+/// A planned subquery. The planner turns expressions holding one into a semi or anti join, or
+/// into an `Apply` which runs the subquery for every input row, so it is never evaluated itself.
+#[derive(Clone, Debug)]
+pub struct Subquery {
+    pub subquery: Arc<LogicalPlan>,
+}
+
+impl Subquery {
+    /// The type of the only column of the subquery.
+    pub fn output_type(&self) -> BustubxResult<DataType> {
+        match self.subquery.schema().columns.as_slice() {
+            [column] => Ok(column.data_type),
+            _ => Err(BustubxError::Plan(
+                "subquery must return only one column".to_string(),
+            )),
+        }
+    }
+}
+
+// plans are not comparable, a cloned subquery still shares its plan
+impl PartialEq for Subquery {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.subquery, &other.subquery)
+    }
+}
+
+impl Eq for Subquery {}
+
+fn not_planned(expr: &dyn std::fmt::Display) -> BustubxError {
+    BustubxError::Internal(format!("subquery {} can not be evaluated directly", expr))
+}
+
+impl ExprTrait for Subquery {
+    fn data_type(&self, _input_schema: &Schema) -> BustubxResult<DataType> {
+        self.output_type()
+    }
+
+    fn nullable(&self, _input_schema: &Schema) -> BustubxResult<bool> {
+        // an empty result is NULL
+        Ok(true)
+    }
+
+    fn evaluate(&self, _tuple: &Tuple) -> BustubxResult<ScalarValue> {
+        Err(not_planned(self))
+    }
+
+    fn to_column(&self, input_schema: &Schema) -> BustubxResult<Column> {
+        Ok(Column::new(
+            format!("{self}"),
+            self.data_type(input_schema)?,
+            self.nullable(input_schema)?,
+        ))
+    }
+}
+
+impl std::fmt::Display for Subquery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(<subquery>)")
+    }
+}
+
+/// `[NOT] EXISTS (subquery)`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Exists {
+    pub subquery: Subquery,
+    pub negated: bool,
+}
+
+impl ExprTrait for Exists {
+    fn data_type(&self, _input_schema: &Schema) -> BustubxResult<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, _input_schema: &Schema) -> BustubxResult<bool> {
+        Ok(false)
+    }
+
+    fn evaluate(&self, _tuple: &Tuple) -> BustubxResult<ScalarValue> {
+        Err(not_planned(self))
+    }
+
+    fn to_column(&self, input_schema: &Schema) -> BustubxResult<Column> {
+        Ok(Column::new(
+            format!("{self}"),
+            self.data_type(input_schema)?,
+            self.nullable(input_schema)?,
+        ))
+    }
+}
+
+impl std::fmt::Display for Exists {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let not = if self.negated { "NOT " } else { "" };
+        write!(f, "{}EXISTS {}", not, self.subquery)
+    }
+}
+
+/// `expr [NOT] IN (subquery)`, with the same NULL semantics as an IN list
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InSubquery {
+    pub expr: Box<Expr>,
+    pub subquery: Subquery,
+    pub negated: bool,
+}
+
+impl ExprTrait for InSubquery {
+    fn data_type(&self, _input_schema: &Schema) -> BustubxResult<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, _input_schema: &Schema) -> BustubxResult<bool> {
+        Ok(true)
+    }
+
+    fn evaluate(&self, _tuple: &Tuple) -> BustubxResult<ScalarValue> {
+        Err(not_planned(self))
+    }
+
+    fn to_column(&self, input_schema: &Schema) -> BustubxResult<Column> {
+        Ok(Column::new(
+            format!("{self}"),
+            self.data_type(input_schema)?,
+            self.nullable(input_schema)?,
+        ))
+    }
+}
+
+impl std::fmt::Display for InSubquery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let not = if self.negated { "NOT " } else { "" };
+        write!(f, "{} {}IN {}", self.expr, not, self.subquery)
+    }
+}
+
+/// A column of an enclosing query referenced inside a subquery. It is replaced by the value of
+/// the current outer row before the subquery runs, or by a plain column when the subquery is
+/// turned into a join.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OuterReferenceColumn {
+    pub column: ColumnExpr,
+    pub data_type: DataType,
+    pub nullable: bool,
+}
+
+impl ExprTrait for OuterReferenceColumn {
+    fn data_type(&self, _input_schema: &Schema) -> BustubxResult<DataType> {
+        Ok(self.data_type)
+    }
+
+    fn nullable(&self, _input_schema: &Schema) -> BustubxResult<bool> {
+        Ok(self.nullable)
+    }
+
+    fn evaluate(&self, _tuple: &Tuple) -> BustubxResult<ScalarValue> {
+        Err(BustubxError::Internal(format!(
+            "outer reference {} was not replaced by a value",
+            self.column
+        )))
+    }
+
+    fn to_column(&self, _input_schema: &Schema) -> BustubxResult<Column> {
+        Ok(Column::new(
+            self.column.name.clone(),
+            self.data_type,
+            self.nullable,
+        ))
+    }
+}
+
+impl std::fmt::Display for OuterReferenceColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "outer_ref({})", self.column)
+    }
+}
@@ -0,0 +1,181 @@
+use crate::expression::{
+    AggregateFunction, Alias, Between, BinaryExpr, Case, Cast, Expr, InList, InSubquery,
+    ScalarFunction, TryCast, UnaryExpr,
+};
+use crate::BustubxResult;
+
+impl Expr {
+    /// The direct sub-expressions, plans of subqueries are not included.
+    pub fn children(&self) -> Vec<&Expr> {
+        match self {
+            Expr::Alias(Alias { expr, .. })
+            | Expr::Cast(Cast { expr, .. })
+            | Expr::TryCast(TryCast { expr, .. })
+            | Expr::UnaryExpr(UnaryExpr { expr, .. })
+            | Expr::InSubquery(InSubquery { expr, .. }) => vec![expr],
+            Expr::BinaryExpr(BinaryExpr { left, right, .. }) => vec![left, right],
+            Expr::InList(InList { expr, list, .. }) => {
+                let mut children = vec![expr.as_ref()];
+                children.extend(list.iter());
+                children
+            }
+            Expr::Between(Between {
+                expr, low, high, ..
+            }) => vec![expr, low, high],
+            Expr::Case(case) => {
+                let mut children = vec![];
+                children.extend(case.expr.as_deref());
+                for (when, then) in case.when_then_expr.iter() {
+                    children.push(when.as_ref());
+                    children.push(then.as_ref());
+                }
+                children.extend(case.else_expr.as_deref());
+                children
+            }
+            Expr::ScalarFunction(ScalarFunction { args, .. })
+            | Expr::AggregateFunction(AggregateFunction { args, .. }) => args.iter().collect(),
+            Expr::Column(_)
+            | Expr::Literal(_)
+            | Expr::ScalarSubquery(_)
+            | Expr::Exists(_)
+            | Expr::OuterReferenceColumn(_) => vec![],
+        }
+    }
+
+    /// Rebuild this expression with `f` applied to each direct sub-expression.
+    pub fn map_children<F>(self, mut f: F) -> BustubxResult<Expr>
+    where
+        F: FnMut(Expr) -> BustubxResult<Expr>,
+    {
+        let mut map_box = |expr: Box<Expr>| f(*expr).map(Box::new);
+        Ok(match self {
+            Expr::Alias(Alias { expr, name }) => Expr::Alias(Alias {
+                expr: map_box(expr)?,
+                name,
+            }),
+            Expr::Cast(Cast { expr, data_type }) => Expr::Cast(Cast {
+                expr: map_box(expr)?,
+                data_type,
+            }),
+            Expr::TryCast(TryCast { expr, data_type }) => Expr::TryCast(TryCast {
+                expr: map_box(expr)?,
+                data_type,
+            }),
+            Expr::UnaryExpr(UnaryExpr { op, expr }) => Expr::UnaryExpr(UnaryExpr {
+                op,
+                expr: map_box(expr)?,
+            }),
+            Expr::InSubquery(InSubquery {
+                expr,
+                subquery,
+                negated,
+            }) => Expr::InSubquery(InSubquery {
+                expr: map_box(expr)?,
+                subquery,
+                negated,
+            }),
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) => Expr::BinaryExpr(BinaryExpr {
+                left: map_box(left)?,
+                op,
+                right: map_box(right)?,
+            }),
+            Expr::InList(InList {
+                expr,
+                list,
+                negated,
+                ..
+            }) => {
+                let expr = map_box(expr)?;
+                let list = list
+                    .into_iter()
+                    .map(|item| map_box(Box::new(item)).map(|item| *item))
+                    .collect::<BustubxResult<Vec<Expr>>>()?;
+                Expr::InList(InList::new(expr, list, negated))
+            }
+            Expr::Between(Between {
+                expr,
+                negated,
+                low,
+                high,
+            }) => Expr::Between(Between {
+                expr: map_box(expr)?,
+                negated,
+                low: map_box(low)?,
+                high: map_box(high)?,
+            }),
+            Expr::Case(Case {
+                expr,
+                when_then_expr,
+                else_expr,
+            }) => {
+                let expr = expr.map(&mut map_box).transpose()?;
+                let when_then_expr = when_then_expr
+                    .into_iter()
+                    .map(|(when, then)| Ok((map_box(when)?, map_box(then)?)))
+                    .collect::<BustubxResult<Vec<_>>>()?;
+                let else_expr = else_expr.map(&mut map_box).transpose()?;
+                Expr::Case(Case {
+                    expr,
+                    when_then_expr,
+                    else_expr,
+                })
+            }
+            Expr::ScalarFunction(ScalarFunction { func, args }) => {
+                Expr::ScalarFunction(ScalarFunction {
+                    func,
+                    args: args
+                        .into_iter()
+                        .map(|arg| map_box(Box::new(arg)).map(|arg| *arg))
+                        .collect::<BustubxResult<Vec<Expr>>>()?,
+                })
+            }
+            Expr::AggregateFunction(AggregateFunction {
+                func,
+                args,
+                distinct,
+            }) => Expr::AggregateFunction(AggregateFunction {
+                func,
+                distinct,
+                args: args
+                    .into_iter()
+                    .map(|arg| map_box(Box::new(arg)).map(|arg| *arg))
+                    .collect::<BustubxResult<Vec<Expr>>>()?,
+            }),
+            expr @ (Expr::Column(_)
+            | Expr::Literal(_)
+            | Expr::ScalarSubquery(_)
+            | Expr::Exists(_)
+            | Expr::OuterReferenceColumn(_)) => expr,
+        })
+    }
+
+    /// Rewrite the expression bottom up, `f` sees each node after its children were rewritten.
+    pub fn transform_up<F>(self, f: &mut F) -> BustubxResult<Expr>
+    where
+        F: FnMut(Expr) -> BustubxResult<Expr>,
+    {
+        let expr = self.map_children(|child| child.transform_up(f))?;
+        f(expr)
+    }
+
+    /// Whether `predicate` holds for this expression or any of its sub-expressions.
+    pub fn exists<F>(&self, predicate: &F) -> bool
+    where
+        F: Fn(&Expr) -> bool,
+    {
+        predicate(self) || self.children().iter().any(|child| child.exists(predicate))
+    }
+
+    pub fn contains_aggregate(&self) -> bool {
+        self.exists(&|expr| matches!(expr, Expr::AggregateFunction(_)))
+    }
+
+    pub fn contains_subquery(&self) -> bool {
+        self.exists(&|expr| {
+            matches!(
+                expr,
+                Expr::ScalarSubquery(_) | Expr::Exists(_) | Expr::InSubquery(_)
+            )
+        })
+    }
+}
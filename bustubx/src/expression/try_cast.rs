@@ -0,0 +1,44 @@
+use crate::catalog::{Column, DataType, Schema};
+use crate::common::ScalarValue;
+use crate::expression::{Expr, ExprTrait};
+use crate::{BustubxResult, Tuple};
+
+/// Like `Cast`, but a value that can not be cast becomes NULL instead of an error
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TryCast {
+    /// The expression being cast
+    pub expr: Box<Expr>,
+    /// The `DataType` the expression will yield
+    pub data_type: DataType,
+}
+
+impl ExprTrait for TryCast {
+    fn data_type(&self, _input_schema: &Schema) -> BustubxResult<DataType> {
+        Ok(self.data_type)
+    }
+
+    fn nullable(&self, _input_schema: &Schema) -> BustubxResult<bool> {
+        Ok(true)
+    }
+
+    fn evaluate(&self, tuple: &Tuple) -> BustubxResult<ScalarValue> {
+        let value = self.expr.evaluate(tuple)?;
+        Ok(value
+            .cast_to(&self.data_type)
+            .unwrap_or_else(|_| ScalarValue::new_empty(self.data_type)))
+    }
+
+    fn to_column(&self, input_schema: &Schema) -> BustubxResult<Column> {
+        Ok(Column::new(
+            format!("{self}"),
+            self.data_type,
+            self.nullable(input_schema)?,
+        ))
+    }
+}
+
+impl std::fmt::Display for TryCast {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TRY_CAST {} AS {}", self.expr, self.data_type)
+    }
+}
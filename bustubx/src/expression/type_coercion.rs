@@ -0,0 +1,174 @@
+//! Implicit type coercion rules. The logical planner uses them to wrap operands in `Cast`s so
+//! both sides of an operator end up with types the operator can evaluate.
+
+use crate::catalog::DataType;
+use crate::common::decimal::DECIMAL128_MAX_PRECISION;
+
+/// The types both numeric operands are cast to before evaluating, e.g. `Int32 + Int64` is
+/// evaluated as `Int64 + Int64`. Decimal operands keep their own precision and scale.
+pub fn numeric_coercion(left_type: DataType, right_type: DataType) -> Option<(DataType, DataType)> {
+    use DataType::*;
+    if !left_type.is_numeric() || !right_type.is_numeric() {
+        return None;
+    }
+    let same = |data_type: DataType| Some((data_type, data_type));
+    match (left_type, right_type) {
+        (Float64, _) | (_, Float64) => same(Float64),
+        (Float32, Float32 | Int8 | Int16) | (Int8 | Int16, Float32) => same(Float32),
+        (Float32, _) | (_, Float32) => same(Float64),
+        (Decimal128(_, _), _)
+        | (_, Decimal128(_, _))
+        | (UInt64, Int8 | Int16 | Int32 | Int64)
+        | (Int8 | Int16 | Int32 | Int64, UInt64) => Some((
+            left_type.integer_as_decimal().unwrap_or(left_type),
+            right_type.integer_as_decimal().unwrap_or(right_type),
+        )),
+        (UInt64, UInt64) => same(UInt64),
+        (Int64, _) | (_, Int64) => same(Int64),
+        (Int32, _) | (_, Int32) => same(Int32),
+        (Int16, _) | (_, Int16) => same(Int16),
+        _ => same(Int8),
+    }
+}
+
+/// The types both operands of a comparison are cast to, `None` if they can not be compared.
+pub fn comparison_coercion(
+    left_type: DataType,
+    right_type: DataType,
+) -> Option<(DataType, DataType)> {
+    use DataType::*;
+    if left_type == right_type {
+        return Some((left_type, right_type));
+    }
+    match (left_type, right_type) {
+        (Null, _) => Some((right_type, right_type)),
+        (_, Null) => Some((left_type, left_type)),
+        (Varchar(_), Varchar(_)) => Some((left_type, right_type)),
+        (Date, Timestamp) | (Timestamp, Date) => Some((Timestamp, Timestamp)),
+        _ => numeric_coercion(left_type, right_type),
+    }
+}
+
+/// The type every value of a column built from `left_type` and `right_type` values can be
+/// cast to, e.g. for the rows of a multi-row VALUES.
+pub fn common_type(left_type: DataType, right_type: DataType) -> Option<DataType> {
+    use DataType::*;
+    match comparison_coercion(left_type, right_type)? {
+        (Decimal128(p1, s1), Decimal128(p2, s2)) => {
+            let scale = s1.max(s2);
+            let precision = ((p1 - s1).max(p2 - s2) + scale).min(DECIMAL128_MAX_PRECISION);
+            Some(Decimal128(precision, scale))
+        }
+        (Varchar(l1), Varchar(l2)) => Some(Varchar(l1.zip(l2).map(|(l1, l2)| l1.max(l2)))),
+        (data_type, _) => Some(data_type),
+    }
+}
+
+/// Whether a value of `from` can be stored into a column of type `to` without an explicit
+/// cast. Values that do not fit are still rejected when the cast is evaluated.
+pub fn can_coerce_from(from: DataType, to: DataType) -> bool {
+    use DataType::*;
+    from == to
+        || from == Null
+        || (from.is_numeric() && to.is_numeric())
+        || matches!(
+            (from, to),
+            (Varchar(_), Varchar(_)) | (Date, Timestamp) | (Timestamp, Date)
+        )
+}
+
+/// Whether an explicit `CAST` from `from` to `to` is supported. Values are still checked when
+/// the cast is evaluated, e.g. casting `'abc'` or `300` to a `TINYINT` fails.
+pub fn can_cast(from: DataType, to: DataType) -> bool {
+    use DataType::*;
+    from == to
+        || from == Null
+        || matches!(from, Varchar(_))
+        || matches!(to, Varchar(_))
+        || (from.is_numeric() && to.is_numeric())
+        || (from == Boolean && to.is_integer())
+        || (from.is_integer() && to == Boolean)
+        || matches!((from, to), (Date, Timestamp) | (Timestamp, Date | Time))
+}
+
+/// Whether a string literal can be read as a value of `data_type`, like a postgres literal of
+/// unknown type.
+pub fn can_coerce_string_literal(data_type: DataType) -> bool {
+    use DataType::*;
+    matches!(data_type, Varchar(_) | Date | Time | Timestamp | Interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{can_cast, can_coerce_from, common_type, comparison_coercion, numeric_coercion};
+    use crate::catalog::DataType;
+
+    #[test]
+    fn coercion_rules() {
+        assert_eq!(
+            numeric_coercion(DataType::Int32, DataType::Int64),
+            Some((DataType::Int64, DataType::Int64))
+        );
+        assert_eq!(
+            numeric_coercion(DataType::Int16, DataType::Float32),
+            Some((DataType::Float32, DataType::Float32))
+        );
+        assert_eq!(
+            numeric_coercion(DataType::Int64, DataType::Float32),
+            Some((DataType::Float64, DataType::Float64))
+        );
+        assert_eq!(
+            numeric_coercion(DataType::Int32, DataType::Decimal128(5, 2)),
+            Some((DataType::Decimal128(10, 0), DataType::Decimal128(5, 2)))
+        );
+        assert_eq!(
+            numeric_coercion(DataType::UInt64, DataType::Int8),
+            Some((DataType::Decimal128(20, 0), DataType::Decimal128(3, 0)))
+        );
+        assert_eq!(numeric_coercion(DataType::Int32, DataType::Date), None);
+
+        assert_eq!(
+            comparison_coercion(DataType::Date, DataType::Timestamp),
+            Some((DataType::Timestamp, DataType::Timestamp))
+        );
+        assert_eq!(
+            comparison_coercion(DataType::Varchar(Some(3)), DataType::Varchar(None)),
+            Some((DataType::Varchar(Some(3)), DataType::Varchar(None)))
+        );
+        assert_eq!(
+            comparison_coercion(DataType::Varchar(None), DataType::Int32),
+            None
+        );
+        assert_eq!(comparison_coercion(DataType::Boolean, DataType::Int8), None);
+
+        assert!(can_coerce_from(DataType::Float64, DataType::Int32));
+        assert!(can_coerce_from(DataType::Timestamp, DataType::Date));
+        assert!(!can_coerce_from(DataType::Int32, DataType::Varchar(None)));
+        assert!(!can_coerce_from(DataType::Interval, DataType::Time));
+
+        assert_eq!(
+            comparison_coercion(DataType::Null, DataType::Date),
+            Some((DataType::Date, DataType::Date))
+        );
+        assert_eq!(
+            common_type(DataType::Decimal128(5, 2), DataType::Int32),
+            Some(DataType::Decimal128(12, 2))
+        );
+        assert_eq!(
+            common_type(DataType::Null, DataType::Varchar(Some(4))),
+            Some(DataType::Varchar(Some(4)))
+        );
+        assert_eq!(
+            common_type(DataType::Null, DataType::Null),
+            Some(DataType::Null)
+        );
+        assert!(can_coerce_from(DataType::Null, DataType::Interval));
+
+        assert!(can_cast(DataType::Int32, DataType::Varchar(None)));
+        assert!(can_cast(DataType::Varchar(Some(8)), DataType::Date));
+        assert!(can_cast(DataType::Boolean, DataType::Int8));
+        assert!(can_cast(DataType::Timestamp, DataType::Time));
+        assert!(!can_cast(DataType::Boolean, DataType::Float64));
+        assert!(!can_cast(DataType::Date, DataType::Int32));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::catalog::DataType;
+use crate::common::ScalarValue;
+use crate::expression::type_coercion::can_coerce_from;
+use crate::expression::{
+    BuiltinAggregateFunction, BuiltinScalarFunction, ScalarFunctionDefinition,
+};
+use crate::{BustubxError, BustubxResult};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Rust implementation of a user defined scalar function, called once per row
+/// with arguments already cast to the declared argument types.
+pub type ScalarFunctionImplementation =
+    Arc<dyn Fn(&[ScalarValue]) -> BustubxResult<ScalarValue> + Send + Sync>;
+
+/// A user defined scalar function with a fixed signature
+#[derive(Clone)]
+pub struct ScalarUdf {
+    pub name: String,
+    pub arg_types: Vec<DataType>,
+    pub return_type: DataType,
+    pub fun: ScalarFunctionImplementation,
+}
+
+impl ScalarUdf {
+    pub fn new(
+        name: &str,
+        arg_types: Vec<DataType>,
+        return_type: DataType,
+        fun: ScalarFunctionImplementation,
+    ) -> Self {
+        Self {
+            name: name.to_lowercase(),
+            arg_types,
+            return_type,
+            fun,
+        }
+    }
+
+    pub(crate) fn check_arg_count(&self, count: usize) -> BustubxResult<()> {
+        if count == self.arg_types.len() {
+            Ok(())
+        } else {
+            Err(BustubxError::Plan(format!(
+                "function {} expects {} arguments but got {}",
+                self.name,
+                self.arg_types.len(),
+                count
+            )))
+        }
+    }
+
+    pub(crate) fn coerce_arg_types(&self, arg_types: &[DataType]) -> BustubxResult<Vec<DataType>> {
+        for (arg_type, expected) in arg_types.iter().zip(self.arg_types.iter()) {
+            if !can_coerce_from(*arg_type, *expected) {
+                return Err(BustubxError::Plan(format!(
+                    "function {} expects argument of type {} but got {}",
+                    self.name, expected, arg_type
+                )));
+            }
+        }
+        Ok(self.arg_types.clone())
+    }
+
+    pub(crate) fn invoke(&self, args: &[ScalarValue]) -> BustubxResult<ScalarValue> {
+        (self.fun)(args)?.cast_to(&self.return_type)
+    }
+}
+
+impl std::fmt::Debug for ScalarUdf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScalarUdf")
+            .field("name", &self.name)
+            .field("arg_types", &self.arg_types)
+            .field("return_type", &self.return_type)
+            .finish()
+    }
+}
+
+// functions are identified by their name and signature, the closure can not be compared
+impl PartialEq for ScalarUdf {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.arg_types == other.arg_types
+            && self.return_type == other.return_type
+    }
+}
+
+impl Eq for ScalarUdf {}
+
+/// Resolves function names to built-in functions and registered user defined functions
+#[derive(Debug, Default)]
+pub struct FunctionRegistry {
+    udfs: HashMap<String, Arc<ScalarUdf>>,
+}
+
+impl FunctionRegistry {
+    pub fn register_udf(&mut self, udf: ScalarUdf) -> BustubxResult<()> {
+        let reserved = matches!(
+            udf.name.as_str(),
+            "now" | "current_timestamp" | "current_date"
+        );
+        if reserved
+            || BuiltinScalarFunction::from_name(&udf.name).is_some()
+            || BuiltinAggregateFunction::from_name(&udf.name).is_some()
+        {
+            return Err(BustubxError::Plan(format!(
+                "function {} is a built-in function",
+                udf.name
+            )));
+        }
+        if self.udfs.contains_key(&udf.name) {
+            return Err(BustubxError::Plan(format!(
+                "function {} already exists",
+                udf.name
+            )));
+        }
+        self.udfs.insert(udf.name.clone(), Arc::new(udf));
+        Ok(())
+    }
+
+    pub fn resolve(&self, name: &str) -> Option<ScalarFunctionDefinition> {
+        let name = name.to_lowercase();
+        BuiltinScalarFunction::from_name(&name)
+            .map(ScalarFunctionDefinition::Builtin)
+            .or_else(|| {
+                self.udfs
+                    .get(&name)
+                    .cloned()
+                    .map(ScalarFunctionDefinition::Udf)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FunctionRegistry, ScalarUdf};
+    use crate::catalog::DataType;
+    use crate::common::ScalarValue;
+    use crate::expression::ScalarFunctionDefinition;
+    use std::sync::Arc;
+
+    #[test]
+    fn register_udf() {
+        let double = ScalarUdf::new(
+            "Double",
+            vec![DataType::Int64],
+            DataType::Int64,
+            Arc::new(|args| match &args[0] {
+                ScalarValue::Int64(v) => Ok(ScalarValue::Int64(v.map(|v| v * 2))),
+                _ => Ok(ScalarValue::Int64(None)),
+            }),
+        );
+        let mut registry = FunctionRegistry::default();
+        registry.register_udf(double.clone()).unwrap();
+        assert!(registry.register_udf(double.clone()).is_err());
+        assert!(registry
+            .register_udf(ScalarUdf::new(
+                "abs",
+                vec![],
+                DataType::Int64,
+                double.fun.clone()
+            ))
+            .is_err());
+
+        let Some(ScalarFunctionDefinition::Udf(udf)) = registry.resolve("DOUBLE") else {
+            panic!("double is not registered");
+        };
+        assert_eq!(
+            udf.invoke(&[ScalarValue::Int64(Some(21))]).unwrap(),
+            ScalarValue::Int64(Some(42))
+        );
+        assert!(udf.coerce_arg_types(&[DataType::Date]).is_err());
+        assert_eq!(
+            registry.resolve("abs"),
+            Some(ScalarFunctionDefinition::Builtin(
+                crate::expression::BuiltinScalarFunction::Abs
+            ))
+        );
+    }
+
+    #[test]
+    fn call_udf_from_sql() {
+        let mut db = crate::Database::new_temp().unwrap();
+        db.register_udf(ScalarUdf::new(
+            "double",
+            vec![DataType::Int64],
+            DataType::Int64,
+            Arc::new(|args| match &args[0] {
+                ScalarValue::Int64(v) => Ok(ScalarValue::Int64(v.map(|v| v * 2))),
+                _ => Ok(ScalarValue::Int64(None)),
+            }),
+        ))
+        .unwrap();
+        db.run("create table t1 (a int)").unwrap();
+        db.run("insert into t1 values (1), (NULL)").unwrap();
+        let tuples = db.run("select double(a) from t1").unwrap();
+        assert_eq!(tuples[0].data, vec![ScalarValue::Int64(Some(2))]);
+        assert_eq!(tuples[1].data, vec![ScalarValue::Int64(None)]);
+        assert!(db.run("select double(DATE '2024-01-01')").is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::catalog::{Column, DataType, Schema};
+use crate::common::ScalarValue;
+use crate::expression::{Expr, ExprTrait};
+use crate::storage::Tuple;
+use crate::{BustubxError, BustubxResult};
+
+/// Unary expression such as `NOT a` or `a IS NULL`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UnaryExpr {
+    /// The operator
+    pub op: UnaryOp,
+    /// The operand
+    pub expr: Box<Expr>,
+}
+
+impl ExprTrait for UnaryExpr {
+    fn data_type(&self, input_schema: &Schema) -> BustubxResult<DataType> {
+        let data_type = self.expr.data_type(input_schema)?;
+        match self.op {
+            UnaryOp::Minus => match data_type {
+                DataType::Int8
+                | DataType::Int16
+                | DataType::Int32
+                | DataType::Int64
+                | DataType::Float32
+                | DataType::Float64
+                | DataType::Decimal128(_, _)
+                | DataType::Interval
+                | DataType::Null => Ok(data_type),
+                _ => Err(BustubxError::Plan(format!(
+                    "operator {} is not supported for {}",
+                    self.op, data_type
+                ))),
+            },
+            UnaryOp::Not
+            | UnaryOp::IsNull
+            | UnaryOp::IsNotNull
+            | UnaryOp::IsTrue
+            | UnaryOp::IsFalse
+            | UnaryOp::IsNotTrue
+            | UnaryOp::IsNotFalse => Ok(DataType::Boolean),
+        }
+    }
+
+    fn nullable(&self, input_schema: &Schema) -> BustubxResult<bool> {
+        match self.op {
+            UnaryOp::Not | UnaryOp::Minus => self.expr.nullable(input_schema),
+            // IS predicates never return NULL
+            _ => Ok(false),
+        }
+    }
+
+    fn evaluate(&self, tuple: &Tuple) -> BustubxResult<ScalarValue> {
+        let value = self.expr.evaluate(tuple)?;
+        match self.op {
+            UnaryOp::Not => Ok(ScalarValue::Boolean(
+                self.boolean_operand(&value)?.map(|v| !v),
+            )),
+            UnaryOp::Minus => negate(&value),
+            UnaryOp::IsNull => Ok(ScalarValue::Boolean(Some(value.is_null()))),
+            UnaryOp::IsNotNull => Ok(ScalarValue::Boolean(Some(!value.is_null()))),
+            UnaryOp::IsTrue => Ok(ScalarValue::Boolean(Some(
+                self.boolean_operand(&value)? == Some(true),
+            ))),
+            UnaryOp::IsFalse => Ok(ScalarValue::Boolean(Some(
+                self.boolean_operand(&value)? == Some(false),
+            ))),
+            UnaryOp::IsNotTrue => Ok(ScalarValue::Boolean(Some(
+                self.boolean_operand(&value)? != Some(true),
+            ))),
+            UnaryOp::IsNotFalse => Ok(ScalarValue::Boolean(Some(
+                self.boolean_operand(&value)? != Some(false),
+            ))),
+        }
+    }
+
+    fn to_column(&self, input_schema: &Schema) -> BustubxResult<Column> {
+        Ok(Column::new(
+            format!("{self}"),
+            self.data_type(input_schema)?,
+            self.nullable(input_schema)?,
+        ))
+    }
+}
+
+impl UnaryExpr {
+    fn boolean_operand(&self, value: &ScalarValue) -> BustubxResult<Option<bool>> {
+        match value {
+            ScalarValue::Boolean(v) => Ok(*v),
+            _ => Err(BustubxError::Execution(format!(
+                "operator {} expects a boolean but got {}",
+                self.op, value
+            ))),
+        }
+    }
+}
+
+fn negate(value: &ScalarValue) -> BustubxResult<ScalarValue> {
+    let out_of_range = || BustubxError::Execution(format!("{} out of range", value.data_type()));
+    match value {
+        ScalarValue::Int8(v) => Ok(ScalarValue::Int8(
+            v.map(|v| v.checked_neg().ok_or_else(out_of_range))
+                .transpose()?,
+        )),
+        ScalarValue::Int16(v) => Ok(ScalarValue::Int16(
+            v.map(|v| v.checked_neg().ok_or_else(out_of_range))
+                .transpose()?,
+        )),
+        ScalarValue::Int32(v) => Ok(ScalarValue::Int32(
+            v.map(|v| v.checked_neg().ok_or_else(out_of_range))
+                .transpose()?,
+        )),
+        ScalarValue::Int64(v) => Ok(ScalarValue::Int64(
+            v.map(|v| v.checked_neg().ok_or_else(out_of_range))
+                .transpose()?,
+        )),
+        ScalarValue::Float32(v) => Ok(ScalarValue::Float32(v.map(|v| -v))),
+        ScalarValue::Float64(v) => Ok(ScalarValue::Float64(v.map(|v| -v))),
+        ScalarValue::Decimal128(v, precision, scale) => {
+            Ok(ScalarValue::Decimal128(v.map(|v| -v), *precision, *scale))
+        }
+        ScalarValue::Null => Ok(ScalarValue::Null),
+        ScalarValue::Interval(v) => Ok(ScalarValue::Interval(v.map(|v| v.negate()).transpose()?)),
+        _ => Err(BustubxError::Execution(format!(
+            "operator {} is not supported for {}",
+            UnaryOp::Minus,
+            value.data_type()
+        ))),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnaryOp {
+    Not,
+    Minus,
+    IsNull,
+    IsNotNull,
+    IsTrue,
+    IsFalse,
+    IsNotTrue,
+    IsNotFalse,
+}
+
+impl std::fmt::Display for UnaryExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.op {
+            UnaryOp::Not => write!(f, "NOT {}", self.expr),
+            UnaryOp::Minus => write!(f, "-{}", self.expr),
+            _ => write!(f, "{} {}", self.expr, self.op),
+        }
+    }
+}
+
+impl std::fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = match self {
+            UnaryOp::Not => "NOT",
+            UnaryOp::Minus => "-",
+            UnaryOp::IsNull => "IS NULL",
+            UnaryOp::IsNotNull => "IS NOT NULL",
+            UnaryOp::IsTrue => "IS TRUE",
+            UnaryOp::IsFalse => "IS FALSE",
+            UnaryOp::IsNotTrue => "IS NOT TRUE",
+            UnaryOp::IsNotFalse => "IS NOT FALSE",
+        };
+        write!(f, "{op}")
+    }
+}
@@ -10,7 +10,10 @@ mod parser;
 mod planner;
 mod storage;
 
+pub use catalog::DataType;
 pub use common::util::pretty_format_tuples;
+pub use common::ScalarValue;
 pub use database::Database;
 pub use error::{BustubxError, BustubxResult};
+pub use expression::{ScalarFunctionImplementation, ScalarUdf};
 pub use storage::Tuple;
@@ -1,11 +1,82 @@
 use crate::error::BustubxResult;
-use sqlparser::{ast::Statement, dialect::PostgreSqlDialect, parser::Parser};
+use sqlparser::ast::{Query, SetExpr, Statement, Values};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+use sqlparser::{dialect::PostgreSqlDialect, parser::Parser};
 
 pub fn parse_sql(sql: &str) -> BustubxResult<Vec<Statement>> {
-    let stmts = Parser::parse_sql(&PostgreSqlDialect {}, sql)?;
+    let dialect = PostgreSqlDialect {};
+    let mut parser = Parser::new(&dialect).try_with_sql(sql)?;
+    // same as Parser::parse_statements, with the statements sqlparser does not know
+    let mut stmts = Vec::new();
+    let mut expecting_statement_delimiter = false;
+    loop {
+        while parser.consume_token(&Token::SemiColon) {
+            expecting_statement_delimiter = false;
+        }
+        if parser.peek_token() == Token::EOF {
+            break;
+        }
+        if expecting_statement_delimiter {
+            return Ok(parser.expected("end of statement", parser.peek_token())?);
+        }
+        let stmt = match parse_insert_default_values(&mut parser)? {
+            Some(stmt) => stmt,
+            None => parser.parse_statement()?,
+        };
+        stmts.push(stmt);
+        expecting_statement_delimiter = true;
+    }
     Ok(stmts)
 }
 
+/// `INSERT INTO t DEFAULT VALUES` becomes an insert of a single empty VALUES row, which the
+/// planner fills with the defaults of all columns.
+fn parse_insert_default_values(parser: &mut Parser) -> Result<Option<Statement>, ParserError> {
+    let is_keyword = |n: usize, keyword: Keyword| matches!(parser.peek_nth_token(n).token, Token::Word(w) if w.keyword == keyword);
+    if !is_keyword(0, Keyword::INSERT) || !is_keyword(1, Keyword::INTO) {
+        return Ok(None);
+    }
+    // the table name is a dot separated list of identifiers
+    let mut n = 2;
+    while matches!(parser.peek_nth_token(n + 1).token, Token::Period) {
+        n += 2;
+    }
+    if !is_keyword(n + 1, Keyword::DEFAULT) || !is_keyword(n + 2, Keyword::VALUES) {
+        return Ok(None);
+    }
+
+    parser.expect_keywords(&[Keyword::INSERT, Keyword::INTO])?;
+    let table_name = parser.parse_object_name()?;
+    parser.expect_keywords(&[Keyword::DEFAULT, Keyword::VALUES])?;
+    let source = Query {
+        with: None,
+        body: Box::new(SetExpr::Values(Values {
+            explicit_row: false,
+            rows: vec![vec![]],
+        })),
+        order_by: vec![],
+        limit: None,
+        offset: None,
+        fetch: None,
+        locks: vec![],
+    };
+    Ok(Some(Statement::Insert {
+        or: None,
+        into: true,
+        table_name,
+        columns: vec![],
+        overwrite: false,
+        source: Box::new(source),
+        partitioned: None,
+        after_columns: vec![],
+        table: false,
+        on: None,
+        returning: None,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -32,4 +103,15 @@ mod tests {
             Err(e) => println!("Error parsing SQL: {:?}", e),
         }
     }
+
+    #[test]
+    pub fn test_insert_default_values() {
+        let sql = "INSERT INTO t1 DEFAULT VALUES; INSERT INTO t1 VALUES (1)";
+        let stmts = super::parse_sql(sql).unwrap();
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0].to_string(), "INSERT INTO t1 VALUES ()");
+        assert_eq!(stmts[1].to_string(), "INSERT INTO t1 VALUES (1)");
+
+        assert!(super::parse_sql("INSERT INTO t1 DEFAULT VALUES (1)").is_err());
+    }
 }
@@ -0,0 +1,34 @@
+use crate::catalog::SchemaRef;
+use crate::expression::Expr;
+use crate::planner::logical_plan::LogicalPlan;
+use std::sync::Arc;
+
+/// Groups the input rows by `group_exprs` and computes `aggr_exprs` for every group. Without
+/// group expressions all rows form a single group, even when there are none.
+#[derive(derive_new::new, Debug, Clone)]
+pub struct Aggregate {
+    pub input: Arc<LogicalPlan>,
+    pub group_exprs: Vec<Expr>,
+    /// `AggregateFunction` expressions
+    pub aggr_exprs: Vec<Expr>,
+    /// The group columns followed by the aggregate columns
+    pub schema: SchemaRef,
+}
+
+impl std::fmt::Display for Aggregate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |exprs: &[Expr]| {
+            exprs
+                .iter()
+                .map(|e| format!("{e}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        write!(
+            f,
+            "Aggregate: groupBy=[{}], aggr=[{}]",
+            join(&self.group_exprs),
+            join(&self.aggr_exprs)
+        )
+    }
+}
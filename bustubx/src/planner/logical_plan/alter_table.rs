@@ -0,0 +1,34 @@
+use crate::catalog::Column;
+use crate::common::TableReference;
+use crate::expression::Expr;
+
+#[derive(derive_new::new, Debug, Clone)]
+pub struct AlterTable {
+    pub table: TableReference,
+    pub operation: AlterTableOperation,
+}
+
+#[derive(Debug, Clone)]
+pub enum AlterTableOperation {
+    /// Existing rows get the value of `default`
+    AddColumn {
+        column: Column,
+        default: Expr,
+    },
+    DropColumn {
+        index: usize,
+    },
+    RenameColumn {
+        index: usize,
+        new_name: String,
+    },
+    RenameTable {
+        new_table: TableReference,
+    },
+}
+
+impl std::fmt::Display for AlterTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AlterTable: {}", self.table)
+    }
+}
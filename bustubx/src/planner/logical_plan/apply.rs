@@ -0,0 +1,21 @@
+use crate::catalog::SchemaRef;
+use crate::expression::Expr;
+use crate::planner::logical_plan::LogicalPlan;
+use std::sync::Arc;
+
+/// Evaluates a subquery expression for every input row and appends its value as a new column.
+/// Used for subqueries the planner can not turn into a join.
+#[derive(derive_new::new, Debug, Clone)]
+pub struct Apply {
+    pub input: Arc<LogicalPlan>,
+    /// A `ScalarSubquery`, `Exists` or `InSubquery` expression
+    pub subquery: Expr,
+    /// The input columns followed by the subquery column
+    pub schema: SchemaRef,
+}
+
+impl std::fmt::Display for Apply {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Apply: {}", self.subquery)
+    }
+}
@@ -0,0 +1,19 @@
+use crate::catalog::SchemaRef;
+use crate::common::TableReference;
+use crate::planner::logical_plan::LogicalPlan;
+use std::sync::Arc;
+
+/// Deletes the rows of `table` produced by `input`, whose rows are the columns of the table
+/// followed by their rid.
+#[derive(derive_new::new, Debug, Clone)]
+pub struct Delete {
+    pub table: TableReference,
+    pub table_schema: SchemaRef,
+    pub input: Arc<LogicalPlan>,
+}
+
+impl std::fmt::Display for Delete {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Delete: {}", self.table)
+    }
+}
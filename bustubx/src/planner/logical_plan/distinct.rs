@@ -0,0 +1,14 @@
+use crate::planner::logical_plan::LogicalPlan;
+use std::sync::Arc;
+
+/// Removes duplicate rows of the input, NULL values are equal to each other.
+#[derive(derive_new::new, Debug, Clone)]
+pub struct Distinct {
+    pub input: Arc<LogicalPlan>,
+}
+
+impl std::fmt::Display for Distinct {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Distinct")
+    }
+}
@@ -0,0 +1,22 @@
+use crate::common::TableReference;
+
+#[derive(derive_new::new, Debug, Clone)]
+pub struct DropIndex {
+    /// Index names with the tables they belong to
+    pub indexes: Vec<(TableReference, String)>,
+    pub if_exists: bool,
+}
+
+impl std::fmt::Display for DropIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DropIndex: {}",
+            self.indexes
+                .iter()
+                .map(|(_, name)| name.clone())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
@@ -0,0 +1,21 @@
+use crate::common::TableReference;
+
+#[derive(derive_new::new, Debug, Clone)]
+pub struct DropTable {
+    pub tables: Vec<TableReference>,
+    pub if_exists: bool,
+}
+
+impl std::fmt::Display for DropTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DropTable: {}",
+            self.tables
+                .iter()
+                .map(|t| t.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
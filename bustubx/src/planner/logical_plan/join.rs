@@ -25,6 +25,10 @@ pub enum JoinType {
     // select * from x, y
     // select * from x cross join y
     Cross,
+    // left rows with at least one match, e.g. from `x where exists (select ... y)`
+    LeftSemi,
+    // left rows without any match, e.g. from `x where not exists (select ... y)`
+    LeftAnti,
 }
 
 impl std::fmt::Display for Join {
@@ -1,58 +1,98 @@
+mod aggregate;
+mod alter_table;
+mod apply;
 mod create_index;
 mod create_table;
+mod delete;
+mod distinct;
+mod drop_index;
+mod drop_table;
 mod empty_relation;
 mod filter;
 mod insert;
 mod join;
 mod limit;
 mod project;
+mod set_operation;
 mod sort;
 mod table_scan;
+mod update;
 mod util;
 mod values;
 
+pub use aggregate::Aggregate;
+pub use alter_table::{AlterTable, AlterTableOperation};
+pub use apply::Apply;
 pub use create_index::CreateIndex;
 pub use create_table::CreateTable;
+pub use delete::Delete;
+pub use distinct::Distinct;
+pub use drop_index::DropIndex;
+pub use drop_table::DropTable;
 pub use empty_relation::EmptyRelation;
 pub use filter::Filter;
 pub use insert::Insert;
 pub use join::{Join, JoinType};
 pub use limit::Limit;
 pub use project::Project;
+pub use set_operation::{SetOperation, SetOperator};
 pub use sort::{OrderByExpr, Sort};
-pub use table_scan::TableScan;
+pub use table_scan::{TableScan, RID_COLUMN_NAME};
+pub use update::Update;
 pub use util::*;
 pub use values::Values;
 
-use crate::catalog::{SchemaRef, EMPTY_SCHEMA_REF, INSERT_OUTPUT_SCHEMA_REF};
+use crate::catalog::{
+    SchemaRef, DELETE_OUTPUT_SCHEMA_REF, EMPTY_SCHEMA_REF, INSERT_OUTPUT_SCHEMA_REF,
+    UPDATE_OUTPUT_SCHEMA_REF,
+};
+use crate::expression::Expr;
 use crate::{BustubxError, BustubxResult};
 use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub enum LogicalPlan {
+    Aggregate(Aggregate),
+    Apply(Apply),
     CreateTable(CreateTable),
     CreateIndex(CreateIndex),
+    DropTable(DropTable),
+    DropIndex(DropIndex),
+    AlterTable(AlterTable),
+    Delete(Delete),
+    Distinct(Distinct),
     Filter(Filter),
     Insert(Insert),
     Join(Join),
     Limit(Limit),
     Project(Project),
+    SetOperation(SetOperation),
     TableScan(TableScan),
     Sort(Sort),
     Values(Values),
+    Update(Update),
     EmptyRelation(EmptyRelation),
 }
 
 impl LogicalPlan {
     pub fn schema(&self) -> &SchemaRef {
         match self {
+            LogicalPlan::Aggregate(Aggregate { schema, .. }) => schema,
+            LogicalPlan::Apply(Apply { schema, .. }) => schema,
             LogicalPlan::CreateTable(_) => &EMPTY_SCHEMA_REF,
             LogicalPlan::CreateIndex(_) => &EMPTY_SCHEMA_REF,
+            LogicalPlan::DropTable(_) => &EMPTY_SCHEMA_REF,
+            LogicalPlan::DropIndex(_) => &EMPTY_SCHEMA_REF,
+            LogicalPlan::AlterTable(_) => &EMPTY_SCHEMA_REF,
+            LogicalPlan::Delete(_) => &DELETE_OUTPUT_SCHEMA_REF,
+            LogicalPlan::Update(_) => &UPDATE_OUTPUT_SCHEMA_REF,
+            LogicalPlan::Distinct(Distinct { input }) => input.schema(),
             LogicalPlan::Filter(Filter { input, .. }) => input.schema(),
             LogicalPlan::Insert(_) => &INSERT_OUTPUT_SCHEMA_REF,
             LogicalPlan::Join(Join { schema, .. }) => schema,
             LogicalPlan::Limit(Limit { input, .. }) => input.schema(),
             LogicalPlan::Project(Project { schema, .. }) => schema,
+            LogicalPlan::SetOperation(SetOperation { schema, .. }) => schema,
             LogicalPlan::TableScan(TableScan { table_schema, .. }) => table_schema,
             LogicalPlan::Sort(Sort { input, .. }) => input.schema(),
             LogicalPlan::Values(Values { schema, .. }) => schema,
@@ -62,14 +102,23 @@ impl LogicalPlan {
 
     pub fn inputs(&self) -> Vec<&LogicalPlan> {
         match self {
+            LogicalPlan::Aggregate(Aggregate { input, .. }) => vec![input],
+            LogicalPlan::Apply(Apply { input, .. }) => vec![input],
+            LogicalPlan::Delete(Delete { input, .. }) => vec![input],
+            LogicalPlan::Distinct(Distinct { input }) => vec![input],
             LogicalPlan::Filter(Filter { input, .. }) => vec![input],
             LogicalPlan::Insert(Insert { input, .. }) => vec![input],
             LogicalPlan::Join(Join { left, right, .. }) => vec![left, right],
             LogicalPlan::Limit(Limit { input, .. }) => vec![input],
             LogicalPlan::Project(Project { input, .. }) => vec![input],
+            LogicalPlan::SetOperation(SetOperation { left, right, .. }) => vec![left, right],
             LogicalPlan::Sort(Sort { input, .. }) => vec![input],
+            LogicalPlan::Update(Update { input, .. }) => vec![input],
             LogicalPlan::CreateTable(_)
             | LogicalPlan::CreateIndex(_)
+            | LogicalPlan::DropTable(_)
+            | LogicalPlan::DropIndex(_)
+            | LogicalPlan::AlterTable(_)
             | LogicalPlan::TableScan(_)
             | LogicalPlan::Values(_)
             | LogicalPlan::EmptyRelation(_) => vec![],
@@ -78,6 +127,95 @@ impl LogicalPlan {
 
     pub fn with_new_inputs(&self, inputs: &[LogicalPlan]) -> BustubxResult<LogicalPlan> {
         match self {
+            LogicalPlan::Aggregate(Aggregate {
+                group_exprs,
+                aggr_exprs,
+                schema,
+                ..
+            }) => Ok(LogicalPlan::Aggregate(Aggregate {
+                group_exprs: group_exprs.clone(),
+                aggr_exprs: aggr_exprs.clone(),
+                schema: schema.clone(),
+                input: Arc::new(
+                    inputs
+                        .first()
+                        .ok_or_else(|| {
+                            BustubxError::Internal(format!(
+                                "inputs {:?} should have at least one",
+                                inputs
+                            ))
+                        })?
+                        .clone(),
+                ),
+            })),
+            LogicalPlan::Apply(Apply {
+                subquery, schema, ..
+            }) => Ok(LogicalPlan::Apply(Apply {
+                subquery: subquery.clone(),
+                schema: schema.clone(),
+                input: Arc::new(
+                    inputs
+                        .first()
+                        .ok_or_else(|| {
+                            BustubxError::Internal(format!(
+                                "inputs {:?} should have at least one",
+                                inputs
+                            ))
+                        })?
+                        .clone(),
+                ),
+            })),
+            LogicalPlan::Delete(Delete {
+                table,
+                table_schema,
+                ..
+            }) => Ok(LogicalPlan::Delete(Delete {
+                table: table.clone(),
+                table_schema: table_schema.clone(),
+                input: Arc::new(
+                    inputs
+                        .first()
+                        .ok_or_else(|| {
+                            BustubxError::Internal(format!(
+                                "inputs {:?} should have at least one",
+                                inputs
+                            ))
+                        })?
+                        .clone(),
+                ),
+            })),
+            LogicalPlan::Update(Update {
+                table,
+                table_schema,
+                ..
+            }) => Ok(LogicalPlan::Update(Update {
+                table: table.clone(),
+                table_schema: table_schema.clone(),
+                input: Arc::new(
+                    inputs
+                        .first()
+                        .ok_or_else(|| {
+                            BustubxError::Internal(format!(
+                                "inputs {:?} should have at least one",
+                                inputs
+                            ))
+                        })?
+                        .clone(),
+                ),
+            })),
+            LogicalPlan::Distinct(_) => Ok(LogicalPlan::Distinct(Distinct {
+                input: Arc::new(
+                    inputs
+                        .first()
+                        .ok_or_else(|| {
+                            BustubxError::Internal(format!(
+                                "inputs {:?} should have at least one",
+                                inputs
+                            ))
+                        })?
+                        .clone(),
+                ),
+            })),
             LogicalPlan::Filter(Filter { predicate, .. }) => Ok(LogicalPlan::Filter(Filter {
                 predicate: predicate.clone(),
                 input: Arc::new(
@@ -132,7 +270,7 @@ impl LogicalPlan {
                 ),
                 right: Arc::new(
                     inputs
-                        .get(0)
+                        .get(1)
                         .ok_or_else(|| {
                             BustubxError::Internal(format!(
                                 "inputs {:?} should have at least two",
@@ -177,6 +315,35 @@ impl LogicalPlan {
                     ),
                 }))
             }
+            LogicalPlan::SetOperation(SetOperation {
+                op, all, schema, ..
+            }) => Ok(LogicalPlan::SetOperation(SetOperation {
+                op: *op,
+                all: *all,
+                left: Arc::new(
+                    inputs
+                        .first()
+                        .ok_or_else(|| {
+                            BustubxError::Internal(format!(
+                                "inputs {:?} should have at least two",
+                                inputs
+                            ))
+                        })?
+                        .clone(),
+                ),
+                right: Arc::new(
+                    inputs
+                        .get(1)
+                        .ok_or_else(|| {
+                            BustubxError::Internal(format!(
+                                "inputs {:?} should have at least two",
+                                inputs
+                            ))
+                        })?
+                        .clone(),
+                ),
+                schema: schema.clone(),
+            })),
             LogicalPlan::Sort(Sort {
                 order_by, limit, ..
             }) => Ok(LogicalPlan::Sort(Sort {
@@ -196,26 +363,159 @@ impl LogicalPlan {
             })),
             LogicalPlan::CreateTable(_)
             | LogicalPlan::CreateIndex(_)
+            | LogicalPlan::DropTable(_)
+            | LogicalPlan::DropIndex(_)
+            | LogicalPlan::AlterTable(_)
             | LogicalPlan::TableScan(_)
             | LogicalPlan::Values(_)
             | LogicalPlan::EmptyRelation(_) => Ok(self.clone()),
         }
     }
+
+    /// The expressions evaluated by this node, not including those of its inputs.
+    pub fn expressions(&self) -> Vec<&Expr> {
+        match self {
+            LogicalPlan::Aggregate(Aggregate {
+                group_exprs,
+                aggr_exprs,
+                ..
+            }) => group_exprs.iter().chain(aggr_exprs.iter()).collect(),
+            LogicalPlan::Apply(Apply { subquery, .. }) => vec![subquery],
+            LogicalPlan::Filter(Filter { predicate, .. }) => vec![predicate],
+            LogicalPlan::Join(Join { condition, .. }) => condition.iter().collect(),
+            LogicalPlan::Project(Project { exprs, .. }) => exprs.iter().collect(),
+            LogicalPlan::TableScan(TableScan { filters, .. }) => filters.iter().collect(),
+            LogicalPlan::Sort(Sort { order_by, .. }) => {
+                order_by.iter().map(|order| order.expr.as_ref()).collect()
+            }
+            LogicalPlan::Values(Values { values, .. }) => values.iter().flatten().collect(),
+            LogicalPlan::CreateTable(_)
+            | LogicalPlan::CreateIndex(_)
+            | LogicalPlan::DropTable(_)
+            | LogicalPlan::DropIndex(_)
+            | LogicalPlan::AlterTable(_)
+            | LogicalPlan::Delete(_)
+            | LogicalPlan::Distinct(_)
+            | LogicalPlan::Insert(_)
+            | LogicalPlan::Limit(_)
+            | LogicalPlan::SetOperation(_)
+            | LogicalPlan::Update(_)
+            | LogicalPlan::EmptyRelation(_) => vec![],
+        }
+    }
+
+    /// Rebuild this node with `f` applied to each of its expressions, schemas are kept.
+    pub fn map_expressions<F>(&self, mut f: F) -> BustubxResult<LogicalPlan>
+    where
+        F: FnMut(Expr) -> BustubxResult<Expr>,
+    {
+        Ok(match self {
+            LogicalPlan::Aggregate(aggregate) => LogicalPlan::Aggregate(Aggregate {
+                group_exprs: aggregate
+                    .group_exprs
+                    .iter()
+                    .map(|expr| f(expr.clone()))
+                    .collect::<BustubxResult<Vec<Expr>>>()?,
+                aggr_exprs: aggregate
+                    .aggr_exprs
+                    .iter()
+                    .map(|expr| f(expr.clone()))
+                    .collect::<BustubxResult<Vec<Expr>>>()?,
+                ..aggregate.clone()
+            }),
+            LogicalPlan::Apply(apply) => LogicalPlan::Apply(Apply {
+                subquery: f(apply.subquery.clone())?,
+                ..apply.clone()
+            }),
+            LogicalPlan::Filter(filter) => LogicalPlan::Filter(Filter {
+                predicate: f(filter.predicate.clone())?,
+                input: filter.input.clone(),
+            }),
+            LogicalPlan::Join(join) => LogicalPlan::Join(Join {
+                condition: join.condition.clone().map(&mut f).transpose()?,
+                ..join.clone()
+            }),
+            LogicalPlan::Project(project) => LogicalPlan::Project(Project {
+                exprs: project
+                    .exprs
+                    .iter()
+                    .map(|expr| f(expr.clone()))
+                    .collect::<BustubxResult<Vec<Expr>>>()?,
+                ..project.clone()
+            }),
+            LogicalPlan::TableScan(scan) => LogicalPlan::TableScan(TableScan {
+                filters: scan
+                    .filters
+                    .iter()
+                    .map(|expr| f(expr.clone()))
+                    .collect::<BustubxResult<Vec<Expr>>>()?,
+                ..scan.clone()
+            }),
+            LogicalPlan::Sort(sort) => {
+                let mut order_by = vec![];
+                for order in sort.order_by.iter() {
+                    order_by.push(OrderByExpr {
+                        expr: Box::new(f(order.expr.as_ref().clone())?),
+                        ..order.clone()
+                    });
+                }
+                LogicalPlan::Sort(Sort {
+                    order_by,
+                    ..sort.clone()
+                })
+            }
+            LogicalPlan::Values(values) => {
+                let mut rows = vec![];
+                for row in values.values.iter() {
+                    rows.push(
+                        row.iter()
+                            .map(|expr| f(expr.clone()))
+                            .collect::<BustubxResult<Vec<Expr>>>()?,
+                    );
+                }
+                LogicalPlan::Values(Values {
+                    schema: values.schema.clone(),
+                    values: rows,
+                })
+            }
+            LogicalPlan::CreateTable(_)
+            | LogicalPlan::CreateIndex(_)
+            | LogicalPlan::DropTable(_)
+            | LogicalPlan::DropIndex(_)
+            | LogicalPlan::AlterTable(_)
+            | LogicalPlan::Delete(_)
+            | LogicalPlan::Distinct(_)
+            | LogicalPlan::Insert(_)
+            | LogicalPlan::Limit(_)
+            | LogicalPlan::SetOperation(_)
+            | LogicalPlan::Update(_)
+            | LogicalPlan::EmptyRelation(_) => self.clone(),
+        })
+    }
 }
 
 impl std::fmt::Display for LogicalPlan {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            LogicalPlan::Aggregate(v) => write!(f, "{v}"),
+            LogicalPlan::Apply(v) => write!(f, "{v}"),
             LogicalPlan::CreateTable(v) => write!(f, "{v}"),
             LogicalPlan::CreateIndex(v) => write!(f, "{v}"),
+            LogicalPlan::DropTable(v) => write!(f, "{v}"),
+            LogicalPlan::DropIndex(v) => write!(f, "{v}"),
+            LogicalPlan::AlterTable(v) => write!(f, "{v}"),
+            LogicalPlan::Delete(v) => write!(f, "{v}"),
+            LogicalPlan::Distinct(v) => write!(f, "{v}"),
             LogicalPlan::Filter(v) => write!(f, "{v}"),
             LogicalPlan::Insert(v) => write!(f, "{v}"),
             LogicalPlan::Join(v) => write!(f, "{v}"),
             LogicalPlan::Limit(v) => write!(f, "{v}"),
             LogicalPlan::Project(v) => write!(f, "{v}"),
+            LogicalPlan::SetOperation(v) => write!(f, "{v}"),
             LogicalPlan::TableScan(v) => write!(f, "{v}"),
             LogicalPlan::Sort(v) => write!(f, "{v}"),
             LogicalPlan::Values(v) => write!(f, "{v}"),
+            LogicalPlan::Update(v) => write!(f, "{v}"),
             LogicalPlan::EmptyRelation(v) => write!(f, "{v}"),
         }
     }
@@ -0,0 +1,42 @@
+use crate::catalog::SchemaRef;
+use crate::planner::logical_plan::LogicalPlan;
+use std::sync::Arc;
+
+/// Combines the rows of two inputs with the same number of columns. Rows are compared as a
+/// whole and NULL values are equal to each other.
+#[derive(derive_new::new, Debug, Clone)]
+pub struct SetOperation {
+    pub op: SetOperator,
+    /// Whether duplicate rows are kept, e.g. `UNION ALL`
+    pub all: bool,
+    pub left: Arc<LogicalPlan>,
+    pub right: Arc<LogicalPlan>,
+    /// Named after the left input, with the common type of both inputs for every column
+    pub schema: SchemaRef,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOperator {
+    // select ... union select ...
+    Union,
+    // select ... intersect select ...
+    Intersect,
+    // select ... except select ...
+    Except,
+}
+
+impl std::fmt::Display for SetOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.op)?;
+        if self.all {
+            write!(f, " All")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for SetOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
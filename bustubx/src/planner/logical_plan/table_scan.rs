@@ -2,12 +2,18 @@ use crate::catalog::SchemaRef;
 use crate::common::TableReference;
 use crate::expression::Expr;
 
+/// Name of the column holding the rid of each row when a scan is made `with_rid`.
+pub const RID_COLUMN_NAME: &str = "__rid";
+
 #[derive(derive_new::new, Debug, Clone)]
 pub struct TableScan {
     pub table_ref: TableReference,
     pub table_schema: SchemaRef,
     pub filters: Vec<Expr>,
     pub limit: Option<usize>,
+    /// Appends the rid of every row as a last [`RID_COLUMN_NAME`] column, so that DELETE and
+    /// UPDATE change exactly the rows their input produced.
+    pub with_rid: bool,
 }
 
 impl std::fmt::Display for TableScan {
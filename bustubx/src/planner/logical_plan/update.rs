@@ -0,0 +1,19 @@
+use crate::catalog::SchemaRef;
+use crate::common::TableReference;
+use crate::planner::logical_plan::LogicalPlan;
+use std::sync::Arc;
+
+/// Replaces rows of `table`. Every row of `input` is a row of the table followed by its new
+/// values, one for each column of the table, and its rid.
+#[derive(derive_new::new, Debug, Clone)]
+pub struct Update {
+    pub table: TableReference,
+    pub table_schema: SchemaRef,
+    pub input: Arc<LogicalPlan>,
+}
+
+impl std::fmt::Display for Update {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Update: {}", self.table)
+    }
+}
@@ -1,7 +1,8 @@
 use crate::catalog::{ColumnRef, Schema};
-use crate::expression::{Expr, ExprTrait};
+use crate::expression::{Exists, Expr, ExprTrait, InSubquery, Literal, Subquery};
 use crate::planner::logical_plan::JoinType;
 use crate::planner::logical_plan::LogicalPlan;
+use crate::storage::Tuple;
 use crate::BustubxResult;
 use std::sync::Arc;
 
@@ -39,6 +40,7 @@ pub fn build_join_schema(
             .chain(&nullify_columns(right_cols))
             .cloned()
             .collect(),
+        JoinType::LeftSemi | JoinType::LeftAnti => left_cols.clone(),
     };
     Ok(Schema { columns })
 }
@@ -51,3 +53,69 @@ pub fn project_schema(input: &LogicalPlan, exprs: &[Expr]) -> BustubxResult<Sche
     }
     Ok(Schema::new(columns))
 }
+
+/// Whether `plan`, or a subquery within it, references a column of an enclosing query.
+pub fn plan_has_outer_references(plan: &LogicalPlan) -> bool {
+    plan.expressions()
+        .into_iter()
+        .any(expr_has_outer_references)
+        || plan.inputs().into_iter().any(plan_has_outer_references)
+}
+
+pub fn expr_has_outer_references(expr: &Expr) -> bool {
+    expr.exists(&|expr| match expr {
+        Expr::OuterReferenceColumn(_) => true,
+        Expr::ScalarSubquery(subquery)
+        | Expr::Exists(Exists { subquery, .. })
+        | Expr::InSubquery(InSubquery { subquery, .. }) => {
+            plan_has_outer_references(&subquery.subquery)
+        }
+        _ => false,
+    })
+}
+
+/// Replace the outer references of `plan` which resolve to a column of `tuple` by the values
+/// of that row, including those of nested subqueries.
+pub fn replace_outer_references(plan: &LogicalPlan, tuple: &Tuple) -> BustubxResult<LogicalPlan> {
+    let inputs = plan
+        .inputs()
+        .into_iter()
+        .map(|input| replace_outer_references(input, tuple))
+        .collect::<BustubxResult<Vec<LogicalPlan>>>()?;
+    plan.with_new_inputs(&inputs)?
+        .map_expressions(|expr| expr.transform_up(&mut |expr| replace_outer_values(expr, tuple)))
+}
+
+fn replace_outer_values(expr: Expr, tuple: &Tuple) -> BustubxResult<Expr> {
+    let replace_subquery = |subquery: Subquery| -> BustubxResult<Subquery> {
+        Ok(Subquery {
+            subquery: Arc::new(replace_outer_references(&subquery.subquery, tuple)?),
+        })
+    };
+    Ok(match expr {
+        Expr::OuterReferenceColumn(outer) => {
+            match tuple.value_by_name(outer.column.relation.as_ref(), &outer.column.name) {
+                Ok(value) => Expr::Literal(Literal {
+                    value: value.cast_to(&outer.data_type)?,
+                }),
+                // a column of a query further out
+                Err(_) => Expr::OuterReferenceColumn(outer),
+            }
+        }
+        Expr::ScalarSubquery(subquery) => Expr::ScalarSubquery(replace_subquery(subquery)?),
+        Expr::Exists(Exists { subquery, negated }) => Expr::Exists(Exists {
+            subquery: replace_subquery(subquery)?,
+            negated,
+        }),
+        Expr::InSubquery(InSubquery {
+            expr,
+            subquery,
+            negated,
+        }) => Expr::InSubquery(InSubquery {
+            expr,
+            subquery: replace_subquery(subquery)?,
+            negated,
+        }),
+        expr => expr,
+    })
+}
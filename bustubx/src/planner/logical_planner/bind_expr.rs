@@ -1,20 +1,26 @@
-use std::fmt::format;
-use sqlparser::test_utils::table;
-use crate::common::{ScalarValue, TableReference};
-use crate::expression::{BinaryExpr, ColumnExpr, Expr, Literal};
+use crate::catalog::{DataType, Schema};
+use crate::common::decimal::{self, DECIMAL128_MAX_PRECISION};
+use crate::common::{temporal, ScalarValue, TableReference};
+use crate::expression::{
+    AggregateFunction, Between, BinaryExpr, BuiltinAggregateFunction, BuiltinScalarFunction, Case,
+    Cast, ColumnExpr, Exists, Expr, InList, InSubquery, Literal, ScalarFunction,
+    ScalarFunctionDefinition, TryCast, UnaryExpr, UnaryOp,
+};
 use crate::planner::LogicalPlanner;
 use crate::{BustubxError, BustubxResult};
+use sqlparser::test_utils::table;
+use std::fmt::format;
 
 impl LogicalPlanner<'_> {
-    pub fn bind_expr(&self, sql: &sqlparser::ast::Expr) -> BustubxResult<Expr> {
+    pub fn bind_expr(&self, sql: &sqlparser::ast::Expr, schema: &Schema) -> BustubxResult<Expr> {
         match sql {
             sqlparser::ast::Expr::Identifier(ident) => Ok(Expr::Column(ColumnExpr {
                 relation: None,
                 name: ident.value.clone(),
             })),
             sqlparser::ast::Expr::BinaryOp { left, op, right } => {
-                let left = Box::new(self.bind_expr(left)?);
-                let right = Box::new(self.bind_expr(right)?);
+                let left = Box::new(self.bind_expr(left, schema)?);
+                let right = Box::new(self.bind_expr(right, schema)?);
                 Ok(Expr::BinaryExpr(BinaryExpr {
                     left,
                     op: op.try_into()?,
@@ -22,6 +28,53 @@ impl LogicalPlanner<'_> {
                 }))
             }
             sqlparser::ast::Expr::Value(value) => self.bind_value(value),
+            sqlparser::ast::Expr::UnaryOp {
+                op: sqlparser::ast::UnaryOperator::Minus,
+                expr,
+            } => match expr.as_ref() {
+                // negative numeric literal, e.g. -1.5
+                sqlparser::ast::Expr::Value(sqlparser::ast::Value::Number(s, long)) => {
+                    self.bind_value(&sqlparser::ast::Value::Number(format!("-{s}"), *long))
+                }
+                _ => Ok(Expr::UnaryExpr(UnaryExpr {
+                    op: UnaryOp::Minus,
+                    expr: Box::new(self.bind_expr(expr, schema)?),
+                })),
+            },
+            sqlparser::ast::Expr::UnaryOp {
+                op: sqlparser::ast::UnaryOperator::Plus,
+                expr,
+            } => self.bind_expr(expr, schema),
+            sqlparser::ast::Expr::UnaryOp {
+                op: sqlparser::ast::UnaryOperator::Not,
+                expr,
+            } => self.bind_unary_expr(UnaryOp::Not, expr, schema),
+            sqlparser::ast::Expr::IsNull(expr) => {
+                self.bind_unary_expr(UnaryOp::IsNull, expr, schema)
+            }
+            sqlparser::ast::Expr::IsNotNull(expr) => {
+                self.bind_unary_expr(UnaryOp::IsNotNull, expr, schema)
+            }
+            sqlparser::ast::Expr::IsTrue(expr) => {
+                self.bind_unary_expr(UnaryOp::IsTrue, expr, schema)
+            }
+            sqlparser::ast::Expr::IsFalse(expr) => {
+                self.bind_unary_expr(UnaryOp::IsFalse, expr, schema)
+            }
+            sqlparser::ast::Expr::IsNotTrue(expr) => {
+                self.bind_unary_expr(UnaryOp::IsNotTrue, expr, schema)
+            }
+            sqlparser::ast::Expr::IsNotFalse(expr) => {
+                self.bind_unary_expr(UnaryOp::IsNotFalse, expr, schema)
+            }
+            // a boolean IS UNKNOWN is the same as IS NULL
+            sqlparser::ast::Expr::IsUnknown(expr) => {
+                self.bind_unary_expr(UnaryOp::IsNull, expr, schema)
+            }
+            sqlparser::ast::Expr::IsNotUnknown(expr) => {
+                self.bind_unary_expr(UnaryOp::IsNotNull, expr, schema)
+            }
+            sqlparser::ast::Expr::Nested(expr) => self.bind_expr(expr, schema),
             sqlparser::ast::Expr::CompoundIdentifier(idents) => match idents.as_slice() {
                 [col] => Ok(Expr::Column(ColumnExpr {
                     relation: None,
@@ -51,12 +104,98 @@ impl LogicalPlanner<'_> {
                     idents
                 ))),
             },
-            sqlparser::ast::Expr::Function(t)=>{
-                // select hello('world');
-// function_name = hello, args = [Unnamed(Expr(Value(SingleQuotedString("world"))))], string = "hello('world')"
-                println!("function_name = {}, args = {:?}, string = {:?}", t.name, t.args, t.to_string());
-                Err(BustubxError::NotSupport(format!("x")))
-            },
+            sqlparser::ast::Expr::Function(function) => self.bind_function(function, schema),
+            sqlparser::ast::Expr::Extract { field, expr } => {
+                Ok(Expr::ScalarFunction(ScalarFunction::try_new(
+                    ScalarFunctionDefinition::Builtin(BuiltinScalarFunction::DatePart),
+                    vec![
+                        Expr::Literal(Literal {
+                            value: field.to_string().to_lowercase().into(),
+                        }),
+                        self.bind_expr(expr, schema)?,
+                    ],
+                )?))
+            }
+            sqlparser::ast::Expr::TypedString { data_type, value } => {
+                let data_type: DataType = data_type.try_into()?;
+                Ok(Expr::Literal(Literal {
+                    value: ScalarValue::from(value.as_str()).cast_to(&data_type)?,
+                }))
+            }
+            sqlparser::ast::Expr::Interval(interval) => self.bind_interval(interval),
+            // `x::T` is parsed as a CAST too
+            sqlparser::ast::Expr::Cast { expr, data_type } => Ok(Expr::Cast(Cast {
+                expr: Box::new(self.bind_expr(expr, schema)?),
+                data_type: data_type.try_into()?,
+            })),
+            sqlparser::ast::Expr::TryCast { expr, data_type }
+            | sqlparser::ast::Expr::SafeCast { expr, data_type } => Ok(Expr::TryCast(TryCast {
+                expr: Box::new(self.bind_expr(expr, schema)?),
+                data_type: data_type.try_into()?,
+            })),
+            sqlparser::ast::Expr::InList {
+                expr,
+                list,
+                negated,
+            } => Ok(Expr::InList(InList::new(
+                Box::new(self.bind_expr(expr, schema)?),
+                list.iter()
+                    .map(|item| self.bind_expr(item, schema))
+                    .collect::<BustubxResult<Vec<Expr>>>()?,
+                *negated,
+            ))),
+            sqlparser::ast::Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => Ok(Expr::Between(Between {
+                expr: Box::new(self.bind_expr(expr, schema)?),
+                negated: *negated,
+                low: Box::new(self.bind_expr(low, schema)?),
+                high: Box::new(self.bind_expr(high, schema)?),
+            })),
+            sqlparser::ast::Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                let mut when_then_expr = vec![];
+                for (when, then) in conditions.iter().zip(results.iter()) {
+                    when_then_expr.push((
+                        Box::new(self.bind_expr(when, schema)?),
+                        Box::new(self.bind_expr(then, schema)?),
+                    ));
+                }
+                Ok(Expr::Case(Case {
+                    expr: operand
+                        .as_ref()
+                        .map(|expr| self.bind_expr(expr, schema).map(Box::new))
+                        .transpose()?,
+                    when_then_expr,
+                    else_expr: else_result
+                        .as_ref()
+                        .map(|expr| self.bind_expr(expr, schema).map(Box::new))
+                        .transpose()?,
+                }))
+            }
+            sqlparser::ast::Expr::Subquery(query) => {
+                Ok(Expr::ScalarSubquery(self.bind_subquery(query, schema)?))
+            }
+            sqlparser::ast::Expr::Exists { subquery, negated } => Ok(Expr::Exists(Exists {
+                subquery: self.bind_subquery(subquery, schema)?,
+                negated: *negated,
+            })),
+            sqlparser::ast::Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => Ok(Expr::InSubquery(InSubquery {
+                expr: Box::new(self.bind_expr(expr, schema)?),
+                subquery: self.bind_subquery(subquery, schema)?,
+                negated: *negated,
+            })),
             // 这里判断新增的系统函数
             _ => Err(BustubxError::NotSupport(format!(
                 "sqlparser expr {} not supported",
@@ -65,8 +204,129 @@ impl LogicalPlanner<'_> {
         }
     }
 
+    fn bind_unary_expr(
+        &self,
+        op: UnaryOp,
+        expr: &sqlparser::ast::Expr,
+        schema: &Schema,
+    ) -> BustubxResult<Expr> {
+        Ok(Expr::UnaryExpr(UnaryExpr {
+            op,
+            expr: Box::new(self.bind_expr(expr, schema)?),
+        }))
+    }
+
+    pub fn bind_function(
+        &self,
+        function: &sqlparser::ast::Function,
+        schema: &Schema,
+    ) -> BustubxResult<Expr> {
+        let name = function.name.to_string().to_lowercase();
+        let mut args = vec![];
+        for arg in function.args.iter() {
+            match arg {
+                sqlparser::ast::FunctionArg::Unnamed(sqlparser::ast::FunctionArgExpr::Expr(
+                    expr,
+                )) => args.push(self.bind_expr(expr, schema)?),
+                // count(*) is bound without arguments
+                sqlparser::ast::FunctionArg::Unnamed(sqlparser::ast::FunctionArgExpr::Wildcard)
+                    if name == "count" => {}
+                _ => {
+                    return Err(BustubxError::NotSupport(format!(
+                        "function argument {} not supported",
+                        arg
+                    )))
+                }
+            }
+        }
+        if function.over.is_some() {
+            return Err(BustubxError::NotSupport(format!(
+                "window function {} not supported",
+                function
+            )));
+        }
+        if let Some(func) = BuiltinAggregateFunction::from_name(&name) {
+            return Ok(Expr::AggregateFunction(AggregateFunction::try_new(
+                func,
+                args,
+                function.distinct,
+            )?));
+        }
+        if function.distinct {
+            return Err(BustubxError::Plan(format!(
+                "DISTINCT specified, but {} is not an aggregate function",
+                name
+            )));
+        }
+        match name.as_str() {
+            // evaluated once while planning so every row of a statement sees the same time
+            "now" | "current_timestamp" if args.is_empty() => Ok(Expr::Literal(Literal {
+                value: ScalarValue::Timestamp(Some(temporal::now())),
+            })),
+            "current_date" if args.is_empty() => Ok(Expr::Literal(Literal {
+                value: ScalarValue::Timestamp(Some(temporal::now())).cast_to(&DataType::Date)?,
+            })),
+            _ => match self.context.function_registry.resolve(&name) {
+                Some(func) => Ok(Expr::ScalarFunction(ScalarFunction::try_new(func, args)?)),
+                None => Err(BustubxError::Plan(format!(
+                    "function {} does not exist",
+                    function.name
+                ))),
+            },
+        }
+    }
+
+    pub fn bind_interval(&self, interval: &sqlparser::ast::Interval) -> BustubxResult<Expr> {
+        let value = match interval.value.as_ref() {
+            sqlparser::ast::Expr::Value(sqlparser::ast::Value::SingleQuotedString(s)) => s.clone(),
+            sqlparser::ast::Expr::Value(sqlparser::ast::Value::Number(s, _)) => s.clone(),
+            _ => {
+                return Err(BustubxError::NotSupport(format!(
+                    "interval value {} not supported",
+                    interval.value
+                )))
+            }
+        };
+        if interval.last_field.is_some() {
+            return Err(BustubxError::NotSupport(format!(
+                "interval {} not supported",
+                interval
+            )));
+        }
+        // INTERVAL '3' DAY is the same as INTERVAL '3 day'
+        let value = match interval.leading_field {
+            Some(field) => format!("{} {}", value, field.to_string().to_lowercase()),
+            None => value,
+        };
+        Ok(Expr::Literal(Literal {
+            value: ScalarValue::Interval(Some(temporal::parse_interval(&value)?)),
+        }))
+    }
+
     pub fn bind_value(&self, value: &sqlparser::ast::Value) -> BustubxResult<Expr> {
         match value {
+            // a fractional literal is exact like in postgres, e.g. 10.250 is DECIMAL(5, 3)
+            sqlparser::ast::Value::Number(s, _) if s.contains('.') && !s.contains(['e', 'E']) => {
+                let (int_part, frac_part) = s.split_once('.').unwrap_or((s, ""));
+                let scale = frac_part.len();
+                let precision = (int_part.trim_start_matches('0').len() + scale).max(1);
+                if precision <= DECIMAL128_MAX_PRECISION as usize {
+                    let value = decimal::parse(s, scale as u8)?;
+                    return Ok(Expr::Literal(Literal {
+                        value: ScalarValue::Decimal128(Some(value), precision as u8, scale as u8),
+                    }));
+                }
+                let num: f64 = s.parse::<f64>().map_err(|_| {
+                    BustubxError::Plan(format!("Failed to parse literal {} as f64", s))
+                })?;
+                Ok(Expr::Literal(Literal { value: num.into() }))
+            }
+            sqlparser::ast::Value::Number(s, _) if s.contains(['e', 'E']) => {
+                let num: f64 = s.parse::<f64>().map_err(|_| {
+                    BustubxError::Plan(format!("Failed to parse literal {} as f64", s))
+                })?;
+                Ok(Expr::Literal(Literal { value: num.into() }))
+            }
             sqlparser::ast::Value::Number(s, _) => {
                 let num: i64 = s.parse::<i64>().map_err(|e| {
                     BustubxError::Internal("Failed to parse literal as i64".to_string())
@@ -74,8 +334,11 @@ impl LogicalPlanner<'_> {
                 Ok(Expr::Literal(Literal { value: num.into() }))
             }
             sqlparser::ast::Value::Boolean(b) => Ok(Expr::Literal(Literal { value: (*b).into() })),
+            sqlparser::ast::Value::SingleQuotedString(s) => Ok(Expr::Literal(Literal {
+                value: s.clone().into(),
+            })),
             sqlparser::ast::Value::Null => Ok(Expr::Literal(Literal {
-                value: ScalarValue::Int8(None),
+                value: ScalarValue::Null,
             })),
             _ => Err(BustubxError::NotSupport(format!(
                 "sqlparser value {} not supported",
@@ -0,0 +1,425 @@
+use crate::catalog::{DataType, Schema};
+use crate::common::ScalarValue;
+use crate::expression::type_coercion::{
+    can_cast, can_coerce_from, can_coerce_string_literal, common_type, comparison_coercion,
+    numeric_coercion,
+};
+use crate::expression::{
+    AggregateFunction, Alias, Between, BinaryExpr, BinaryOp, Case, Cast, ColumnExpr, Expr,
+    ExprTrait, InList, InSubquery, Literal, OuterReferenceColumn, ScalarFunction, TryCast,
+    UnaryExpr, UnaryOp,
+};
+use crate::planner::LogicalPlanner;
+use crate::{BustubxError, BustubxResult};
+
+impl LogicalPlanner<'_> {
+    /// Insert `Cast`s so the operands of every operator in `expr` have types the operator
+    /// can evaluate, e.g. `int_col = 1` becomes `CAST int_col AS Int64 = 1`.
+    pub fn coerce_expr(&self, expr: Expr, schema: &Schema) -> BustubxResult<Expr> {
+        match expr {
+            Expr::Alias(Alias { expr, name }) => Ok(Expr::Alias(Alias {
+                expr: Box::new(self.coerce_expr(*expr, schema)?),
+                name,
+            })),
+            Expr::Cast(Cast { expr, data_type }) => Ok(Expr::Cast(Cast {
+                expr: Box::new(self.coerce_cast_input(*expr, schema, data_type)?),
+                data_type,
+            })),
+            Expr::TryCast(TryCast { expr, data_type }) => Ok(Expr::TryCast(TryCast {
+                expr: Box::new(self.coerce_cast_input(*expr, schema, data_type)?),
+                data_type,
+            })),
+            Expr::ScalarFunction(ScalarFunction { func, args }) => {
+                let args = args
+                    .into_iter()
+                    .map(|arg| self.coerce_expr(arg, schema))
+                    .collect::<BustubxResult<Vec<Expr>>>()?;
+                let arg_types = args
+                    .iter()
+                    .map(|arg| arg.data_type(schema))
+                    .collect::<BustubxResult<Vec<DataType>>>()?;
+                let coerced_types = func.coerce_arg_types(&arg_types)?;
+                let args = args
+                    .into_iter()
+                    .zip(arg_types.into_iter().zip(coerced_types))
+                    .map(|(arg, (from, to))| cast_expr(arg, from, to))
+                    .collect::<BustubxResult<Vec<Expr>>>()?;
+                let expr = Expr::ScalarFunction(ScalarFunction { func, args });
+                expr.data_type(schema)?;
+                Ok(expr)
+            }
+            Expr::AggregateFunction(AggregateFunction {
+                func,
+                args,
+                distinct,
+            }) => {
+                let args = args
+                    .into_iter()
+                    .map(|arg| self.coerce_expr(arg, schema))
+                    .collect::<BustubxResult<Vec<Expr>>>()?;
+                let expr = Expr::AggregateFunction(AggregateFunction {
+                    func,
+                    args,
+                    distinct,
+                });
+                expr.data_type(schema)?;
+                Ok(expr)
+            }
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                let left = self.coerce_expr(*left, schema)?;
+                let right = self.coerce_expr(*right, schema)?;
+                self.coerce_binary_expr(left, op, right, schema)
+            }
+            Expr::UnaryExpr(UnaryExpr { op, expr }) => {
+                let expr = self.coerce_expr(*expr, schema)?;
+                let data_type = expr.data_type(schema)?;
+                let expr = match op {
+                    UnaryOp::Not
+                    | UnaryOp::IsTrue
+                    | UnaryOp::IsFalse
+                    | UnaryOp::IsNotTrue
+                    | UnaryOp::IsNotFalse => {
+                        if !can_coerce_from(data_type, DataType::Boolean) {
+                            return Err(BustubxError::Plan(format!(
+                                "operator {} is not supported for {}",
+                                op, data_type
+                            )));
+                        }
+                        cast_expr(expr, data_type, DataType::Boolean)?
+                    }
+                    UnaryOp::Minus | UnaryOp::IsNull | UnaryOp::IsNotNull => expr,
+                };
+                let expr = Expr::UnaryExpr(UnaryExpr {
+                    op,
+                    expr: Box::new(expr),
+                });
+                expr.data_type(schema)?;
+                Ok(expr)
+            }
+            Expr::InList(InList {
+                expr,
+                list,
+                negated,
+                ..
+            }) => {
+                let expr = self.coerce_expr(*expr, schema)?;
+                let list = list
+                    .into_iter()
+                    .map(|item| self.coerce_expr(item, schema))
+                    .collect::<BustubxResult<Vec<Expr>>>()?;
+                let (expr, list) = self.coerce_comparison_list(expr, list, schema)?;
+                Ok(Expr::InList(InList::new(Box::new(expr), list, negated)))
+            }
+            Expr::Between(Between {
+                expr,
+                negated,
+                low,
+                high,
+            }) => {
+                let expr = self.coerce_expr(*expr, schema)?;
+                let low = self.coerce_expr(*low, schema)?;
+                let high = self.coerce_expr(*high, schema)?;
+                let (expr, mut bounds) =
+                    self.coerce_comparison_list(expr, vec![low, high], schema)?;
+                let high = bounds.pop().expect("BETWEEN has two bounds");
+                let low = bounds.pop().expect("BETWEEN has two bounds");
+                Ok(Expr::Between(Between {
+                    expr: Box::new(expr),
+                    negated,
+                    low: Box::new(low),
+                    high: Box::new(high),
+                }))
+            }
+            Expr::Case(case) => self.coerce_case(case, schema),
+            Expr::InSubquery(InSubquery {
+                expr,
+                subquery,
+                negated,
+            }) => {
+                let expr = self.coerce_expr(*expr, schema)?;
+                let expr_type = expr.data_type(schema)?;
+                let subquery_type = subquery.output_type()?;
+                // values of the subquery are cast to the compared type while executing
+                let expr = if literal_coercible_to(&expr, subquery_type) {
+                    cast_expr(expr, expr_type, subquery_type)?
+                } else if comparison_coercion(expr_type, subquery_type).is_some() {
+                    expr
+                } else {
+                    return Err(BustubxError::Plan(format!(
+                        "operator = is not supported for {} and {}",
+                        expr_type, subquery_type
+                    )));
+                };
+                Ok(Expr::InSubquery(InSubquery {
+                    expr: Box::new(expr),
+                    subquery,
+                    negated,
+                }))
+            }
+            Expr::ScalarSubquery(subquery) => {
+                subquery.output_type()?;
+                Ok(Expr::ScalarSubquery(subquery))
+            }
+            Expr::Column(column) => Ok(self.resolve_column(column, schema)),
+            Expr::Literal(_) | Expr::Exists(_) | Expr::OuterReferenceColumn(_) => Ok(expr),
+        }
+    }
+
+    /// A column missing from `schema` is looked up in the enclosing queries of a subquery.
+    fn resolve_column(&self, column: ColumnExpr, schema: &Schema) -> Expr {
+        if schema
+            .index_of(column.relation.as_ref(), &column.name)
+            .is_ok()
+        {
+            return Expr::Column(column);
+        }
+        let outer_column = self.context.outer_query_schema.as_ref().and_then(|outer| {
+            outer
+                .column_with_name(column.relation.as_ref(), &column.name)
+                .ok()
+        });
+        match outer_column {
+            Some(outer_column) => Expr::OuterReferenceColumn(OuterReferenceColumn {
+                column: ColumnExpr {
+                    relation: outer_column.relation.clone().or(column.relation),
+                    name: column.name,
+                },
+                data_type: outer_column.data_type,
+                nullable: outer_column.nullable,
+            }),
+            // reported as an unknown column when its type is needed
+            None => Expr::Column(column),
+        }
+    }
+
+    /// Cast `expr` and all `values` it is compared with to one type, for IN lists, BETWEEN and
+    /// the simple form of CASE. String literals are read as the type of `expr`.
+    fn coerce_comparison_list(
+        &self,
+        expr: Expr,
+        values: Vec<Expr>,
+        schema: &Schema,
+    ) -> BustubxResult<(Expr, Vec<Expr>)> {
+        let expr_type = expr.data_type(schema)?;
+        let mut compare_type = expr_type;
+        for value in values.iter() {
+            if literal_coercible_to(value, expr_type) {
+                continue;
+            }
+            let value_type = value.data_type(schema)?;
+            compare_type = common_type(compare_type, value_type).ok_or_else(|| {
+                BustubxError::Plan(format!(
+                    "operator = is not supported for {} and {}",
+                    expr_type, value_type
+                ))
+            })?;
+        }
+        let values = values
+            .into_iter()
+            .map(|value| {
+                let value_type = value.data_type(schema)?;
+                cast_expr(value, value_type, compare_type)
+            })
+            .collect::<BustubxResult<Vec<Expr>>>()?;
+        Ok((cast_expr(expr, expr_type, compare_type)?, values))
+    }
+
+    /// WHEN values are compared with the operand, or are conditions without an operand. All
+    /// THEN and ELSE expressions are cast to their common type.
+    fn coerce_case(&self, case: Case, schema: &Schema) -> BustubxResult<Expr> {
+        let operand = case
+            .expr
+            .map(|expr| self.coerce_expr(*expr, schema))
+            .transpose()?;
+        let mut whens = vec![];
+        let mut thens = vec![];
+        for (when, then) in case.when_then_expr {
+            whens.push(match operand {
+                Some(_) => self.coerce_expr(*when, schema)?,
+                None => self.coerce_predicate(*when, schema)?,
+            });
+            thens.push(self.coerce_expr(*then, schema)?);
+        }
+        let else_expr = case
+            .else_expr
+            .map(|expr| self.coerce_expr(*expr, schema))
+            .transpose()?;
+
+        // the operand and the WHEN values are all cast to one type
+        let operand = match operand {
+            Some(operand) => {
+                let (operand, coerced_whens) =
+                    self.coerce_comparison_list(operand, whens, schema)?;
+                whens = coerced_whens;
+                Some(Box::new(operand))
+            }
+            None => None,
+        };
+        let case = Case {
+            expr: operand,
+            when_then_expr: whens
+                .into_iter()
+                .zip(thens)
+                .map(|(when, then)| (Box::new(when), Box::new(then)))
+                .collect(),
+            else_expr: else_expr.map(Box::new),
+        };
+        let data_type = case.data_type(schema)?;
+        let cast_result = |expr: Expr| {
+            let expr_type = expr.data_type(schema)?;
+            cast_expr(expr, expr_type, data_type).map(Box::new)
+        };
+        let mut when_then_expr = vec![];
+        for (when, then) in case.when_then_expr {
+            when_then_expr.push((when, cast_result(*then)?));
+        }
+        // without an ELSE the CASE still yields a NULL of the result type
+        let else_expr = match case.else_expr {
+            Some(else_expr) => cast_result(*else_expr)?,
+            None => Box::new(Expr::Literal(Literal {
+                value: ScalarValue::new_empty(data_type),
+            })),
+        };
+        Ok(Expr::Case(Case {
+            expr: case.expr,
+            when_then_expr,
+            else_expr: Some(else_expr),
+        }))
+    }
+
+    /// Coerce a WHERE or JOIN ON condition, which has to be a boolean.
+    pub fn coerce_predicate(&self, expr: Expr, schema: &Schema) -> BustubxResult<Expr> {
+        let expr = self.coerce_expr(expr, schema)?;
+        let data_type = expr.data_type(schema)?;
+        if !can_coerce_from(data_type, DataType::Boolean) {
+            return Err(BustubxError::Plan(format!(
+                "predicate {} must be type Boolean, not {}",
+                expr, data_type
+            )));
+        }
+        cast_expr(expr, data_type, DataType::Boolean)
+    }
+
+    /// Cast `expr` to the type of the column it is assigned to, used for INSERT values.
+    pub fn coerce_expr_to(
+        &self,
+        expr: Expr,
+        schema: &Schema,
+        data_type: DataType,
+    ) -> BustubxResult<Expr> {
+        let expr = self.coerce_expr(expr, schema)?;
+        let expr_type = expr.data_type(schema)?;
+        if !can_coerce_from(expr_type, data_type) && !literal_coercible_to(&expr, data_type) {
+            return Err(BustubxError::Plan(format!(
+                "expression {} of type {} can not be assigned to type {}",
+                expr, expr_type, data_type
+            )));
+        }
+        cast_expr(expr, expr_type, data_type)
+    }
+
+    fn coerce_cast_input(
+        &self,
+        expr: Expr,
+        schema: &Schema,
+        data_type: DataType,
+    ) -> BustubxResult<Expr> {
+        let expr = self.coerce_expr(expr, schema)?;
+        let expr_type = expr.data_type(schema)?;
+        if !can_cast(expr_type, data_type) {
+            return Err(BustubxError::Plan(format!(
+                "cannot cast type {} to {}",
+                expr_type, data_type
+            )));
+        }
+        Ok(expr)
+    }
+
+    fn coerce_binary_expr(
+        &self,
+        left: Expr,
+        op: BinaryOp,
+        right: Expr,
+        schema: &Schema,
+    ) -> BustubxResult<Expr> {
+        let left_type = left.data_type(schema)?;
+        let right_type = right.data_type(schema)?;
+        let incompatible = || {
+            BustubxError::Plan(format!(
+                "operator {} is not supported for {} and {}",
+                op, left_type, right_type
+            ))
+        };
+        let (left_target, right_target) = match op {
+            BinaryOp::Gt
+            | BinaryOp::Lt
+            | BinaryOp::GtEq
+            | BinaryOp::LtEq
+            | BinaryOp::Eq
+            | BinaryOp::NotEq => {
+                if literal_coercible_to(&left, right_type) {
+                    (right_type, right_type)
+                } else if literal_coercible_to(&right, left_type) {
+                    (left_type, left_type)
+                } else {
+                    comparison_coercion(left_type, right_type).ok_or_else(incompatible)?
+                }
+            }
+            BinaryOp::And | BinaryOp::Or => {
+                for data_type in [left_type, right_type] {
+                    if !can_coerce_from(data_type, DataType::Boolean) {
+                        return Err(incompatible());
+                    }
+                }
+                (DataType::Boolean, DataType::Boolean)
+            }
+            BinaryOp::Plus
+            | BinaryOp::Minus
+            | BinaryOp::Multiply
+            | BinaryOp::Divide
+            | BinaryOp::Modulo => {
+                if left_type == DataType::Null {
+                    (right_type, right_type)
+                } else if right_type == DataType::Null {
+                    (left_type, left_type)
+                } else {
+                    // non numeric operands such as `timestamp + interval` are checked below
+                    numeric_coercion(left_type, right_type).unwrap_or((left_type, right_type))
+                }
+            }
+        };
+        let expr = Expr::BinaryExpr(BinaryExpr {
+            left: Box::new(cast_expr(left, left_type, left_target)?),
+            op,
+            right: Box::new(cast_expr(right, right_type, right_target)?),
+        });
+        expr.data_type(schema)?;
+        Ok(expr)
+    }
+}
+
+/// A string literal can be parsed as a date, time etc.
+fn literal_coercible_to(expr: &Expr, data_type: DataType) -> bool {
+    match expr {
+        Expr::Literal(Literal {
+            value: ScalarValue::Varchar(Some(_)),
+        }) => can_coerce_string_literal(data_type),
+        _ => false,
+    }
+}
+
+/// Wrap `expr` in a cast unless it already has the target type, literals are cast right away.
+fn cast_expr(expr: Expr, from: DataType, to: DataType) -> BustubxResult<Expr> {
+    if from == to {
+        return Ok(expr);
+    }
+    match expr {
+        Expr::Literal(Literal { value }) => Ok(Expr::Literal(Literal {
+            value: value.cast_to(&to)?,
+        })),
+        _ => Ok(Expr::Cast(Cast {
+            expr: Box::new(expr),
+            data_type: to,
+        })),
+    }
+}
@@ -1,11 +1,15 @@
 use crate::{BustubxError, BustubxResult};
 
-use crate::catalog::Catalog;
+use crate::catalog::{Catalog, Schema, SchemaRef};
 use crate::common::TableReference;
+use crate::expression::FunctionRegistry;
 use crate::planner::logical_plan::{LogicalPlan, OrderByExpr};
 
 pub struct PlannerContext<'a> {
     pub catalog: &'a Catalog,
+    pub function_registry: &'a FunctionRegistry,
+    /// Columns of the enclosing queries while planning a subquery, nearest first
+    pub outer_query_schema: Option<SchemaRef>,
 }
 
 pub struct LogicalPlanner<'a> {
@@ -34,16 +38,63 @@ impl<'a> LogicalPlanner<'a> {
                 source,
                 ..
             } => self.plan_insert(table_name, columns, source),
-            // 5. 剩余的没实现
-            _ => unimplemented!(),
+            // 5. 删除数据
+            sqlparser::ast::Statement::Delete {
+                tables,
+                from,
+                using,
+                selection,
+                returning,
+            } => {
+                if !tables.is_empty() || using.is_some() || returning.is_some() {
+                    return Err(BustubxError::NotSupport(format!(
+                        "statement {} not supported",
+                        stmt
+                    )));
+                }
+                self.plan_delete(from, selection)
+            }
+            // 6. 更新数据
+            sqlparser::ast::Statement::Update {
+                table,
+                assignments,
+                from,
+                selection,
+                returning,
+            } => {
+                if from.is_some() || returning.is_some() {
+                    return Err(BustubxError::NotSupport(format!(
+                        "statement {} not supported",
+                        stmt
+                    )));
+                }
+                self.plan_update(table, assignments, selection)
+            }
+            // 7. 删除表和索引
+            sqlparser::ast::Statement::Drop {
+                object_type,
+                if_exists,
+                names,
+                ..
+            } => self.plan_drop(object_type, names, *if_exists),
+            // 8. 修改表
+            sqlparser::ast::Statement::AlterTable { name, operation } => {
+                self.plan_alter_table(name, operation)
+            }
+            // 9. 剩余的没实现
+            _ => Err(BustubxError::NotSupport(format!(
+                "statement {} not supported",
+                stmt
+            ))),
         }
     }
 
     pub fn bind_order_by_expr(
         &self,
         order_by: &sqlparser::ast::OrderByExpr,
+        schema: &Schema,
     ) -> BustubxResult<OrderByExpr> {
-        let expr = self.bind_expr(&order_by.expr)?;
+        let expr = self.bind_expr(&order_by.expr, schema)?;
         Ok(OrderByExpr {
             expr: Box::new(expr),
             asc: order_by.asc.unwrap_or(true),
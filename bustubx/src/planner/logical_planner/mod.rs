@@ -1,9 +1,17 @@
 mod bind_expr;
+mod coerce_expr;
 mod logical_planner;
+mod plan_aggregate;
+mod plan_alter_table;
 mod plan_create_index;
 mod plan_create_table;
+mod plan_delete;
+mod plan_drop;
 mod plan_insert;
 mod plan_query;
 mod plan_set_expr;
+mod plan_set_operation;
+mod plan_subquery;
+mod plan_update;
 
 pub use logical_planner::{LogicalPlanner, PlannerContext};
@@ -0,0 +1,176 @@
+use crate::catalog::{Column, Schema};
+use crate::expression::{ColumnExpr, Expr, ExprTrait};
+use crate::planner::logical_plan::{Aggregate, LogicalPlan};
+use crate::planner::LogicalPlanner;
+use crate::{BustubxError, BustubxResult};
+use std::sync::Arc;
+
+impl LogicalPlanner<'_> {
+    /// Add an `Aggregate` when the query has a GROUP BY or HAVING or calls aggregate functions,
+    /// and rewrite `select_exprs` to read the group keys and aggregate results from its output.
+    /// Groups are filtered by `having`, which may call aggregate functions missing from the
+    /// select list.
+    pub fn plan_aggregate(
+        &self,
+        input: LogicalPlan,
+        select_exprs: Vec<Expr>,
+        having: Option<Expr>,
+        group_by: &[sqlparser::ast::Expr],
+    ) -> BustubxResult<(LogicalPlan, Vec<Expr>)> {
+        if group_by.is_empty()
+            && having.is_none()
+            && !select_exprs.iter().any(|expr| expr.contains_aggregate())
+        {
+            return Ok((input, select_exprs));
+        }
+
+        let mut group_exprs = vec![];
+        for expr in group_by {
+            let expr = match expr {
+                // GROUP BY 1 refers to the first select item
+                sqlparser::ast::Expr::Value(sqlparser::ast::Value::Number(n, _)) => {
+                    let position = n
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|position| (1..=select_exprs.len()).contains(position))
+                        .ok_or_else(|| {
+                            BustubxError::Plan(format!(
+                                "GROUP BY position {} is not in select list",
+                                n
+                            ))
+                        })?;
+                    match &select_exprs[position - 1] {
+                        Expr::Alias(alias) => alias.expr.as_ref().clone(),
+                        expr => expr.clone(),
+                    }
+                }
+                expr => self.coerce_expr(self.bind_expr(expr, input.schema())?, input.schema())?,
+            };
+            check_no_aggregate(&expr, "GROUP BY")?;
+            if !group_exprs.contains(&expr) {
+                group_exprs.push(expr);
+            }
+        }
+
+        let mut aggr_exprs = vec![];
+        for expr in select_exprs.iter().chain(having.iter()) {
+            collect_aggregates(expr, &mut aggr_exprs)?;
+        }
+
+        let columns = group_exprs
+            .iter()
+            .chain(aggr_exprs.iter())
+            .map(|expr| expr.to_column(input.schema()))
+            .collect::<BustubxResult<Vec<Column>>>()?;
+        let schema = Arc::new(Schema::new(columns));
+        let plan = LogicalPlan::Aggregate(Aggregate {
+            input: Arc::new(input),
+            group_exprs,
+            aggr_exprs,
+            schema,
+        });
+        let select_exprs = select_exprs
+            .into_iter()
+            .map(|expr| rewrite_for_aggregate(expr, &plan))
+            .collect::<BustubxResult<Vec<Expr>>>()?;
+        let plan = match having {
+            Some(having) => {
+                let having = rewrite_for_aggregate(having, &plan)?;
+                self.plan_filter(plan, having)?
+            }
+            None => plan,
+        };
+        Ok((plan, select_exprs))
+    }
+}
+
+/// Add the aggregate function calls of `expr` to `aggr_exprs`, once each.
+fn collect_aggregates(expr: &Expr, aggr_exprs: &mut Vec<Expr>) -> BustubxResult<()> {
+    match expr {
+        Expr::AggregateFunction(aggregate) => {
+            if aggregate.args.iter().any(|arg| arg.contains_aggregate()) {
+                return Err(BustubxError::Plan(
+                    "aggregate function calls cannot be nested".to_string(),
+                ));
+            }
+            if !aggr_exprs.contains(expr) {
+                aggr_exprs.push(expr.clone());
+            }
+            Ok(())
+        }
+        expr => expr
+            .children()
+            .into_iter()
+            .try_for_each(|child| collect_aggregates(child, aggr_exprs)),
+    }
+}
+
+/// Replace group expressions and aggregate calls in `expr` by columns of `aggregate`. Any
+/// other column left is neither grouped nor aggregated.
+pub(crate) fn rewrite_for_aggregate(expr: Expr, aggregate: &LogicalPlan) -> BustubxResult<Expr> {
+    let LogicalPlan::Aggregate(Aggregate {
+        group_exprs,
+        aggr_exprs,
+        schema,
+        ..
+    }) = aggregate
+    else {
+        return Err(BustubxError::Internal(format!(
+            "{} is not an aggregate",
+            aggregate
+        )));
+    };
+    let replacements = group_exprs
+        .iter()
+        .chain(aggr_exprs.iter())
+        .zip(schema.columns.iter())
+        .map(|(expr, col)| {
+            let column = ColumnExpr {
+                relation: col.relation.clone(),
+                name: col.name.clone(),
+            };
+            (expr, column)
+        })
+        .collect::<Vec<_>>();
+    let expr = replace_exprs(expr, &replacements)?;
+    if let Some(column) = find_unknown_column(&expr, schema) {
+        return Err(BustubxError::Plan(format!(
+            "column \"{}\" must appear in the GROUP BY clause or be used in an aggregate function",
+            column
+        )));
+    }
+    Ok(expr)
+}
+
+fn replace_exprs(expr: Expr, replacements: &[(&Expr, ColumnExpr)]) -> BustubxResult<Expr> {
+    match replacements.iter().find(|(from, _)| **from == expr) {
+        Some((_, column)) => Ok(Expr::Column(column.clone())),
+        None => expr.map_children(|child| replace_exprs(child, replacements)),
+    }
+}
+
+pub(crate) fn find_unknown_column<'a>(expr: &'a Expr, schema: &Schema) -> Option<&'a ColumnExpr> {
+    match expr {
+        Expr::Column(column)
+            if schema
+                .index_of(column.relation.as_ref(), &column.name)
+                .is_err() =>
+        {
+            Some(column)
+        }
+        expr => expr
+            .children()
+            .into_iter()
+            .find_map(|child| find_unknown_column(child, schema)),
+    }
+}
+
+pub(crate) fn check_no_aggregate(expr: &Expr, clause: &str) -> BustubxResult<()> {
+    if expr.contains_aggregate() {
+        return Err(BustubxError::Plan(format!(
+            "aggregate functions are not allowed in {}",
+            clause
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use crate::catalog::EMPTY_SCHEMA_REF;
+use crate::planner::logical_plan::{AlterTable, AlterTableOperation, EmptyRelation, LogicalPlan};
+use crate::{BustubxError, BustubxResult};
+
+use super::LogicalPlanner;
+
+impl<'a> LogicalPlanner<'a> {
+    pub fn plan_alter_table(
+        &self,
+        name: &sqlparser::ast::ObjectName,
+        operation: &sqlparser::ast::AlterTableOperation,
+    ) -> BustubxResult<LogicalPlan> {
+        let table = self.bind_table_name(name)?;
+        let table_schema = self
+            .context
+            .catalog
+            .get_table_by_name(table.table())
+            .map_or(
+                Err(BustubxError::Plan(format!("table {} not found", table))),
+                |info| Ok(info.schema.clone()),
+            )?;
+        let column_index = |name: &sqlparser::ast::Ident| table_schema.index_of(None, &name.value);
+        let column_not_exist = |name: &sqlparser::ast::Ident| {
+            BustubxError::Plan(format!(
+                "column \"{}\" of relation \"{}\" does not exist",
+                name.value, table
+            ))
+        };
+        let column_exists = |name: &sqlparser::ast::Ident| {
+            BustubxError::Plan(format!(
+                "column \"{}\" of relation \"{}\" already exists",
+                name.value, table
+            ))
+        };
+        // IF [NOT] EXISTS that skips the operation
+        let nothing = LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            schema: EMPTY_SCHEMA_REF.clone(),
+        });
+
+        let operation = match operation {
+            sqlparser::ast::AlterTableOperation::AddColumn {
+                if_not_exists,
+                column_def,
+                ..
+            } => {
+                if column_index(&column_def.name).is_ok() {
+                    if *if_not_exists {
+                        return Ok(nothing);
+                    }
+                    return Err(column_exists(&column_def.name));
+                }
+                let column = self.bind_column_def(&table, column_def)?;
+                let default = self.bind_default(&column)?;
+                AlterTableOperation::AddColumn { column, default }
+            }
+            sqlparser::ast::AlterTableOperation::DropColumn {
+                column_name,
+                if_exists,
+                ..
+            } => {
+                let index = match column_index(column_name) {
+                    Ok(index) => index,
+                    Err(_) if *if_exists => return Ok(nothing),
+                    Err(_) => return Err(column_not_exist(column_name)),
+                };
+                if table_schema.column_count() == 1 {
+                    return Err(BustubxError::NotSupport(format!(
+                        "dropping the last column of table {} not supported",
+                        table
+                    )));
+                }
+                AlterTableOperation::DropColumn { index }
+            }
+            sqlparser::ast::AlterTableOperation::RenameColumn {
+                old_column_name,
+                new_column_name,
+            } => {
+                let index =
+                    column_index(old_column_name).map_err(|_| column_not_exist(old_column_name))?;
+                if column_index(new_column_name).is_ok() {
+                    return Err(column_exists(new_column_name));
+                }
+                AlterTableOperation::RenameColumn {
+                    index,
+                    new_name: new_column_name.value.clone(),
+                }
+            }
+            sqlparser::ast::AlterTableOperation::RenameTable { table_name } => {
+                let new_table = self.bind_table_name(table_name)?;
+                if self
+                    .context
+                    .catalog
+                    .get_table_by_name(new_table.table())
+                    .is_some()
+                {
+                    return Err(BustubxError::Plan(format!(
+                        "table {} already exists",
+                        new_table
+                    )));
+                }
+                AlterTableOperation::RenameTable { new_table }
+            }
+            _ => {
+                return Err(BustubxError::NotSupport(format!(
+                    "ALTER TABLE {} not supported",
+                    operation
+                )))
+            }
+        };
+        Ok(LogicalPlan::AlterTable(AlterTable { table, operation }))
+    }
+}
@@ -17,11 +17,6 @@ impl<'a> LogicalPlanner<'a> {
             |ident| Ok(ident.value.clone()),
         )?;
         let table = self.bind_table_name(table_name)?;
-        let mut columns_expr = vec![];
-        for col in columns.iter() {
-            let col_expr = self.bind_order_by_expr(&col)?;
-            columns_expr.push(col_expr);
-        }
         let table_schema = self
             .context
             .catalog
@@ -30,6 +25,11 @@ impl<'a> LogicalPlanner<'a> {
                 Err(BustubxError::Plan(format!("table {} not found", table))),
                 |info| Ok(info.schema.clone()),
             )?;
+        let mut columns_expr = vec![];
+        for col in columns.iter() {
+            let col_expr = self.bind_order_by_expr(&col, &table_schema)?;
+            columns_expr.push(col_expr);
+        }
         Ok(LogicalPlan::CreateIndex(CreateIndex {
             index_name,
             table,
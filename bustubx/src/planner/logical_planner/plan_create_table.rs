@@ -1,8 +1,12 @@
 use crate::BustubxResult;
 
-use crate::catalog::Column;
+use crate::catalog::{Column, EMPTY_SCHEMA_REF};
+use crate::common::{ScalarValue, TableReference};
+use crate::expression::{Expr, Literal};
 use crate::planner::logical_plan::{CreateTable, LogicalPlan};
 
+use super::plan_aggregate::check_no_aggregate;
+use super::plan_subquery::check_no_subquery;
 use super::LogicalPlanner;
 
 impl<'a> LogicalPlanner<'a> {
@@ -14,20 +18,48 @@ impl<'a> LogicalPlanner<'a> {
         let name = self.bind_table_name(name)?;
         let mut columns = vec![];
         for col_def in column_defs {
-            let not_null: bool = col_def
-                .options
-                .iter()
-                .find(|opt| matches!(opt.option, sqlparser::ast::ColumnOption::NotNull))
-                .is_some();
-            columns.push(
-                Column::new(
-                    col_def.name.value.clone(),
-                    (&col_def.data_type).try_into()?,
-                    !not_null,
-                )
-                .with_relation(Some(name.clone())),
-            )
+            columns.push(self.bind_column_def(&name, col_def)?);
         }
         Ok(LogicalPlan::CreateTable(CreateTable { name, columns }))
     }
+
+    pub fn bind_column_def(
+        &self,
+        table: &TableReference,
+        col_def: &sqlparser::ast::ColumnDef,
+    ) -> BustubxResult<Column> {
+        let not_null: bool = col_def
+            .options
+            .iter()
+            .any(|opt| matches!(opt.option, sqlparser::ast::ColumnOption::NotNull));
+        let default = col_def.options.iter().find_map(|opt| match &opt.option {
+            sqlparser::ast::ColumnOption::Default(expr) => Some(Box::new(expr.clone())),
+            _ => None,
+        });
+        let column = Column::new(
+            col_def.name.value.clone(),
+            (&col_def.data_type).try_into()?,
+            !not_null,
+        )
+        .with_relation(Some(table.clone()))
+        .with_default(default);
+        // reject a default that can not be assigned to the column now instead of on INSERT
+        self.bind_default(&column)?;
+        Ok(column)
+    }
+
+    /// The value of `column` when an INSERT leaves it out, NULL without a DEFAULT.
+    pub fn bind_default(&self, column: &Column) -> BustubxResult<Expr> {
+        match &column.default {
+            Some(default) => {
+                let expr = self.bind_expr(default, &EMPTY_SCHEMA_REF)?;
+                check_no_aggregate(&expr, "DEFAULT")?;
+                check_no_subquery(&expr, "DEFAULT")?;
+                self.coerce_expr_to(expr, &EMPTY_SCHEMA_REF, column.data_type)
+            }
+            None => Ok(Expr::Literal(Literal {
+                value: ScalarValue::new_empty(column.data_type),
+            })),
+        }
+    }
 }
@@ -0,0 +1,63 @@
+use crate::catalog::{Column, DataType, Schema, SchemaRef};
+use crate::common::TableReference;
+use crate::planner::logical_plan::{Delete, LogicalPlan, TableScan, RID_COLUMN_NAME};
+use crate::{BustubxError, BustubxResult};
+use std::sync::Arc;
+
+use super::LogicalPlanner;
+
+impl<'a> LogicalPlanner<'a> {
+    pub fn plan_delete(
+        &self,
+        from: &[sqlparser::ast::TableWithJoins],
+        selection: &Option<sqlparser::ast::Expr>,
+    ) -> BustubxResult<LogicalPlan> {
+        let table_name = match from {
+            [sqlparser::ast::TableWithJoins {
+                relation: sqlparser::ast::TableFactor::Table { name, .. },
+                joins,
+            }] if joins.is_empty() => name,
+            _ => {
+                return Err(BustubxError::NotSupport(format!(
+                    "DELETE from {} not supported",
+                    from.iter()
+                        .map(|t| t.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )))
+            }
+        };
+        let table = self.bind_table_name(table_name)?;
+        let table_schema = self
+            .context
+            .catalog
+            .get_table_by_name(table.table())
+            .map_or(
+                Err(BustubxError::Plan(format!("table {} not found", table))),
+                |info| Ok(info.schema.clone()),
+            )?;
+
+        let scan = self.plan_rid_scan(&table, &table_schema);
+        let input = self.plan_selection(scan, selection)?;
+        Ok(LogicalPlan::Delete(Delete {
+            table,
+            table_schema,
+            input: Arc::new(input),
+        }))
+    }
+
+    /// Scan of `table` whose rows end with their rid, the input of DELETE and UPDATE.
+    pub fn plan_rid_scan(&self, table: &TableReference, table_schema: &SchemaRef) -> LogicalPlan {
+        let rid_column = Column::new(RID_COLUMN_NAME.to_string(), DataType::UInt64, false)
+            .with_relation(Some(table.clone()));
+        let mut columns = table_schema.columns.clone();
+        columns.push(Arc::new(rid_column));
+        LogicalPlan::TableScan(TableScan {
+            table_ref: table.clone(),
+            table_schema: Arc::new(Schema { columns }),
+            filters: vec![],
+            limit: None,
+            with_rid: true,
+        })
+    }
+}
@@ -0,0 +1,83 @@
+use crate::common::TableReference;
+use crate::planner::logical_plan::{DropIndex, DropTable, LogicalPlan};
+use crate::{BustubxError, BustubxResult};
+
+use super::LogicalPlanner;
+
+impl<'a> LogicalPlanner<'a> {
+    pub fn plan_drop(
+        &self,
+        object_type: &sqlparser::ast::ObjectType,
+        names: &[sqlparser::ast::ObjectName],
+        if_exists: bool,
+    ) -> BustubxResult<LogicalPlan> {
+        match object_type {
+            sqlparser::ast::ObjectType::Table => self.plan_drop_table(names, if_exists),
+            sqlparser::ast::ObjectType::Index => self.plan_drop_index(names, if_exists),
+            _ => Err(BustubxError::NotSupport(format!(
+                "DROP {} not supported",
+                object_type
+            ))),
+        }
+    }
+
+    pub fn plan_drop_table(
+        &self,
+        names: &[sqlparser::ast::ObjectName],
+        if_exists: bool,
+    ) -> BustubxResult<LogicalPlan> {
+        let mut tables = vec![];
+        for name in names {
+            let table = self.bind_table_name(name)?;
+            if self
+                .context
+                .catalog
+                .get_table_by_name(table.table())
+                .is_none()
+            {
+                if if_exists {
+                    continue;
+                }
+                return Err(BustubxError::Plan(format!("table {} not found", table)));
+            }
+            tables.push(table);
+        }
+        Ok(LogicalPlan::DropTable(DropTable { tables, if_exists }))
+    }
+
+    pub fn plan_drop_index(
+        &self,
+        names: &[sqlparser::ast::ObjectName],
+        if_exists: bool,
+    ) -> BustubxResult<LogicalPlan> {
+        let mut indexes = vec![];
+        for name in names {
+            let index_name = name.0.first().map_or(
+                Err(BustubxError::Plan(format!(
+                    "Index name {name} is not expected"
+                ))),
+                |ident| Ok(ident.value.clone()),
+            )?;
+            // DROP INDEX does not name the table, look the index up among all tables
+            let index_info = self
+                .context
+                .catalog
+                .indexes
+                .values()
+                .find(|info| info.name == index_name);
+            match index_info {
+                Some(info) => {
+                    indexes.push((TableReference::bare(info.table_name.clone()), index_name))
+                }
+                None if if_exists => continue,
+                None => {
+                    return Err(BustubxError::Plan(format!(
+                        "index {} not found",
+                        index_name
+                    )))
+                }
+            }
+        }
+        Ok(LogicalPlan::DropIndex(DropIndex { indexes, if_exists }))
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{BustubxError, BustubxResult};
 use std::sync::Arc;
 
-use crate::planner::logical_plan::{Insert, LogicalPlan};
+use crate::catalog::{SchemaRef, EMPTY_SCHEMA_REF};
+use crate::expression::{ColumnExpr, Expr};
+use crate::planner::logical_plan::{project_schema, Insert, LogicalPlan, Project, Values};
 
 use super::LogicalPlanner;
 
@@ -12,7 +14,6 @@ impl<'a> LogicalPlanner<'a> {
         columns_ident: &Vec<sqlparser::ast::Ident>,
         source: &sqlparser::ast::Query,
     ) -> BustubxResult<LogicalPlan> {
-        let values = self.plan_set_expr(source.body.as_ref())?;
         let table = self.bind_table_name(table_name)?;
         let table_schema = self
             .context
@@ -23,26 +24,174 @@ impl<'a> LogicalPlanner<'a> {
                 |info| Ok(info.schema.clone()),
             )?;
 
-        let projected_schema = if columns_ident.is_empty() {
-            table_schema.clone()
-        } else {
-            let columns: Vec<String> = columns_ident
-                .iter()
-                .map(|ident| ident.value.clone())
-                .collect();
-            let indices = columns
-                .iter()
-                .map(|name| table_schema.index_of(Some(&table), name.as_str()))
-                .collect::<BustubxResult<Vec<usize>>>()?;
-            let projected_schema = table_schema.project(&indices)?;
-            projected_schema
+        // the columns the source provides values for, the others get their defaults
+        let indices = match source.body.as_ref() {
+            // INSERT ... DEFAULT VALUES
+            sqlparser::ast::SetExpr::Values(values)
+                if columns_ident.is_empty() && values.rows.iter().all(|row| row.is_empty()) =>
+            {
+                vec![]
+            }
+            _ if columns_ident.is_empty() => (0..table_schema.column_count()).collect(),
+            _ => {
+                let mut indices = vec![];
+                for ident in columns_ident {
+                    let index = table_schema.index_of(Some(&table), &ident.value)?;
+                    if indices.contains(&index) {
+                        return Err(BustubxError::Plan(format!(
+                            "column \"{}\" specified more than once",
+                            ident.value
+                        )));
+                    }
+                    indices.push(index);
+                }
+                indices
+            }
+        };
+        let projected_schema = table_schema.project(&indices)?;
+        let defaults = table_schema
+            .columns
+            .iter()
+            .map(|col| self.bind_default(col))
+            .collect::<BustubxResult<Vec<Expr>>>()?;
+
+        let input = match source.body.as_ref() {
+            // VALUES rows are cast to the column types directly, so a NULL or a string literal
+            // takes the type of its column instead of the type of the other rows
+            sqlparser::ast::SetExpr::Values(values) => {
+                self.plan_insert_values(values, &table_schema, &indices, defaults)?
+            }
+            body => {
+                let input =
+                    self.coerce_insert_input(self.plan_set_expr(body)?, &projected_schema)?;
+                self.fill_insert_defaults(input, &indices, defaults)?
+            }
         };
 
         Ok(LogicalPlan::Insert(Insert {
             table,
             table_schema,
             projected_schema,
-            input: Arc::new(values),
+            input: Arc::new(input),
         }))
     }
+
+    /// Builds full table rows from VALUES rows, where a `DEFAULT` item takes the default of its
+    /// column.
+    fn plan_insert_values(
+        &self,
+        values: &sqlparser::ast::Values,
+        table_schema: &SchemaRef,
+        indices: &[usize],
+        defaults: Vec<Expr>,
+    ) -> BustubxResult<LogicalPlan> {
+        let mut rows = vec![];
+        for row in values.rows.iter() {
+            if row.len() != indices.len() {
+                return Err(insert_arity_error(indices.len(), row.len()));
+            }
+            let mut full_row = defaults.clone();
+            for (item, index) in row.iter().zip(indices.iter()) {
+                if is_default_keyword(item) {
+                    continue;
+                }
+                full_row[*index] = self.coerce_expr_to(
+                    self.bind_values_item(item)?,
+                    &EMPTY_SCHEMA_REF,
+                    table_schema.columns[*index].data_type,
+                )?;
+            }
+            rows.push(full_row);
+        }
+        Ok(LogicalPlan::Values(Values {
+            schema: table_schema.clone(),
+            values: rows,
+        }))
+    }
+
+    /// Puts the columns of an `INSERT ... SELECT` at their positions in the table and the
+    /// defaults of the other columns around them.
+    fn fill_insert_defaults(
+        &self,
+        input: LogicalPlan,
+        indices: &[usize],
+        defaults: Vec<Expr>,
+    ) -> BustubxResult<LogicalPlan> {
+        if indices.iter().copied().eq(0..defaults.len()) {
+            return Ok(input);
+        }
+        let input_schema = input.schema().clone();
+        let mut exprs = defaults;
+        for (col, index) in input_schema.columns.iter().zip(indices.iter()) {
+            exprs[*index] = Expr::Column(ColumnExpr {
+                relation: col.relation.clone(),
+                name: col.name.clone(),
+            });
+        }
+        let schema = Arc::new(project_schema(&input, &exprs)?);
+        Ok(LogicalPlan::Project(Project {
+            exprs,
+            input: Arc::new(input),
+            schema,
+        }))
+    }
+
+    /// Cast the rows of an `INSERT ... SELECT` to the types of the target columns.
+    fn coerce_insert_input(
+        &self,
+        input: LogicalPlan,
+        target_schema: &SchemaRef,
+    ) -> BustubxResult<LogicalPlan> {
+        let target_types = target_schema
+            .columns
+            .iter()
+            .map(|col| col.data_type)
+            .collect::<Vec<_>>();
+        let input_schema = input.schema().clone();
+        if input_schema.column_count() != target_types.len() {
+            return Err(insert_arity_error(
+                target_types.len(),
+                input_schema.column_count(),
+            ));
+        }
+        if input_schema
+            .columns
+            .iter()
+            .zip(target_types.iter())
+            .all(|(col, data_type)| col.data_type == *data_type)
+        {
+            return Ok(input);
+        }
+        let exprs = input_schema
+            .columns
+            .iter()
+            .zip(target_types.iter())
+            .map(|(col, data_type)| {
+                let expr = Expr::Column(ColumnExpr {
+                    relation: col.relation.clone(),
+                    name: col.name.clone(),
+                });
+                self.coerce_expr_to(expr, &input_schema, *data_type)
+            })
+            .collect::<BustubxResult<Vec<Expr>>>()?;
+        let schema = Arc::new(project_schema(&input, &exprs)?);
+        Ok(LogicalPlan::Project(Project {
+            exprs,
+            input: Arc::new(input),
+            schema,
+        }))
+    }
+}
+
+/// sqlparser reads `DEFAULT` in a VALUES row as an identifier.
+fn is_default_keyword(item: &sqlparser::ast::Expr) -> bool {
+    matches!(item, sqlparser::ast::Expr::Identifier(ident)
+        if ident.quote_style.is_none() && ident.value.eq_ignore_ascii_case("default"))
+}
+
+fn insert_arity_error(expected: usize, actual: usize) -> BustubxError {
+    BustubxError::Plan(format!(
+        "INSERT has {} target columns but {} expressions",
+        expected, actual
+    ))
 }
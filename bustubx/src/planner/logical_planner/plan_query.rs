@@ -1,10 +1,13 @@
+use crate::catalog::EMPTY_SCHEMA_REF;
 use crate::common::ScalarValue;
-use crate::expression::Expr;
+use crate::expression::{ColumnExpr, Expr};
 use crate::{BustubxError, BustubxResult};
 use std::sync::Arc;
 
-use crate::planner::logical_plan::{Limit, LogicalPlan, Sort};
+use crate::planner::logical_plan::{Limit, LogicalPlan, Project, Sort};
 
+use super::plan_aggregate::{check_no_aggregate, find_unknown_column};
+use super::plan_subquery::check_no_subquery;
 use super::LogicalPlanner;
 
 impl<'a> LogicalPlanner<'a> {
@@ -23,16 +26,77 @@ impl<'a> LogicalPlanner<'a> {
             return Ok(input);
         }
 
+        let output_schema = input.schema().clone();
+        let mut input = input;
         let mut order_by_exprs = vec![];
         for order in order_by {
-            order_by_exprs.push(self.bind_order_by_expr(order)?);
+            let mut order_by_expr = self.bind_order_by_expr(order, input.schema())?;
+            // e.g. ORDER BY count(*) sorts by the select item computing it
+            let name = format!("{}", order_by_expr.expr);
+            if !matches!(order_by_expr.expr.as_ref(), Expr::Column(_))
+                && input.schema().index_of(None, &name).is_ok()
+            {
+                order_by_expr.expr = Box::new(Expr::Column(ColumnExpr {
+                    relation: None,
+                    name,
+                }));
+            }
+            check_no_aggregate(&order_by_expr.expr, "ORDER BY")?;
+            check_no_subquery(&order_by_expr.expr, "ORDER BY")?;
+            while let Some(column) = find_unknown_column(&order_by_expr.expr, input.schema()) {
+                input = self.plan_hidden_sort_column(input, column.clone())?;
+            }
+            order_by_expr.expr = Box::new(self.coerce_expr(*order_by_expr.expr, input.schema())?);
+            order_by_exprs.push(order_by_expr);
         }
 
-        Ok(LogicalPlan::Sort(Sort {
+        let sort = LogicalPlan::Sort(Sort {
             order_by: order_by_exprs,
             input: Arc::new(input),
             limit: None,
-        }))
+        });
+        if sort.schema() == &output_schema {
+            return Ok(sort);
+        }
+        // drop the hidden sort columns again
+        let exprs = output_schema
+            .columns
+            .iter()
+            .map(|col| {
+                Expr::Column(ColumnExpr {
+                    relation: col.relation.clone(),
+                    name: col.name.clone(),
+                })
+            })
+            .collect::<Vec<Expr>>();
+        self.plan_project(sort, exprs)
+    }
+
+    /// A plain SELECT can be sorted on a column of its input that is not selected, the column is
+    /// appended to the select list. Only the output columns of other queries can be sorted on.
+    fn plan_hidden_sort_column(
+        &self,
+        input: LogicalPlan,
+        column: ColumnExpr,
+    ) -> BustubxResult<LogicalPlan> {
+        match input {
+            LogicalPlan::Project(Project { exprs, input, .. })
+                if input
+                    .schema()
+                    .index_of(column.relation.as_ref(), &column.name)
+                    .is_ok() =>
+            {
+                let exprs = [exprs, vec![Expr::Column(column)]].concat();
+                self.plan_project(input.as_ref().clone(), exprs)
+            }
+            LogicalPlan::Distinct(_) => Err(BustubxError::Plan(
+                "for SELECT DISTINCT, ORDER BY expressions must appear in select list".to_string(),
+            )),
+            _ => Err(BustubxError::Plan(format!(
+                "column \"{}\" does not exist",
+                column
+            ))),
+        }
     }
 
     pub fn plan_limit(
@@ -48,7 +112,7 @@ impl<'a> LogicalPlanner<'a> {
         let limit = match limit {
             None => None,
             Some(limit_expr) => {
-                let n = match self.bind_expr(&limit_expr)? {
+                let n = match self.bind_expr(&limit_expr, &EMPTY_SCHEMA_REF)? {
                     Expr::Literal(lit) => match lit.value {
                         ScalarValue::Int64(Some(v)) if v >= 0 => Ok(v as usize),
                         _ => Err(BustubxError::Plan(format!(
@@ -67,7 +131,7 @@ impl<'a> LogicalPlanner<'a> {
 
         let offset = match offset {
             None => 0,
-            Some(offset_expr) => match self.bind_expr(&offset_expr.value)? {
+            Some(offset_expr) => match self.bind_expr(&offset_expr.value, &EMPTY_SCHEMA_REF)? {
                 Expr::Literal(lit) => match lit.value {
                     ScalarValue::Int64(Some(v)) => {
                         if v < 0 {
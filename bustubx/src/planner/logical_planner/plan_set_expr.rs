@@ -1,8 +1,14 @@
-use crate::catalog::{Column, Schema, EMPTY_SCHEMA_REF};
-use crate::expression::{Alias, ColumnExpr, Expr, ExprTrait};
+use super::plan_aggregate::check_no_aggregate;
+use super::plan_subquery::{check_no_subquery, conjunction};
+use crate::catalog::{Column, DataType, Schema, EMPTY_SCHEMA_REF};
+use crate::expression::type_coercion::common_type;
+use crate::expression::{
+    Alias, BinaryExpr, BinaryOp, BuiltinScalarFunction, ColumnExpr, Expr, ExprTrait,
+    ScalarFunction, ScalarFunctionDefinition,
+};
 use crate::planner::logical_plan::JoinType;
 use crate::planner::logical_plan::{
-    build_join_schema, project_schema, EmptyRelation, Filter, Join, LogicalPlan, Project,
+    build_join_schema, project_schema, Distinct, EmptyRelation, Join, LogicalPlan, Project,
     TableScan, Values,
 };
 use crate::planner::LogicalPlanner;
@@ -14,6 +20,13 @@ impl LogicalPlanner<'_> {
         match set_expr {
             sqlparser::ast::SetExpr::Select(select) => self.plan_select(select),
             sqlparser::ast::SetExpr::Values(values) => self.plan_values(values),
+            sqlparser::ast::SetExpr::Query(query) => self.plan_query(query),
+            sqlparser::ast::SetExpr::SetOperation {
+                op,
+                set_quantifier,
+                left,
+                right,
+            } => self.plan_set_operation(op, set_quantifier, left, right),
             _ => Err(BustubxError::Plan(format!(
                 "Failed to plan set expr: {}",
                 set_expr
@@ -24,22 +37,45 @@ impl LogicalPlanner<'_> {
     pub fn plan_select(&self, select: &sqlparser::ast::Select) -> BustubxResult<LogicalPlan> {
         let table_scan = self.plan_from_tables(&select.from)?;
         let selection = self.plan_selection(table_scan, &select.selection)?;
-        self.plan_project(selection, &select.projection)
+        let exprs = self.bind_select_items(&selection, &select.projection)?;
+        let having = select
+            .having
+            .as_ref()
+            .map(|having| {
+                let having = self.bind_expr(having, selection.schema())?;
+                self.coerce_predicate(having, selection.schema())
+            })
+            .transpose()?;
+        let (input, exprs) = self.plan_aggregate(selection, exprs, having, &select.group_by)?;
+        let plan = self.plan_project(input, exprs)?;
+        match &select.distinct {
+            None => Ok(plan),
+            Some(sqlparser::ast::Distinct::Distinct) => {
+                Ok(LogicalPlan::Distinct(Distinct::new(Arc::new(plan))))
+            }
+            Some(distinct) => Err(BustubxError::NotSupport(format!(
+                "{} not supported",
+                distinct
+            ))),
+        }
     }
 
-    pub fn plan_project(
+    /// Bind and coerce the select list against the schema of `input`, wildcards are expanded.
+    pub fn bind_select_items(
         &self,
-        input: LogicalPlan,
+        input: &LogicalPlan,
         project: &Vec<sqlparser::ast::SelectItem>,
-    ) -> BustubxResult<LogicalPlan> {
+    ) -> BustubxResult<Vec<Expr>> {
         let mut exprs = vec![];
         for select_item in project {
             match select_item {
-                sqlparser::ast::SelectItem::UnnamedExpr(expr) => exprs.push(self.bind_expr(expr)?),
+                sqlparser::ast::SelectItem::UnnamedExpr(expr) => {
+                    exprs.push(self.bind_expr(expr, input.schema())?)
+                }
                 sqlparser::ast::SelectItem::ExprWithAlias { expr, alias } => {
                     exprs.push(Expr::Alias(Alias {
                         name: alias.value.clone(),
-                        expr: Box::new(self.bind_expr(expr)?),
+                        expr: Box::new(self.bind_expr(expr, input.schema())?),
                     }))
                 }
                 sqlparser::ast::SelectItem::Wildcard(_) => {
@@ -47,6 +83,7 @@ impl LogicalPlanner<'_> {
                         .schema()
                         .columns
                         .iter()
+                        .filter(|col| !col.hidden)
                         .map(|col| {
                             Expr::Column(ColumnExpr {
                                 relation: col.relation.clone(),
@@ -64,6 +101,33 @@ impl LogicalPlanner<'_> {
                 }
             }
         }
+        exprs
+            .into_iter()
+            .map(|expr| self.coerce_expr(expr, input.schema()))
+            .collect::<BustubxResult<Vec<Expr>>>()
+    }
+
+    pub fn plan_project(&self, input: LogicalPlan, exprs: Vec<Expr>) -> BustubxResult<LogicalPlan> {
+        // subqueries are evaluated by Apply nodes below the projection
+        let mut input = input;
+        let mut planned_exprs = vec![];
+        for expr in exprs {
+            if !expr.contains_subquery() {
+                planned_exprs.push(expr);
+                continue;
+            }
+            let (plan, planned) = self.plan_apply(input, expr.clone())?;
+            input = plan;
+            planned_exprs.push(match expr {
+                Expr::Alias(_) => planned,
+                // keep the column name of the original expression
+                expr => Expr::Alias(Alias {
+                    name: format!("{expr}"),
+                    expr: Box::new(planned),
+                }),
+            });
+        }
+        let exprs = planned_exprs;
         let schema = Arc::new(project_schema(&input, &exprs)?);
         Ok(LogicalPlan::Project(Project {
             exprs,
@@ -80,11 +144,10 @@ impl LogicalPlanner<'_> {
         match selection {
             None => Ok(input),
             Some(predicate) => {
-                let predicate = self.bind_expr(predicate)?;
-                Ok(LogicalPlan::Filter(Filter {
-                    input: Arc::new(input),
-                    predicate,
-                }))
+                let predicate = self
+                    .coerce_predicate(self.bind_expr(predicate, input.schema())?, input.schema())?;
+                check_no_aggregate(&predicate, "WHERE")?;
+                self.plan_filter(input, predicate)
             }
         }
     }
@@ -137,13 +200,13 @@ impl LogicalPlanner<'_> {
                 self.plan_join(left, right, constraint, JoinType::Inner)
             }
             sqlparser::ast::JoinOperator::LeftOuter(constraint) => {
-                self.plan_join(left, right, constraint, JoinType::Inner)
+                self.plan_join(left, right, constraint, JoinType::LeftOuter)
             }
             sqlparser::ast::JoinOperator::RightOuter(constraint) => {
-                self.plan_join(left, right, constraint, JoinType::Inner)
+                self.plan_join(left, right, constraint, JoinType::RightOuter)
             }
             sqlparser::ast::JoinOperator::FullOuter(constraint) => {
-                self.plan_join(left, right, constraint, JoinType::Inner)
+                self.plan_join(left, right, constraint, JoinType::FullOuter)
             }
             sqlparser::ast::JoinOperator::CrossJoin => self.plan_cross_join(left, right),
             _ => Err(BustubxError::Plan(format!(
@@ -162,8 +225,10 @@ impl LogicalPlanner<'_> {
     ) -> BustubxResult<LogicalPlan> {
         match constraint {
             sqlparser::ast::JoinConstraint::On(expr) => {
-                let expr = self.bind_expr(expr)?;
                 let schema = Arc::new(build_join_schema(left.schema(), right.schema(), join_type)?);
+                let expr = self.coerce_predicate(self.bind_expr(expr, &schema)?, &schema)?;
+                check_no_aggregate(&expr, "JOIN ON")?;
+                check_no_subquery(&expr, "JOIN ON")?;
                 Ok(LogicalPlan::Join(Join {
                     left: Arc::new(left),
                     right: Arc::new(right),
@@ -172,6 +237,22 @@ impl LogicalPlanner<'_> {
                     schema,
                 }))
             }
+            sqlparser::ast::JoinConstraint::Using(idents) => {
+                let using = idents.iter().map(|ident| ident.value.clone()).collect();
+                self.plan_using_join(left, right, using, join_type)
+            }
+            // joins on all the column names both sides have in common
+            sqlparser::ast::JoinConstraint::Natural => {
+                let mut using: Vec<String> = vec![];
+                for col in left.schema().columns.iter().filter(|col| !col.hidden) {
+                    if right.schema().index_of(None, &col.name).is_ok()
+                        && !using.contains(&col.name)
+                    {
+                        using.push(col.name.clone());
+                    }
+                }
+                self.plan_using_join(left, right, using, join_type)
+            }
             _ => Err(BustubxError::Plan(format!(
                 "Only support join on constraint, {:?}",
                 constraint
@@ -179,6 +260,124 @@ impl LogicalPlanner<'_> {
         }
     }
 
+    /// Join on the equality of the `using` columns of both sides. Each pair is merged into a
+    /// single unqualified output column in front of all columns of the left and right side.
+    /// The key columns of both sides stay reachable by qualified name but are hidden from
+    /// `SELECT *`.
+    fn plan_using_join(
+        &self,
+        left: LogicalPlan,
+        right: LogicalPlan,
+        using: Vec<String>,
+        join_type: JoinType,
+    ) -> BustubxResult<LogicalPlan> {
+        let schema = Arc::new(build_join_schema(left.schema(), right.schema(), join_type)?);
+        let left_len = left.schema().columns.len();
+        let using_column = |plan: &LogicalPlan, name: &str, side: &str| {
+            let index = plan.schema().index_of(None, name).map_err(|_| {
+                BustubxError::Plan(format!(
+                    "column \"{}\" specified in USING clause does not exist in {} table",
+                    name, side
+                ))
+            })?;
+            let col = plan.schema().column_with_index(index)?;
+            Ok::<_, BustubxError>((
+                index,
+                Expr::Column(ColumnExpr {
+                    relation: col.relation.clone(),
+                    name: col.name.clone(),
+                }),
+            ))
+        };
+
+        let mut conditions = vec![];
+        let mut exprs = vec![];
+        let mut left_using = vec![];
+        let mut right_using = vec![];
+        for (i, name) in using.iter().enumerate() {
+            if using[..i].contains(name) {
+                return Err(BustubxError::Plan(format!(
+                    "column name \"{}\" appears more than once in USING clause",
+                    name
+                )));
+            }
+            let (left_index, left_expr) = using_column(&left, name, "left")?;
+            let (right_index, right_expr) = using_column(&right, name, "right")?;
+            left_using.push(left_index);
+            right_using.push(right_index);
+            conditions.push(Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(left_expr.clone()),
+                op: BinaryOp::Eq,
+                right: Box::new(right_expr.clone()),
+            }));
+            // the merged column takes the value of the side which is never NULL padded
+            let merged = match join_type {
+                JoinType::RightOuter => right_expr,
+                JoinType::FullOuter => Expr::ScalarFunction(ScalarFunction::try_new(
+                    ScalarFunctionDefinition::Builtin(BuiltinScalarFunction::Coalesce),
+                    vec![left_expr, right_expr],
+                )?),
+                _ => left_expr,
+            };
+            exprs.push(Expr::Alias(Alias {
+                name: name.clone(),
+                expr: Box::new(merged),
+            }));
+        }
+        for plan in [&left, &right] {
+            for col in plan.schema().columns.iter() {
+                exprs.push(Expr::Column(ColumnExpr {
+                    relation: col.relation.clone(),
+                    name: col.name.clone(),
+                }));
+            }
+        }
+
+        let condition = conjunction(conditions)
+            .map(|condition| self.coerce_predicate(condition, &schema))
+            .transpose()?;
+        let exprs = exprs
+            .into_iter()
+            .map(|expr| self.coerce_expr(expr, &schema))
+            .collect::<BustubxResult<Vec<Expr>>>()?;
+        let join = LogicalPlan::Join(Join {
+            left: Arc::new(left),
+            right: Arc::new(right),
+            join_type,
+            condition,
+            schema,
+        });
+        let LogicalPlan::Project(mut project) = self.plan_project(join, exprs)? else {
+            unreachable!("plan_project always returns a Project");
+        };
+        // the merged columns come first, followed by all left and then all right columns
+        let hidden = left_using
+            .iter()
+            .map(|index| using.len() + index)
+            .chain(
+                right_using
+                    .iter()
+                    .map(|index| using.len() + left_len + index),
+            )
+            .collect::<Vec<usize>>();
+        project.schema = Arc::new(Schema {
+            columns: project
+                .schema
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(index, col)| {
+                    if hidden.contains(&index) {
+                        Arc::new(col.as_ref().clone().with_hidden(true))
+                    } else {
+                        col.clone()
+                    }
+                })
+                .collect(),
+        });
+        Ok(LogicalPlan::Project(project))
+    }
+
     pub fn plan_cross_join(
         &self,
         left: LogicalPlan,
@@ -220,6 +419,7 @@ impl LogicalPlanner<'_> {
                     table_schema: schema,
                     filters: vec![],
                     limit: None,
+                    with_rid: false,
                 }))
             }
             sqlparser::ast::TableFactor::NestedJoin {
@@ -240,14 +440,7 @@ impl LogicalPlanner<'_> {
     }
 
     pub fn plan_values(&self, values: &sqlparser::ast::Values) -> BustubxResult<LogicalPlan> {
-        let mut result = vec![];
-        for row in values.rows.iter() {
-            let mut record = vec![];
-            for item in row {
-                record.push(self.bind_expr(item)?);
-            }
-            result.push(record);
-        }
+        let result = self.bind_values(values)?;
         if result.is_empty() {
             return Ok(LogicalPlan::Values(Values {
                 schema: Arc::new(Schema::empty()),
@@ -255,20 +448,67 @@ impl LogicalPlanner<'_> {
             }));
         }
 
-        // parse schema
-        let first_row = &result[0];
+        // parse schema, every row takes part so a NULL in the first row still gets a type
+        let column_count = result[0].len();
         let mut columns = vec![];
-        for (idx, item) in first_row.iter().enumerate() {
-            columns.push(Column::new(
-                idx.to_string(),
-                item.data_type(&EMPTY_SCHEMA_REF)?,
-                item.nullable(&EMPTY_SCHEMA_REF)?,
-            ))
+        for idx in 0..column_count {
+            let mut data_type = DataType::Null;
+            let mut nullable = false;
+            for row in result.iter() {
+                let item = row.get(idx).ok_or_else(|| {
+                    BustubxError::Plan("VALUES lists must all be the same length".to_string())
+                })?;
+                let item_type = item.data_type(&EMPTY_SCHEMA_REF)?;
+                data_type = common_type(data_type, item_type).ok_or_else(|| {
+                    BustubxError::Plan(format!(
+                        "VALUES column {} has incompatible types {} and {}",
+                        idx + 1,
+                        data_type,
+                        item_type
+                    ))
+                })?;
+                nullable |= item.nullable(&EMPTY_SCHEMA_REF)?;
+            }
+            columns.push(Column::new(idx.to_string(), data_type, nullable));
+        }
+
+        let mut rows = vec![];
+        for row in result {
+            if row.len() != column_count {
+                return Err(BustubxError::Plan(
+                    "VALUES lists must all be the same length".to_string(),
+                ));
+            }
+            let row = row
+                .into_iter()
+                .zip(columns.iter())
+                .map(|(item, col)| self.coerce_expr_to(item, &EMPTY_SCHEMA_REF, col.data_type))
+                .collect::<BustubxResult<Vec<Expr>>>()?;
+            rows.push(row);
         }
 
         Ok(LogicalPlan::Values(Values {
             schema: Arc::new(Schema::new(columns)),
-            values: result,
+            values: rows,
         }))
     }
+
+    pub fn bind_values(&self, values: &sqlparser::ast::Values) -> BustubxResult<Vec<Vec<Expr>>> {
+        let mut result = vec![];
+        for row in values.rows.iter() {
+            let mut record = vec![];
+            for item in row {
+                record.push(self.bind_values_item(item)?);
+            }
+            result.push(record);
+        }
+        Ok(result)
+    }
+
+    pub fn bind_values_item(&self, item: &sqlparser::ast::Expr) -> BustubxResult<Expr> {
+        let expr = self.coerce_expr(self.bind_expr(item, &EMPTY_SCHEMA_REF)?, &EMPTY_SCHEMA_REF)?;
+        check_no_aggregate(&expr, "VALUES")?;
+        check_no_subquery(&expr, "VALUES")?;
+        Ok(expr)
+    }
 }
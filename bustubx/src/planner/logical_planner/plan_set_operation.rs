@@ -0,0 +1,65 @@
+use crate::catalog::{Column, Schema};
+use crate::expression::type_coercion::common_type;
+use crate::planner::logical_plan::{LogicalPlan, SetOperation, SetOperator};
+use crate::{BustubxError, BustubxResult};
+use std::sync::Arc;
+
+use super::LogicalPlanner;
+
+impl LogicalPlanner<'_> {
+    pub fn plan_set_operation(
+        &self,
+        op: &sqlparser::ast::SetOperator,
+        set_quantifier: &sqlparser::ast::SetQuantifier,
+        left: &sqlparser::ast::SetExpr,
+        right: &sqlparser::ast::SetExpr,
+    ) -> BustubxResult<LogicalPlan> {
+        let op = match op {
+            sqlparser::ast::SetOperator::Union => SetOperator::Union,
+            sqlparser::ast::SetOperator::Intersect => SetOperator::Intersect,
+            sqlparser::ast::SetOperator::Except => SetOperator::Except,
+        };
+        let all = match set_quantifier {
+            sqlparser::ast::SetQuantifier::All => true,
+            sqlparser::ast::SetQuantifier::Distinct | sqlparser::ast::SetQuantifier::None => false,
+        };
+        let left = self.plan_set_expr(left)?;
+        let right = self.plan_set_expr(right)?;
+        let schema = set_operation_schema(op, left.schema(), right.schema())?;
+        Ok(LogicalPlan::SetOperation(SetOperation {
+            op,
+            all,
+            left: Arc::new(left),
+            right: Arc::new(right),
+            schema: Arc::new(schema),
+        }))
+    }
+}
+
+/// The output columns are named after the left input and have the common type of both inputs,
+/// the rows of both inputs are cast to them.
+fn set_operation_schema(op: SetOperator, left: &Schema, right: &Schema) -> BustubxResult<Schema> {
+    let op_name = op.to_string().to_uppercase();
+    if left.column_count() != right.column_count() {
+        return Err(BustubxError::Plan(format!(
+            "each {} query must have the same number of columns",
+            op_name
+        )));
+    }
+    let mut columns = vec![];
+    for (left_col, right_col) in left.columns.iter().zip(right.columns.iter()) {
+        let data_type = common_type(left_col.data_type, right_col.data_type).ok_or_else(|| {
+            BustubxError::Plan(format!(
+                "{} types {} and {} cannot be matched",
+                op_name, left_col.data_type, right_col.data_type
+            ))
+        })?;
+        let nullable = match op {
+            SetOperator::Union => left_col.nullable || right_col.nullable,
+            SetOperator::Intersect => left_col.nullable && right_col.nullable,
+            SetOperator::Except => left_col.nullable,
+        };
+        columns.push(Column::new(left_col.name.clone(), data_type, nullable));
+    }
+    Ok(Schema::new(columns))
+}
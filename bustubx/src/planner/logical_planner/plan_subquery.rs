@@ -0,0 +1,395 @@
+use crate::catalog::{Column, Schema};
+use crate::expression::{
+    BinaryExpr, BinaryOp, ColumnExpr, Exists, Expr, ExprTrait, InSubquery, Subquery, UnaryExpr,
+    UnaryOp,
+};
+use crate::planner::logical_plan::{
+    build_join_schema, expr_has_outer_references, plan_has_outer_references, Apply, Filter, Join,
+    JoinType, LogicalPlan, Project,
+};
+use crate::planner::{LogicalPlanner, PlannerContext};
+use crate::{BustubxError, BustubxResult};
+use std::sync::Arc;
+
+impl LogicalPlanner<'_> {
+    /// Plan the query of a subquery expression. Columns of `schema`, the input of the
+    /// expression, and of all enclosing queries can be referenced from it.
+    pub fn bind_subquery(
+        &self,
+        query: &sqlparser::ast::Query,
+        schema: &Schema,
+    ) -> BustubxResult<Subquery> {
+        let outer_query_schema = match self.context.outer_query_schema.as_ref() {
+            Some(outer) => Schema::try_merge(vec![schema.clone(), outer.as_ref().clone()])?,
+            None => schema.clone(),
+        };
+        let planner = LogicalPlanner {
+            context: PlannerContext {
+                catalog: self.context.catalog,
+                function_registry: self.context.function_registry,
+                outer_query_schema: Some(Arc::new(outer_query_schema)),
+            },
+        };
+        Ok(Subquery {
+            subquery: Arc::new(planner.plan_query(query)?),
+        })
+    }
+
+    /// Filter `input` by a coerced `predicate`. Top level `[NOT] EXISTS` and `[NOT] IN`
+    /// subqueries become semi or anti joins where possible, any other subquery is evaluated
+    /// for every row by an `Apply`.
+    pub fn plan_filter(&self, input: LogicalPlan, predicate: Expr) -> BustubxResult<LogicalPlan> {
+        if !predicate.contains_subquery() {
+            return Ok(LogicalPlan::Filter(Filter {
+                predicate,
+                input: Arc::new(input),
+            }));
+        }
+
+        let mut plan = input;
+        let mut remaining = vec![];
+        for conjunct in split_conjunction(predicate) {
+            match self.try_decorrelate(&plan, &conjunct)? {
+                Some(join) => plan = join,
+                None => remaining.push(conjunct),
+            }
+        }
+        let Some(predicate) = conjunction(remaining) else {
+            return Ok(plan);
+        };
+        if !predicate.contains_subquery() {
+            return Ok(LogicalPlan::Filter(Filter {
+                predicate,
+                input: Arc::new(plan),
+            }));
+        }
+
+        // the subquery columns added by Apply are dropped again after filtering
+        let schema = plan.schema().clone();
+        let (plan, predicate) = self.plan_apply(plan, predicate)?;
+        let filter = LogicalPlan::Filter(Filter {
+            predicate,
+            input: Arc::new(plan),
+        });
+        let exprs = schema
+            .columns
+            .iter()
+            .map(|col| {
+                Expr::Column(ColumnExpr {
+                    relation: col.relation.clone(),
+                    name: col.name.clone(),
+                })
+            })
+            .collect();
+        Ok(LogicalPlan::Project(Project {
+            exprs,
+            input: Arc::new(filter),
+            schema,
+        }))
+    }
+
+    /// Add an `Apply` to `input` for every subquery in `expr`, and replace each subquery by a
+    /// reference to the column holding its value.
+    pub fn plan_apply(&self, input: LogicalPlan, expr: Expr) -> BustubxResult<(LogicalPlan, Expr)> {
+        let column_count = input.schema().column_count();
+        let mut subqueries = vec![];
+        let expr = expr.transform_up(&mut |expr| match expr {
+            Expr::ScalarSubquery(_) | Expr::Exists(_) | Expr::InSubquery(_) => {
+                let name = format!("__subquery_{}", column_count + subqueries.len());
+                subqueries.push((name.clone(), expr));
+                Ok(Expr::Column(ColumnExpr {
+                    relation: None,
+                    name,
+                }))
+            }
+            expr => Ok(expr),
+        })?;
+
+        let mut plan = input;
+        for (name, subquery) in subqueries {
+            let column = Column::new(
+                name,
+                subquery.data_type(plan.schema())?,
+                subquery.nullable(plan.schema())?,
+            );
+            let schema = Schema::try_merge(vec![
+                plan.schema().as_ref().clone(),
+                Schema::new(vec![column]),
+            ])?;
+            plan = LogicalPlan::Apply(Apply {
+                input: Arc::new(plan),
+                subquery,
+                schema: Arc::new(schema),
+            });
+        }
+        Ok((plan, expr))
+    }
+
+    /// Turn `input WHERE [NOT] EXISTS (subquery)` or `input WHERE x [NOT] IN (subquery)` into
+    /// a semi or anti join of `input` and the subquery. Predicates of the subquery referencing
+    /// `input` become part of the join condition. Returns `None` if the subquery has a shape
+    /// which can not be joined, e.g. an outer reference below an aggregate or a LIMIT.
+    fn try_decorrelate(
+        &self,
+        input: &LogicalPlan,
+        conjunct: &Expr,
+    ) -> BustubxResult<Option<LogicalPlan>> {
+        let (subquery, in_expr, negated) = match conjunct {
+            Expr::Exists(Exists { subquery, negated }) => (subquery, None, *negated),
+            Expr::InSubquery(InSubquery {
+                expr,
+                subquery,
+                negated,
+            }) if !expr.contains_subquery() => (subquery, Some(expr.as_ref()), *negated),
+            _ => return Ok(None),
+        };
+
+        let subquery = subquery.subquery.as_ref();
+        let (right, compared, correlated) = if plan_has_outer_references(subquery) {
+            match pull_up_correlated_predicates(subquery, in_expr.is_some()) {
+                Some(pulled) => pulled,
+                None => return Ok(None),
+            }
+        } else {
+            let compared = subquery.schema().columns.first().map(|col| {
+                Expr::Column(ColumnExpr {
+                    relation: col.relation.clone(),
+                    name: col.name.clone(),
+                })
+            });
+            (subquery.clone(), compared, vec![])
+        };
+
+        // qualify columns so they can be told apart once both sides are merged
+        let left_schema = input.schema();
+        let right_schema = right.schema();
+        let qualify_right = |expr: Expr| {
+            expr.transform_up(&mut |expr| {
+                Ok(match expr {
+                    Expr::Column(column) => Expr::Column(qualify_column(column, right_schema)),
+                    Expr::OuterReferenceColumn(outer)
+                        if left_schema
+                            .index_of(outer.column.relation.as_ref(), &outer.column.name)
+                            .is_ok() =>
+                    {
+                        Expr::Column(qualify_column(outer.column, left_schema))
+                    }
+                    expr => expr,
+                })
+            })
+        };
+
+        let mut conditions = vec![];
+        if let (Some(in_expr), Some(compared)) = (in_expr, compared) {
+            let left = in_expr.clone().transform_up(&mut |expr| {
+                Ok(match expr {
+                    Expr::Column(column) => Expr::Column(qualify_column(column, left_schema)),
+                    expr => expr,
+                })
+            })?;
+            let eq = Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(left),
+                op: BinaryOp::Eq,
+                right: Box::new(qualify_right(compared)?),
+            });
+            // a row is kept by NOT IN only if it compares false with every value
+            conditions.push(match negated {
+                true => Expr::UnaryExpr(UnaryExpr {
+                    op: UnaryOp::IsNotFalse,
+                    expr: Box::new(eq),
+                }),
+                false => eq,
+            });
+        }
+        for predicate in correlated {
+            conditions.push(qualify_right(predicate)?);
+        }
+
+        let merged_schema = build_join_schema(left_schema, right_schema, JoinType::Inner)?;
+        let condition = match conjunction(conditions) {
+            Some(condition) => {
+                let ambiguous = condition.exists(&|expr| match expr {
+                    Expr::Column(column) => !resolves_uniquely(column, &merged_schema),
+                    _ => false,
+                });
+                if ambiguous {
+                    return Ok(None);
+                }
+                Some(self.coerce_predicate(condition, &merged_schema)?)
+            }
+            None => None,
+        };
+
+        let join_type = if negated {
+            JoinType::LeftAnti
+        } else {
+            JoinType::LeftSemi
+        };
+        let schema = build_join_schema(left_schema, right_schema, join_type)?;
+        Ok(Some(LogicalPlan::Join(Join {
+            left: Arc::new(input.clone()),
+            right: Arc::new(right),
+            join_type,
+            condition,
+            schema: Arc::new(schema),
+        })))
+    }
+}
+
+/// Strip the projection of a correlated subquery and take the predicates referencing outer
+/// columns out of its filters. Returns the remaining uncorrelated plan, the expression an IN
+/// compares with and the correlated predicates.
+fn pull_up_correlated_predicates(
+    subquery: &LogicalPlan,
+    is_in: bool,
+) -> Option<(LogicalPlan, Option<Expr>, Vec<Expr>)> {
+    let mut plan = subquery;
+    // the order of rows does not matter for EXISTS and IN
+    if let LogicalPlan::Sort(sort) = plan {
+        plan = sort.input.as_ref();
+    }
+    let mut compared = None;
+    match plan {
+        LogicalPlan::Project(project) => {
+            if project.exprs.iter().any(|expr| expr.contains_subquery()) {
+                return None;
+            }
+            if is_in {
+                compared = Some(match project.exprs.first()? {
+                    Expr::Alias(alias) => alias.expr.as_ref().clone(),
+                    expr => expr.clone(),
+                });
+            }
+            plan = project.input.as_ref();
+        }
+        _ if is_in => return None,
+        _ => {}
+    }
+
+    let mut correlated = vec![];
+    let mut uncorrelated = vec![];
+    while let LogicalPlan::Filter(filter) = plan {
+        for conjunct in split_conjunction(filter.predicate.clone()) {
+            if expr_has_outer_references(&conjunct) {
+                correlated.push(conjunct);
+            } else {
+                uncorrelated.push(conjunct);
+            }
+        }
+        plan = filter.input.as_ref();
+    }
+    if plan_has_outer_references(plan) {
+        return None;
+    }
+
+    let plan = match conjunction(uncorrelated) {
+        Some(predicate) => LogicalPlan::Filter(Filter {
+            predicate,
+            input: Arc::new(plan.clone()),
+        }),
+        None => plan.clone(),
+    };
+    Some((plan, compared, correlated))
+}
+
+fn qualify_column(column: ColumnExpr, schema: &Schema) -> ColumnExpr {
+    match schema.column_with_name(column.relation.as_ref(), &column.name) {
+        Ok(col) => ColumnExpr {
+            relation: col.relation.clone().or(column.relation),
+            name: column.name,
+        },
+        Err(_) => column,
+    }
+}
+
+fn resolves_uniquely(column: &ColumnExpr, schema: &Schema) -> bool {
+    let matches = schema.columns.iter().filter(|col| {
+        col.name == column.name
+            && column.relation.as_ref().is_none_or(|relation| {
+                col.relation
+                    .as_ref()
+                    .is_some_and(|col_relation| relation.resolved_eq(col_relation))
+            })
+    });
+    matches.count() == 1
+}
+
+pub(crate) fn split_conjunction(expr: Expr) -> Vec<Expr> {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: BinaryOp::And,
+            right,
+        }) => {
+            let mut exprs = split_conjunction(*left);
+            exprs.extend(split_conjunction(*right));
+            exprs
+        }
+        expr => vec![expr],
+    }
+}
+
+pub(crate) fn conjunction(exprs: Vec<Expr>) -> Option<Expr> {
+    exprs.into_iter().reduce(|left, right| {
+        Expr::BinaryExpr(BinaryExpr {
+            left: Box::new(left),
+            op: BinaryOp::And,
+            right: Box::new(right),
+        })
+    })
+}
+
+/// Subqueries are only planned in WHERE and the select list.
+pub(crate) fn check_no_subquery(expr: &Expr, clause: &str) -> BustubxResult<()> {
+    if expr.contains_subquery() {
+        return Err(BustubxError::NotSupport(format!(
+            "subquery in {} not supported",
+            clause
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::planner::logical_plan::{JoinType, LogicalPlan};
+    use crate::Database;
+
+    fn contains_plan(plan: &LogicalPlan, predicate: &dyn Fn(&LogicalPlan) -> bool) -> bool {
+        predicate(plan)
+            || plan
+                .inputs()
+                .into_iter()
+                .any(|input| contains_plan(input, predicate))
+    }
+
+    #[test]
+    fn decorrelate_subqueries() {
+        let mut db = Database::new_temp().unwrap();
+        db.run("create table t1 (a int, b int)").unwrap();
+        db.run("create table t2 (c int, d int)").unwrap();
+
+        let is_join = |join_type: JoinType| move |plan: &LogicalPlan| matches!(plan, LogicalPlan::Join(join) if join.join_type == join_type);
+        let is_apply = |plan: &LogicalPlan| matches!(plan, LogicalPlan::Apply(_));
+
+        let plan = db
+            .create_logical_plan("select a from t1 where exists (select * from t2 where c = a)")
+            .unwrap();
+        assert!(contains_plan(&plan, &is_join(JoinType::LeftSemi)));
+        assert!(!contains_plan(&plan, &is_apply));
+
+        let plan = db
+            .create_logical_plan("select a from t1 where b not in (select d from t2 where c = a)")
+            .unwrap();
+        assert!(contains_plan(&plan, &is_join(JoinType::LeftAnti)));
+        assert!(!contains_plan(&plan, &is_apply));
+
+        // a subquery below OR can not be turned into a join
+        let plan = db
+            .create_logical_plan(
+                "select a from t1 where a > 1 or exists (select * from t2 where c = a)",
+            )
+            .unwrap();
+        assert!(contains_plan(&plan, &is_apply));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::expression::{ColumnExpr, Expr};
+use crate::planner::logical_plan::{LogicalPlan, Update, RID_COLUMN_NAME};
+use crate::{BustubxError, BustubxResult};
+use std::sync::Arc;
+
+use super::plan_aggregate::check_no_aggregate;
+use super::LogicalPlanner;
+
+impl<'a> LogicalPlanner<'a> {
+    pub fn plan_update(
+        &self,
+        table_with_joins: &sqlparser::ast::TableWithJoins,
+        assignments: &[sqlparser::ast::Assignment],
+        selection: &Option<sqlparser::ast::Expr>,
+    ) -> BustubxResult<LogicalPlan> {
+        let table_name = match &table_with_joins.relation {
+            sqlparser::ast::TableFactor::Table { name, .. }
+                if table_with_joins.joins.is_empty() =>
+            {
+                name
+            }
+            _ => {
+                return Err(BustubxError::NotSupport(format!(
+                    "UPDATE of {} not supported",
+                    table_with_joins
+                )))
+            }
+        };
+        let table = self.bind_table_name(table_name)?;
+        let table_schema = self
+            .context
+            .catalog
+            .get_table_by_name(table.table())
+            .map_or(
+                Err(BustubxError::Plan(format!("table {} not found", table))),
+                |info| Ok(info.schema.clone()),
+            )?;
+
+        let scan = self.plan_rid_scan(&table, &table_schema);
+        let input = self.plan_selection(scan, selection)?;
+
+        // SET expressions see the old row
+        let mut new_values: Vec<Option<Expr>> = vec![None; table_schema.column_count()];
+        for assignment in assignments {
+            let name = match assignment.id.as_slice() {
+                [column] => &column.value,
+                _ => {
+                    return Err(BustubxError::NotSupport(format!(
+                        "UPDATE assignment to {} not supported",
+                        assignment
+                    )))
+                }
+            };
+            let index = table_schema.index_of(None, name).map_err(|_| {
+                BustubxError::Plan(format!(
+                    "column \"{}\" of relation \"{}\" does not exist",
+                    name, table
+                ))
+            })?;
+            if new_values[index].is_some() {
+                return Err(BustubxError::Plan(format!(
+                    "multiple assignments to same column \"{}\"",
+                    name
+                )));
+            }
+            let value = self.bind_expr(&assignment.value, input.schema())?;
+            let value = self.coerce_expr_to(
+                value,
+                input.schema(),
+                table_schema.column_with_index(index)?.data_type,
+            )?;
+            check_no_aggregate(&value, "UPDATE")?;
+            new_values[index] = Some(value);
+        }
+
+        let old_values = table_schema
+            .columns
+            .iter()
+            .map(|col| {
+                Expr::Column(ColumnExpr {
+                    relation: Some(table.clone()),
+                    name: col.name.clone(),
+                })
+            })
+            .collect::<Vec<Expr>>();
+        let new_values = new_values
+            .into_iter()
+            .zip(old_values.iter())
+            .map(|(new_value, old_value)| new_value.unwrap_or_else(|| old_value.clone()))
+            .collect::<Vec<Expr>>();
+        let rid = Expr::Column(ColumnExpr {
+            relation: Some(table.clone()),
+            name: RID_COLUMN_NAME.to_string(),
+        });
+        let input = self.plan_project(input, [old_values, new_values, vec![rid]].concat())?;
+
+        Ok(LogicalPlan::Update(Update {
+            table,
+            table_schema,
+            input: Arc::new(input),
+        }))
+    }
+}
@@ -3,4 +3,4 @@ mod logical_planner;
 mod physical_planner;
 
 pub use logical_planner::{LogicalPlanner, PlannerContext};
-pub use physical_planner::PhysicalPlanner;
+pub use physical_planner::{build_plan, PhysicalPlanner};
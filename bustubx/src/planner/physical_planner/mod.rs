@@ -1,3 +1,3 @@
 mod physical_planner;
 
-pub use physical_planner::PhysicalPlanner;
+pub use physical_planner::{build_plan, PhysicalPlanner};
@@ -2,11 +2,19 @@ use crate::catalog::Schema;
 use std::sync::Arc;
 
 use crate::planner::logical_plan::{
-    CreateIndex, CreateTable, EmptyRelation, Filter, Insert, Join, Limit, LogicalPlan, Project,
-    Sort, TableScan, Values,
+    Aggregate, AlterTable, Apply, CreateIndex, CreateTable, Delete, Distinct, DropIndex, DropTable,
+    EmptyRelation, Filter, Insert, Join, Limit, LogicalPlan, Project, SetOperation, Sort,
+    TableScan, Update, Values,
 };
 
+use crate::execution::physical_plan::PhysicalAggregate;
+use crate::execution::physical_plan::PhysicalAlterTable;
+use crate::execution::physical_plan::PhysicalApply;
 use crate::execution::physical_plan::PhysicalCreateTable;
+use crate::execution::physical_plan::PhysicalDelete;
+use crate::execution::physical_plan::PhysicalDistinct;
+use crate::execution::physical_plan::PhysicalDropIndex;
+use crate::execution::physical_plan::PhysicalDropTable;
 use crate::execution::physical_plan::PhysicalFilter;
 use crate::execution::physical_plan::PhysicalInsert;
 use crate::execution::physical_plan::PhysicalLimit;
@@ -14,7 +22,9 @@ use crate::execution::physical_plan::PhysicalNestedLoopJoin;
 use crate::execution::physical_plan::PhysicalPlan;
 use crate::execution::physical_plan::PhysicalProject;
 use crate::execution::physical_plan::PhysicalSeqScan;
+use crate::execution::physical_plan::PhysicalSetOperation;
 use crate::execution::physical_plan::PhysicalSort;
+use crate::execution::physical_plan::PhysicalUpdate;
 use crate::execution::physical_plan::PhysicalValues;
 use crate::execution::physical_plan::{PhysicalCreateIndex, PhysicalEmpty};
 
@@ -34,6 +44,55 @@ impl PhysicalPlanner {
 pub fn build_plan(logical_plan: Arc<LogicalPlan>) -> PhysicalPlan {
     // select走的哪个逻辑呀？
     let plan = match logical_plan.as_ref() {
+        LogicalPlan::Aggregate(Aggregate {
+            input,
+            group_exprs,
+            aggr_exprs,
+            schema,
+        }) => {
+            let input_physical_plan = build_plan(input.clone());
+            PhysicalPlan::Aggregate(PhysicalAggregate::new(
+                Arc::new(input_physical_plan),
+                group_exprs.clone(),
+                aggr_exprs.clone(),
+                schema.clone(),
+            ))
+        }
+        LogicalPlan::Apply(Apply {
+            input,
+            subquery,
+            schema,
+        }) => {
+            let input_physical_plan = build_plan(input.clone());
+            PhysicalPlan::Apply(PhysicalApply::new(
+                Arc::new(input_physical_plan),
+                subquery.clone(),
+                schema.clone(),
+            ))
+        }
+        LogicalPlan::Delete(Delete { table, input, .. }) => {
+            let input_physical_plan = build_plan(input.clone());
+            PhysicalPlan::Delete(PhysicalDelete::new(
+                table.clone(),
+                Arc::new(input_physical_plan),
+            ))
+        }
+        LogicalPlan::Update(Update {
+            table,
+            table_schema,
+            input,
+        }) => {
+            let input_physical_plan = build_plan(input.clone());
+            PhysicalPlan::Update(PhysicalUpdate::new(
+                table.clone(),
+                table_schema.clone(),
+                Arc::new(input_physical_plan),
+            ))
+        }
+        LogicalPlan::Distinct(Distinct { input }) => {
+            let input_physical_plan = build_plan(input.clone());
+            PhysicalPlan::Distinct(PhysicalDistinct::new(Arc::new(input_physical_plan)))
+        }
         LogicalPlan::CreateTable(CreateTable { name, columns }) => PhysicalPlan::CreateTable(
             PhysicalCreateTable::new(name.clone(), Schema::new(columns.clone())),
         ),
@@ -48,17 +107,25 @@ pub fn build_plan(logical_plan: Arc<LogicalPlan>) -> PhysicalPlan {
             table_schema.clone(),
             columns.clone(),
         )),
+        LogicalPlan::DropTable(DropTable { tables, if_exists }) => {
+            PhysicalPlan::DropTable(PhysicalDropTable::new(tables.clone(), *if_exists))
+        }
+        LogicalPlan::DropIndex(DropIndex { indexes, if_exists }) => {
+            PhysicalPlan::DropIndex(PhysicalDropIndex::new(indexes.clone(), *if_exists))
+        }
+        LogicalPlan::AlterTable(AlterTable { table, operation }) => {
+            PhysicalPlan::AlterTable(PhysicalAlterTable::new(table.clone(), operation.clone()))
+        }
         LogicalPlan::Insert(Insert {
             table,
             table_schema,
-            projected_schema,
             input,
+            ..
         }) => {
             let input_physical_plan = build_plan(input.clone());
             PhysicalPlan::Insert(PhysicalInsert::new(
                 table.clone(),
                 table_schema.clone(),
-                projected_schema.clone(),
                 Arc::new(input_physical_plan),
             ))
         }
@@ -90,9 +157,11 @@ pub fn build_plan(logical_plan: Arc<LogicalPlan>) -> PhysicalPlan {
             table_schema,
             filters,
             limit,
+            with_rid,
         }) => PhysicalPlan::TableScan(PhysicalSeqScan::new(
             table_ref.clone(),
             table_schema.clone(),
+            *with_rid,
         )),
         LogicalPlan::Limit(Limit {
             limit,
@@ -134,6 +203,23 @@ pub fn build_plan(logical_plan: Arc<LogicalPlan>) -> PhysicalPlan {
                 Arc::new(input_physical_plan),
             ))
         }
+        LogicalPlan::SetOperation(SetOperation {
+            op,
+            all,
+            left,
+            right,
+            schema,
+        }) => {
+            let left_physical_plan = build_plan(left.clone());
+            let right_physical_plan = build_plan(right.clone());
+            PhysicalPlan::SetOperation(PhysicalSetOperation::new(
+                *op,
+                *all,
+                Arc::new(left_physical_plan),
+                Arc::new(right_physical_plan),
+                schema.clone(),
+            ))
+        }
         LogicalPlan::EmptyRelation(EmptyRelation {
             produce_one_row,
             schema,
@@ -1,3 +1,4 @@
+use crate::common::temporal::IntervalValue;
 use crate::storage::codec::DecodedData;
 use crate::{BustubxError, BustubxResult};
 
@@ -152,10 +153,102 @@ impl CommonCodec {
         ];
         Ok((i64::from_be_bytes(data), 8))
     }
+
+    pub fn encode_i128(data: i128) -> Vec<u8> {
+        data.to_be_bytes().to_vec()
+    }
+
+    pub fn decode_i128(bytes: &[u8]) -> BustubxResult<DecodedData<i128>> {
+        if bytes.len() < 16 {
+            return Err(BustubxError::Storage(format!(
+                "bytes length {} is less than {}",
+                bytes.len(),
+                16
+            )));
+        }
+        let mut data = [0u8; 16];
+        data.copy_from_slice(&bytes[..16]);
+        Ok((i128::from_be_bytes(data), 16))
+    }
+
+    pub fn encode_f32(data: f32) -> Vec<u8> {
+        data.to_be_bytes().to_vec()
+    }
+
+    pub fn decode_f32(bytes: &[u8]) -> BustubxResult<DecodedData<f32>> {
+        if bytes.len() < 4 {
+            return Err(BustubxError::Storage(format!(
+                "bytes length {} is less than {}",
+                bytes.len(),
+                4
+            )));
+        }
+        let data = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        Ok((f32::from_be_bytes(data), 4))
+    }
+
+    pub fn encode_f64(data: f64) -> Vec<u8> {
+        data.to_be_bytes().to_vec()
+    }
+
+    pub fn decode_f64(bytes: &[u8]) -> BustubxResult<DecodedData<f64>> {
+        if bytes.len() < 8 {
+            return Err(BustubxError::Storage(format!(
+                "bytes length {} is less than {}",
+                bytes.len(),
+                8
+            )));
+        }
+        let data = [
+            bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+        ];
+        Ok((f64::from_be_bytes(data), 8))
+    }
+
+    // months + days + micros
+    pub fn encode_interval(data: IntervalValue) -> Vec<u8> {
+        let mut bytes = Self::encode_i32(data.months);
+        bytes.extend(Self::encode_i32(data.days));
+        bytes.extend(Self::encode_i64(data.micros));
+        bytes
+    }
+
+    pub fn decode_interval(bytes: &[u8]) -> BustubxResult<DecodedData<IntervalValue>> {
+        let (months, offset1) = Self::decode_i32(bytes)?;
+        let (days, offset2) = Self::decode_i32(&bytes[offset1..])?;
+        let (micros, offset3) = Self::decode_i64(&bytes[offset1 + offset2..])?;
+        Ok((
+            IntervalValue::new(months, days, micros),
+            offset1 + offset2 + offset3,
+        ))
+    }
+
+    // u32 length prefix + utf8 bytes
+    pub fn encode_string(data: &str) -> Vec<u8> {
+        let mut bytes = Self::encode_u32(data.len() as u32);
+        bytes.extend(data.as_bytes());
+        bytes
+    }
+
+    pub fn decode_string(bytes: &[u8]) -> BustubxResult<DecodedData<String>> {
+        let (len, offset) = Self::decode_u32(bytes)?;
+        let total = offset + len as usize;
+        if bytes.len() < total {
+            return Err(BustubxError::Storage(format!(
+                "bytes length {} is less than {}",
+                bytes.len(),
+                total
+            )));
+        }
+        let data = String::from_utf8(bytes[offset..total].to_vec())
+            .map_err(|e| BustubxError::Storage(format!("Invalid utf8 string: {}", e)))?;
+        Ok((data, total))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::common::temporal::IntervalValue;
     use crate::storage::codec::CommonCodec;
 
     #[test]
@@ -221,5 +314,47 @@ mod tests {
                 .unwrap()
                 .0
         );
+        assert_eq!(
+            -5i128,
+            CommonCodec::decode_i128(&CommonCodec::encode_i128(-5i128))
+                .unwrap()
+                .0
+        );
+        assert_eq!(
+            1.5f32,
+            CommonCodec::decode_f32(&CommonCodec::encode_f32(1.5f32))
+                .unwrap()
+                .0
+        );
+        assert_eq!(
+            -2.25f64,
+            CommonCodec::decode_f64(&CommonCodec::encode_f64(-2.25f64))
+                .unwrap()
+                .0
+        );
+        assert!(CommonCodec::decode_f64(&CommonCodec::encode_f64(f64::NAN))
+            .unwrap()
+            .0
+            .is_nan());
+        assert_eq!(
+            IntervalValue::new(-1, 2, 3),
+            CommonCodec::decode_interval(&CommonCodec::encode_interval(IntervalValue::new(
+                -1, 2, 3
+            )))
+            .unwrap()
+            .0
+        );
+        assert_eq!(
+            "abc".to_string(),
+            CommonCodec::decode_string(&CommonCodec::encode_string("abc"))
+                .unwrap()
+                .0
+        );
+        assert_eq!(
+            "".to_string(),
+            CommonCodec::decode_string(&CommonCodec::encode_string(""))
+                .unwrap()
+                .0
+        );
     }
 }
@@ -14,18 +14,41 @@ impl ScalarValueCodec {
             ScalarValue::Int32(Some(v)) => CommonCodec::encode_i32(*v),
             ScalarValue::Int64(Some(v)) => CommonCodec::encode_i64(*v),
             ScalarValue::UInt64(Some(v)) => CommonCodec::encode_u64(*v),
+            ScalarValue::Float32(Some(v)) => CommonCodec::encode_f32(*v),
+            ScalarValue::Float64(Some(v)) => CommonCodec::encode_f64(*v),
+            // decimals only take as many bytes as their precision needs
+            ScalarValue::Decimal128(Some(v), precision, _) => match precision {
+                0..=9 => CommonCodec::encode_i32(*v as i32),
+                10..=18 => CommonCodec::encode_i64(*v as i64),
+                _ => CommonCodec::encode_i128(*v),
+            },
+            ScalarValue::Date(Some(v)) => CommonCodec::encode_i32(*v),
+            ScalarValue::Time(Some(v)) => CommonCodec::encode_i64(*v),
+            ScalarValue::Timestamp(Some(v)) => CommonCodec::encode_i64(*v),
+            ScalarValue::Interval(Some(v)) => CommonCodec::encode_interval(*v),
+            ScalarValue::Varchar(Some(v)) => CommonCodec::encode_string(v),
             // null
-            ScalarValue::Boolean(None)
+            ScalarValue::Null
+            | ScalarValue::Boolean(None)
             | ScalarValue::Int8(None)
             | ScalarValue::Int16(None)
             | ScalarValue::Int32(None)
             | ScalarValue::Int64(None)
-            | ScalarValue::UInt64(None) => vec![],
+            | ScalarValue::UInt64(None)
+            | ScalarValue::Float32(None)
+            | ScalarValue::Float64(None)
+            | ScalarValue::Decimal128(None, _, _)
+            | ScalarValue::Date(None)
+            | ScalarValue::Time(None)
+            | ScalarValue::Timestamp(None)
+            | ScalarValue::Interval(None)
+            | ScalarValue::Varchar(None) => vec![],
         }
     }
 
     pub fn decode(bytes: &[u8], data_type: DataType) -> BustubxResult<DecodedData<ScalarValue>> {
         match data_type {
+            DataType::Null => Ok((ScalarValue::Null, 0)),
             DataType::Boolean => {
                 let (value, offset) = CommonCodec::decode_bool(bytes)?;
                 Ok((ScalarValue::Boolean(Some(value)), offset))
@@ -50,6 +73,51 @@ impl ScalarValueCodec {
                 let (value, offset) = CommonCodec::decode_u64(bytes)?;
                 Ok((ScalarValue::UInt64(Some(value)), offset))
             }
+            DataType::Float32 => {
+                let (value, offset) = CommonCodec::decode_f32(bytes)?;
+                Ok((ScalarValue::Float32(Some(value)), offset))
+            }
+            DataType::Float64 => {
+                let (value, offset) = CommonCodec::decode_f64(bytes)?;
+                Ok((ScalarValue::Float64(Some(value)), offset))
+            }
+            DataType::Decimal128(precision, scale) => {
+                let (value, offset) = match precision {
+                    0..=9 => {
+                        let (v, offset) = CommonCodec::decode_i32(bytes)?;
+                        (v as i128, offset)
+                    }
+                    10..=18 => {
+                        let (v, offset) = CommonCodec::decode_i64(bytes)?;
+                        (v as i128, offset)
+                    }
+                    _ => CommonCodec::decode_i128(bytes)?,
+                };
+                Ok((
+                    ScalarValue::Decimal128(Some(value), precision, scale),
+                    offset,
+                ))
+            }
+            DataType::Date => {
+                let (value, offset) = CommonCodec::decode_i32(bytes)?;
+                Ok((ScalarValue::Date(Some(value)), offset))
+            }
+            DataType::Time => {
+                let (value, offset) = CommonCodec::decode_i64(bytes)?;
+                Ok((ScalarValue::Time(Some(value)), offset))
+            }
+            DataType::Timestamp => {
+                let (value, offset) = CommonCodec::decode_i64(bytes)?;
+                Ok((ScalarValue::Timestamp(Some(value)), offset))
+            }
+            DataType::Interval => {
+                let (value, offset) = CommonCodec::decode_interval(bytes)?;
+                Ok((ScalarValue::Interval(Some(value)), offset))
+            }
+            DataType::Varchar(_) => {
+                let (value, offset) = CommonCodec::decode_string(bytes)?;
+                Ok((ScalarValue::Varchar(Some(value)), offset))
+            }
         }
     }
 }
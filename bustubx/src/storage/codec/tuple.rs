@@ -6,6 +6,8 @@ use crate::{BustubxError, BustubxResult, Tuple};
 pub struct TupleCodec;
 
 impl TupleCodec {
+    /// Layout: a null bitmap of `ceil(column_count / 8)` bytes with one bit per column,
+    /// followed by the encoded values of the non-null columns.
     pub fn encode(tuple: &Tuple) -> Vec<u8> {
         // null map
         let mut null_map = DynamicBitmap::new();
@@ -25,7 +27,14 @@ impl TupleCodec {
     pub fn decode(bytes: &[u8], schema: SchemaRef) -> BustubxResult<DecodedData<Tuple>> {
         let mut total_offset = 0;
 
-        let null_map_bytes = (schema.column_count() >> 3) + 1;
+        let null_map_bytes = schema.column_count().div_ceil(8);
+        if bytes.len() < null_map_bytes {
+            return Err(BustubxError::Storage(format!(
+                "bytes length {} is less than null map size {}",
+                bytes.len(),
+                null_map_bytes
+            )));
+        }
         let null_map = DynamicBitmap::from_bytes(&bytes[0..null_map_bytes]);
         total_offset += null_map_bytes;
         let mut bytes = &bytes[null_map_bytes..];
@@ -72,5 +81,58 @@ mod tests {
             .unwrap()
             .0;
         assert_eq!(new_tuple, tuple);
+
+        let schema = Arc::new(Schema::new(vec![
+            Column::new("a".to_string(), DataType::Varchar(None), true),
+            Column::new("b".to_string(), DataType::Int32, true),
+            Column::new("c".to_string(), DataType::Varchar(Some(10)), true),
+        ]));
+        let tuple = Tuple::new(
+            schema.clone(),
+            vec!["hello".into(), 5i32.into(), ScalarValue::Varchar(None)],
+        );
+        let new_tuple = TupleCodec::decode(&TupleCodec::encode(&tuple), schema)
+            .unwrap()
+            .0;
+        assert_eq!(new_tuple, tuple);
+
+        let schema = Arc::new(Schema::new(vec![
+            Column::new("a".to_string(), DataType::Decimal128(5, 2), true),
+            Column::new("b".to_string(), DataType::Decimal128(18, 4), true),
+            Column::new("c".to_string(), DataType::Decimal128(38, 10), true),
+        ]));
+        let tuple = Tuple::new(
+            schema.clone(),
+            vec![
+                ScalarValue::Decimal128(Some(-12345), 5, 2),
+                ScalarValue::Decimal128(None, 18, 4),
+                ScalarValue::Decimal128(Some(i128::MAX / 2), 38, 10),
+            ],
+        );
+        let bytes = TupleCodec::encode(&tuple);
+        // 1 bitmap byte + 4 bytes for DECIMAL(5,2) + 16 bytes for DECIMAL(38,10)
+        assert_eq!(bytes.len(), 21);
+        let new_tuple = TupleCodec::decode(&bytes, schema).unwrap().0;
+        assert_eq!(new_tuple, tuple);
+    }
+
+    #[test]
+    fn tuple_codec_null_map_size() {
+        // 8 and 9 columns need one and two bitmap bytes
+        for column_count in [8, 9] {
+            let schema = Arc::new(Schema::new(
+                (0..column_count)
+                    .map(|i| Column::new(format!("c{i}"), DataType::Int8, true))
+                    .collect(),
+            ));
+            let mut data: Vec<ScalarValue> = (0..column_count).map(|i| (i as i8).into()).collect();
+            data[column_count - 1] = ScalarValue::Int8(None);
+            let tuple = Tuple::new(schema.clone(), data);
+            let bytes = TupleCodec::encode(&tuple);
+            assert_eq!(bytes.len(), column_count.div_ceil(8) + column_count - 1);
+            let (new_tuple, offset) = TupleCodec::decode(&bytes, schema).unwrap();
+            assert_eq!(offset, bytes.len());
+            assert_eq!(new_tuple, tuple);
+        }
     }
 }
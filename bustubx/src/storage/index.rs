@@ -1,5 +1,4 @@
 use std::collections::VecDeque;
-use std::sync::Arc;
 
 use crate::buffer::{PageId, INVALID_PAGE_ID};
 use crate::catalog::SchemaRef;
@@ -167,6 +166,15 @@ impl BPlusTreeIndex {
     }
 
     pub fn delete(&mut self, key: &Tuple) {
+        self.delete_inner(key, None)
+    }
+
+    /// Deletes the entry of `key` pointing at `rid`, leaving other rows with the same key.
+    pub fn delete_entry(&mut self, key: &Tuple, rid: Rid) {
+        self.delete_inner(key, Some(rid))
+    }
+
+    fn delete_inner(&mut self, key: &Tuple, rid: Option<Rid>) {
         if self.is_empty() {
             return;
         }
@@ -179,7 +187,10 @@ impl BPlusTreeIndex {
             .expect("Leaf page can not be fetched");
         let mut leaf_page =
             BPlusTreeLeafPage::from_bytes(&page.data, self.index_metadata.key_schema.clone());
-        leaf_page.delete(key, &self.index_metadata.key_schema);
+        match rid {
+            Some(rid) => leaf_page.delete_kv(key, rid, &self.index_metadata.key_schema),
+            None => leaf_page.delete(key, &self.index_metadata.key_schema),
+        }
 
         let mut curr_page = BPlusTreePage::Leaf(leaf_page);
         let mut curr_page_id = leaf_page_id;
@@ -514,6 +525,28 @@ impl BPlusTreeIndex {
         self.buffer_pool_manager.unpin_page(curr_page_id, true);
     }
 
+    /// Returns all pages of the tree to the disk manager, the index can not be used afterwards.
+    pub fn destroy(mut self) {
+        if self.is_empty() {
+            return;
+        }
+        let mut queue = VecDeque::new();
+        queue.push_back(self.root_page_id);
+        while let Some(page_id) = queue.pop_front() {
+            let page = self
+                .buffer_pool_manager
+                .fetch_page(page_id)
+                .expect("Page can not be fetched");
+            let curr_page =
+                BPlusTreePage::from_bytes(&page.data, self.index_metadata.key_schema.clone());
+            self.buffer_pool_manager.unpin_page(page_id, false);
+            if let BPlusTreePage::Internal(internal_page) = curr_page {
+                queue.extend(internal_page.values());
+            }
+            self.buffer_pool_manager.delete_page(page_id);
+        }
+    }
+
     pub fn scan(&self, key: &Tuple) -> Vec<Rid> {
         unimplemented!()
     }
@@ -3,7 +3,7 @@ use std::mem::size_of;
 use super::Tuple;
 use crate::buffer::{PageId, BUSTUBX_PAGE_SIZE, INVALID_PAGE_ID};
 use crate::catalog::SchemaRef;
-use crate::storage::codec::BPlusTreePageTypeCodec;
+use crate::storage::codec::{BPlusTreePageTypeCodec, TupleCodec};
 use crate::{catalog::Schema, common::rid::Rid};
 
 pub const INTERNAL_PAGE_HEADER_SIZE: usize = 4 + 4 + 4;
@@ -328,15 +328,15 @@ impl BPlusTreeInternalPage {
         let current_size = u32::from_be_bytes(raw[4..8].try_into().unwrap());
         let max_size = u32::from_be_bytes(raw[8..12].try_into().unwrap());
         let mut array = Vec::with_capacity(max_size as usize);
-        let key_size = key_schema.fixed_len();
         let value_size = size_of::<PageId>();
-        let kv_size = key_size + value_size;
-        for i in 0..current_size {
-            let start = 12 + i as usize * kv_size;
-            let end = 12 + (i + 1) as usize * kv_size;
-            let key = Tuple::from_bytes(key_schema.clone(), &raw[start..start + key_size]);
+        // keys may contain variable-length values, so walk the kv pairs one by one
+        let mut start = 12;
+        for _ in 0..current_size {
+            let (key, key_size) = TupleCodec::decode(&raw[start..], key_schema.clone()).unwrap();
+            let end = start + key_size + value_size;
             let page_id = u32::from_be_bytes(raw[start + key_size..end].try_into().unwrap());
             array.push((key, page_id));
+            start = end;
         }
         Self {
             schema: key_schema,
@@ -357,14 +357,13 @@ impl BPlusTreeInternalPage {
         if self.header.current_size == 0 {
             buf[12..BUSTUBX_PAGE_SIZE].fill(0);
         } else {
-            let key_size = self.array[0].0.to_bytes().len();
-            let value_size = size_of::<PageId>();
-            let kv_size = key_size + value_size;
-            for i in 0..self.header.current_size {
-                let start = 12 + i as usize * kv_size;
-                let end = 12 + (i + 1) as usize * kv_size;
-                buf[start..start + key_size].copy_from_slice(&self.array[i as usize].0.to_bytes());
-                buf[start + key_size..end].copy_from_slice(&self.array[i as usize].1.to_be_bytes());
+            let mut start = 12;
+            for (key, page_id) in self.array.iter() {
+                let key_bytes = TupleCodec::encode(key);
+                let end = start + key_bytes.len() + size_of::<PageId>();
+                buf[start..start + key_bytes.len()].copy_from_slice(&key_bytes);
+                buf[start + key_bytes.len()..end].copy_from_slice(&page_id.to_be_bytes());
+                start = end;
             }
         }
         buf
@@ -442,15 +441,15 @@ impl BPlusTreeLeafPage {
         let max_size = u32::from_be_bytes(raw[8..12].try_into().unwrap());
         let next_page_id = u32::from_be_bytes(raw[12..16].try_into().unwrap());
         let mut array = Vec::with_capacity(max_size as usize);
-        let key_size = key_schema.fixed_len();
         let value_size = size_of::<Rid>();
-        let kv_size = key_size + value_size;
-        for i in 0..current_size {
-            let start = 16 + i as usize * kv_size;
-            let end = 16 + (i + 1) as usize * kv_size;
-            let key = Tuple::from_bytes(key_schema.clone(), &raw[start..start + key_size]);
+        // keys may contain variable-length values, so walk the kv pairs one by one
+        let mut start = 16;
+        for _ in 0..current_size {
+            let (key, key_size) = TupleCodec::decode(&raw[start..], key_schema.clone()).unwrap();
+            let end = start + key_size + value_size;
             let rid = Rid::from_bytes(raw[start + key_size..end].try_into().unwrap());
             array.push((key, rid));
+            start = end;
         }
         Self {
             schema: key_schema,
@@ -473,14 +472,13 @@ impl BPlusTreeLeafPage {
         if self.header.current_size == 0 {
             buf[16..BUSTUBX_PAGE_SIZE].fill(0);
         } else {
-            let key_size = self.array[0].0.to_bytes().len();
-            let value_size = size_of::<Rid>();
-            let kv_size = key_size + value_size;
-            for i in 0..self.header.current_size {
-                let start = 16 + i as usize * kv_size;
-                let end = 16 + (i + 1) as usize * kv_size;
-                buf[start..start + key_size].copy_from_slice(&self.array[i as usize].0.to_bytes());
-                buf[start + key_size..end].copy_from_slice(&self.array[i as usize].1.to_bytes());
+            let mut start = 16;
+            for (key, rid) in self.array.iter() {
+                let key_bytes = TupleCodec::encode(key);
+                let end = start + key_bytes.len() + size_of::<Rid>();
+                buf[start..start + key_bytes.len()].copy_from_slice(&key_bytes);
+                buf[start + key_bytes.len()..end].copy_from_slice(&rid.to_bytes());
+                start = end;
             }
         }
         buf
@@ -539,6 +537,17 @@ impl BPlusTreeLeafPage {
         }
     }
 
+    // key可能重复，只删除rid也相同的kv
+    pub fn delete_kv(&mut self, key: &Tuple, rid: Rid, key_schema: &Schema) {
+        let key_index = self.array.iter().position(|(k, r)| {
+            *r == rid && key.compare(k, key_schema) == std::cmp::Ordering::Equal
+        });
+        if let Some(index) = key_index {
+            self.array.remove(index);
+            self.header.current_size -= 1;
+        }
+    }
+
     // 查找key对应的rid
     pub fn look_up(&self, key: &Tuple, key_schema: &Schema) -> Option<Rid> {
         let key_index = self.key_index(key, key_schema);
@@ -636,7 +645,7 @@ mod tests {
         assert_eq!(new_page.header.max_size, 5);
         assert_eq!(
             new_page.array[0].0.data,
-            vec![ScalarValue::Int8(Some(0)), ScalarValue::Int16(Some(0))]
+            vec![ScalarValue::Int8(None), ScalarValue::Int16(None)]
         );
         assert_eq!(new_page.array[0].1, 0);
         assert_eq!(new_page.array[1].0.data, vec![1i8.into(), 1i16.into()]);
@@ -676,6 +685,68 @@ mod tests {
         assert_eq!(new_page.array[1].1, Rid::new(2, 2));
     }
 
+    #[test]
+    pub fn test_leaf_page_varchar_key_from_to_bytes() {
+        let key_schema = Arc::new(Schema::new(vec![
+            Column::new("a".to_string(), DataType::Varchar(None), false),
+            Column::new("b".to_string(), DataType::Int16, false),
+        ]));
+        let mut ori_page = BPlusTreeLeafPage::new(key_schema.clone(), 5);
+        ori_page.insert(
+            Tuple::new(key_schema.clone(), vec!["bob".into(), 1i16.into()]),
+            Rid::new(1, 1),
+            &key_schema,
+        );
+        ori_page.insert(
+            Tuple::new(key_schema.clone(), vec!["alice".into(), 2i16.into()]),
+            Rid::new(2, 2),
+            &key_schema,
+        );
+
+        let bytes = ori_page.to_bytes();
+
+        let new_page = BPlusTreeLeafPage::from_bytes(&bytes, key_schema.clone());
+        assert_eq!(new_page.header.current_size, 2);
+        assert_eq!(new_page.array[0].0.data, vec!["alice".into(), 2i16.into()]);
+        assert_eq!(new_page.array[0].1, Rid::new(2, 2));
+        assert_eq!(new_page.array[1].0.data, vec!["bob".into(), 1i16.into()]);
+        assert_eq!(new_page.array[1].1, Rid::new(1, 1));
+    }
+
+    #[test]
+    pub fn test_leaf_page_null_key_from_to_bytes() {
+        let key_schema = Arc::new(Schema::new(vec![
+            Column::new("a".to_string(), DataType::Int32, true),
+            Column::new("b".to_string(), DataType::Varchar(None), true),
+        ]));
+        let mut ori_page = BPlusTreeLeafPage::new(key_schema.clone(), 5);
+        ori_page.insert(
+            Tuple::new(key_schema.clone(), vec![0i32.into(), "x".into()]),
+            Rid::new(1, 1),
+            &key_schema,
+        );
+        ori_page.insert(
+            Tuple::new(
+                key_schema.clone(),
+                vec![ScalarValue::Int32(None), ScalarValue::Varchar(None)],
+            ),
+            Rid::new(2, 2),
+            &key_schema,
+        );
+
+        let bytes = ori_page.to_bytes();
+
+        // NULL sorts before any value and must not come back as 0
+        let new_page = BPlusTreeLeafPage::from_bytes(&bytes, key_schema.clone());
+        assert_eq!(
+            new_page.array[0].0.data,
+            vec![ScalarValue::Int32(None), ScalarValue::Varchar(None)]
+        );
+        assert_eq!(new_page.array[0].1, Rid::new(2, 2));
+        assert_eq!(new_page.array[1].0.data, vec![0i32.into(), "x".into()]);
+        assert_eq!(new_page.array[1].1, Rid::new(1, 1));
+    }
+
     #[test]
     pub fn test_internal_page_insert() {
         let key_schema = Arc::new(Schema::new(vec![
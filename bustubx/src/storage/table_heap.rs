@@ -2,6 +2,7 @@ use crate::buffer::{PageId, BUSTUBX_PAGE_SIZE, INVALID_PAGE_ID};
 use crate::catalog::SchemaRef;
 use crate::storage::codec::TablePageCodec;
 use crate::{buffer::BufferPoolManager, common::rid::Rid};
+use crate::{BustubxError, BustubxResult};
 
 use super::{
     table_page::TablePage,
@@ -48,8 +49,18 @@ impl TableHeap {
     /// - `tuple`: The tuple to be inserted.
     ///
     /// Returns:
-    /// An `Option` containing the `Rid` of the inserted tuple if successful, otherwise `None`.
-    pub fn insert_tuple(&mut self, meta: &TupleMeta, tuple: &Tuple) -> Option<Rid> {
+    /// The `Rid` of the inserted tuple, or an error if the tuple can not fit in an empty page.
+    pub fn insert_tuple(&mut self, meta: &TupleMeta, tuple: &Tuple) -> BustubxResult<Rid> {
+        // tuples are variable-length, check that this one fits in a page at all
+        if TablePage::new(self.schema.clone(), INVALID_PAGE_ID)
+            .get_next_tuple_offset(meta, tuple)
+            .is_none()
+        {
+            return Err(BustubxError::Storage(
+                "tuple is too large, cannot insert".to_string(),
+            ));
+        }
+
         let mut last_page_id = self.last_page_id;
         let last_page = self
             .buffer_pool_manager
@@ -64,13 +75,6 @@ impl TableHeap {
                 break;
             }
 
-            // if there's no tuple in the page, and we can't insert the tuple,
-            // then this tuple is too large.
-            assert!(
-                last_table_page.header.num_tuples > 0,
-                "tuple is too large, cannot insert"
-            );
-
             // Allocate a new page if no more table pages are available.
             let next_page = self
                 .buffer_pool_manager
@@ -107,7 +111,12 @@ impl TableHeap {
         self.buffer_pool_manager.unpin_page(last_page_id, true);
 
         // Map the slot_id to a Rid and return
-        slot_id.map(|slot_id| Rid::new(last_page_id, slot_id as u32))
+        slot_id
+            .map(|slot_id| Rid::new(last_page_id, slot_id as u32))
+            .ok_or(BustubxError::Storage(format!(
+                "Failed to insert tuple into page {}",
+                last_page_id
+            )))
     }
 
     pub fn update_tuple_meta(&mut self, meta: &TupleMeta, rid: Rid) {
@@ -125,6 +134,47 @@ impl TableHeap {
         self.buffer_pool_manager.unpin_page(rid.page_id, true);
     }
 
+    /// Replaces the tuple at `rid` with a new version.
+    ///
+    /// Tuples are variable-length, so the new version is inserted like by `insert_tuple` and
+    /// the old one is marked deleted. Nothing changes if the new version can not be inserted.
+    ///
+    /// Returns:
+    /// The `Rid` of the new version.
+    pub fn update_tuple(&mut self, rid: Rid, tuple: &Tuple) -> BustubxResult<Rid> {
+        let meta = self.get_tuple_meta(rid);
+        let new_rid = self.insert_tuple(
+            &TupleMeta {
+                is_deleted: false,
+                ..meta
+            },
+            tuple,
+        )?;
+        self.update_tuple_meta(
+            &TupleMeta {
+                is_deleted: true,
+                ..meta
+            },
+            rid,
+        );
+        Ok(new_rid)
+    }
+
+    /// Returns all pages of the table to the disk manager, the table can not be used afterwards.
+    pub fn destroy(mut self) {
+        let mut page_id = self.first_page_id;
+        while page_id != INVALID_PAGE_ID {
+            let page = self
+                .buffer_pool_manager
+                .fetch_page_mut(page_id)
+                .expect("Can not fetch page");
+            let (table_page, _) = TablePageCodec::decode(&page.data, self.schema.clone()).unwrap();
+            self.buffer_pool_manager.unpin_page(page_id, false);
+            self.buffer_pool_manager.delete_page(page_id);
+            page_id = table_page.header.next_page_id;
+        }
+    }
+
     pub fn get_tuple(&mut self, rid: Rid) -> (TupleMeta, Tuple) {
         let page = self
             .buffer_pool_manager
@@ -210,6 +260,12 @@ pub struct TableIterator {
 
 impl TableIterator {
     pub fn next(&mut self, table_heap: &mut TableHeap) -> Option<(TupleMeta, Tuple)> {
+        self.next_with_rid(table_heap)
+            .map(|(_, meta, tuple)| (meta, tuple))
+    }
+
+    /// Like `next`, also returning the id of the tuple, e.g. to update its meta.
+    pub fn next_with_rid(&mut self, table_heap: &mut TableHeap) -> Option<(Rid, TupleMeta, Tuple)> {
         if self.rid.is_none() {
             return None;
         }
@@ -217,9 +273,9 @@ impl TableIterator {
         if self.stop_at.is_some() && rid == self.stop_at.unwrap() {
             return None;
         }
-        let result = table_heap.get_tuple(rid);
+        let (meta, tuple) = table_heap.get_tuple(rid);
         self.rid = table_heap.get_next_rid(rid);
-        Some(result)
+        Some((rid, meta, tuple))
     }
 }
 
@@ -264,24 +320,30 @@ mod tests {
             is_deleted: false,
         };
 
-        table_heap.insert_tuple(
-            &meta,
-            &Tuple::new(schema.clone(), vec![1i8.into(), 1i16.into()]),
-        );
+        table_heap
+            .insert_tuple(
+                &meta,
+                &Tuple::new(schema.clone(), vec![1i8.into(), 1i16.into()]),
+            )
+            .unwrap();
         assert_eq!(table_heap.first_page_id, 0);
         assert_eq!(table_heap.last_page_id, 0);
 
-        table_heap.insert_tuple(
-            &meta,
-            &Tuple::new(schema.clone(), vec![2i8.into(), 2i16.into()]),
-        );
+        table_heap
+            .insert_tuple(
+                &meta,
+                &Tuple::new(schema.clone(), vec![2i8.into(), 2i16.into()]),
+            )
+            .unwrap();
         assert_eq!(table_heap.first_page_id, 0);
         assert_eq!(table_heap.last_page_id, 0);
 
-        table_heap.insert_tuple(
-            &meta,
-            &Tuple::new(schema.clone(), vec![3i8.into(), 3i16.into()]),
-        );
+        table_heap
+            .insert_tuple(
+                &meta,
+                &Tuple::new(schema.clone(), vec![3i8.into(), 3i16.into()]),
+            )
+            .unwrap();
         assert_eq!(table_heap.first_page_id, 0);
         assert_eq!(table_heap.last_page_id, 0);
     }
@@ -335,6 +397,44 @@ mod tests {
         assert_eq!(meta.is_deleted, true);
     }
 
+    #[test]
+    pub fn test_table_heap_update_tuple() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().join("test.db");
+
+        let schema = Arc::new(Schema::new(vec![
+            Column::new("a".to_string(), DataType::Int8, false),
+            Column::new("b".to_string(), DataType::Varchar(None), false),
+        ]));
+        let disk_manager = DiskManager::try_new(&temp_path).unwrap();
+        let buffer_pool_manager = BufferPoolManager::new(1000, Arc::new(disk_manager), 2);
+        let mut table_heap = TableHeap::try_new(schema.clone(), buffer_pool_manager);
+        let meta = super::TupleMeta {
+            insert_txn_id: 0,
+            delete_txn_id: 0,
+            is_deleted: false,
+        };
+
+        let rid = table_heap
+            .insert_tuple(
+                &meta,
+                &Tuple::new(schema.clone(), vec![1i8.into(), "a".into()]),
+            )
+            .unwrap();
+        let new_rid = table_heap
+            .update_tuple(
+                rid,
+                &Tuple::new(schema.clone(), vec![2i8.into(), "longer".into()]),
+            )
+            .unwrap();
+        assert_ne!(rid, new_rid);
+        assert!(table_heap.get_tuple_meta(rid).is_deleted);
+
+        let (meta, tuple) = table_heap.get_tuple(new_rid);
+        assert!(!meta.is_deleted);
+        assert_eq!(tuple.data, vec![2i8.into(), "longer".into()]);
+    }
+
     #[test]
     pub fn test_table_heap_get_tuple() {
         let temp_dir = TempDir::new().unwrap();
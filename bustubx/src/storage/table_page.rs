@@ -58,44 +58,51 @@ impl TablePage {
 
     // Get the offset for the next tuple insertion.
     pub fn get_next_tuple_offset(&self, meta: &TupleMeta, tuple: &Tuple) -> Option<u16> {
+        self.next_tuple_offset(TupleCodec::encode(tuple).len())
+    }
+
+    // Tuples are variable-length, so the offset depends on the encoded tuple size.
+    fn next_tuple_offset(&self, tuple_size: usize) -> Option<u16> {
         // Get the ending offset of the current slot. If there are inserted tuples,
         // get the offset of the previous inserted tuple; otherwise, set it to the size of the page.
         let slot_end_offset = if self.header.num_tuples > 0 {
-            self.header.tuple_infos[self.header.num_tuples as usize - 1].offset
+            self.header.tuple_infos[self.header.num_tuples as usize - 1].offset as usize
         } else {
-            BUSTUBX_PAGE_SIZE as u16
+            BUSTUBX_PAGE_SIZE
         };
 
         // Check if the current slot has enough space for the new tuple. Return None if not.
-        if slot_end_offset < TupleCodec::encode(tuple).len() as u16 {
+        if slot_end_offset < tuple_size {
             return None;
         }
 
         // Calculate the insertion offset for the new tuple by subtracting its data length
         // from the ending offset of the current slot.
-        let tuple_offset = slot_end_offset - TupleCodec::encode(tuple).len() as u16;
+        let tuple_offset = slot_end_offset - tuple_size;
 
         // Calculate the minimum valid tuple insertion offset, including the table page header size,
         // the total size of each tuple info (existing tuple infos and newly added tuple info).
         let min_tuple_offset = TablePageHeaderCodec::encode(&self.header).len()
             + TablePageHeaderTupleInfoCodec::encode(&EMPTY_TUPLE_INFO).len();
-        if (tuple_offset as usize) < min_tuple_offset {
+        if tuple_offset < min_tuple_offset {
             return None;
         }
 
         // Return the calculated insertion offset for the new tuple.
-        return Some(tuple_offset);
+        return Some(tuple_offset as u16);
     }
 
     pub fn insert_tuple(&mut self, meta: &TupleMeta, tuple: &Tuple) -> Option<u16> {
+        let tuple_bytes = TupleCodec::encode(tuple);
+
         // Get the offset for the next tuple insertion.
-        let tuple_offset = self.get_next_tuple_offset(meta, tuple)?;
+        let tuple_offset = self.next_tuple_offset(tuple_bytes.len())?;
         let tuple_id = self.header.num_tuples;
 
         // Store tuple information including offset, length, and metadata.
         self.header.tuple_infos.push(TupleInfo {
             offset: tuple_offset,
-            size: TupleCodec::encode(tuple).len() as u16,
+            size: tuple_bytes.len() as u16,
             meta: meta.clone(),
         });
 
@@ -108,9 +115,8 @@ impl TablePage {
         }
 
         // Copy the tuple's data into the appropriate position within the page's data buffer.
-        self.data[tuple_offset as usize
-            ..(tuple_offset + TupleCodec::encode(tuple).len() as u16) as usize]
-            .copy_from_slice(&TupleCodec::encode(tuple));
+        self.data[tuple_offset as usize..tuple_offset as usize + tuple_bytes.len()]
+            .copy_from_slice(&tuple_bytes);
         return Some(tuple_id);
     }
 
@@ -181,7 +187,10 @@ pub struct TupleInfo {
 
 #[cfg(test)]
 mod tests {
+    use crate::buffer::BUSTUBX_PAGE_SIZE;
     use crate::catalog::{Column, DataType, Schema};
+    use crate::common::ScalarValue;
+    use crate::storage::codec::TablePageCodec;
     use crate::storage::Tuple;
     use std::sync::Arc;
 
@@ -258,4 +267,82 @@ mod tests {
         assert_eq!(tuple_meta.delete_txn_id, 1);
         assert_eq!(tuple_meta.insert_txn_id, 2);
     }
+
+    #[test]
+    pub fn test_table_page_variable_length_tuple() {
+        let schema = Arc::new(Schema::new(vec![
+            Column::new("a".to_string(), DataType::Int32, false),
+            Column::new("b".to_string(), DataType::Varchar(None), false),
+        ]));
+        let mut table_page = super::TablePage::new(schema.clone(), 0);
+        let meta = super::TupleMeta {
+            insert_txn_id: 0,
+            delete_txn_id: 0,
+            is_deleted: false,
+        };
+        table_page.insert_tuple(
+            &meta,
+            &Tuple::new(schema.clone(), vec![1i32.into(), "a".into()]),
+        );
+        table_page.insert_tuple(
+            &meta,
+            &Tuple::new(schema.clone(), vec![2i32.into(), "hello world".into()]),
+        );
+        table_page.insert_tuple(
+            &meta,
+            &Tuple::new(schema.clone(), vec![3i32.into(), "".into()]),
+        );
+
+        let (_, tuple) = table_page.get_tuple(&super::Rid::new(0, 0));
+        assert_eq!(tuple.data, vec![1i32.into(), "a".into()]);
+        let (_, tuple) = table_page.get_tuple(&super::Rid::new(0, 1));
+        assert_eq!(tuple.data, vec![2i32.into(), "hello world".into()]);
+        let (_, tuple) = table_page.get_tuple(&super::Rid::new(0, 2));
+        assert_eq!(tuple.data, vec![3i32.into(), "".into()]);
+
+        // a tuple larger than a page can never be inserted
+        let huge = "x".repeat(BUSTUBX_PAGE_SIZE);
+        assert_eq!(
+            table_page.insert_tuple(
+                &meta,
+                &Tuple::new(schema.clone(), vec![4i32.into(), huge.into()]),
+            ),
+            None
+        );
+    }
+
+    #[test]
+    pub fn test_table_page_null_values() {
+        let schema = Arc::new(Schema::new(vec![
+            Column::new("a".to_string(), DataType::Int32, true),
+            Column::new("b".to_string(), DataType::Varchar(None), true),
+            Column::new("c".to_string(), DataType::Int64, true),
+        ]));
+        let mut table_page = super::TablePage::new(schema.clone(), 0);
+        let meta = super::TupleMeta {
+            insert_txn_id: 0,
+            delete_txn_id: 0,
+            is_deleted: false,
+        };
+        let tuple1 = Tuple::new(
+            schema.clone(),
+            vec![ScalarValue::Int32(None), "a".into(), 0i64.into()],
+        );
+        let tuple2 = Tuple::new(
+            schema.clone(),
+            vec![
+                0i32.into(),
+                ScalarValue::Varchar(None),
+                ScalarValue::Int64(None),
+            ],
+        );
+        table_page.insert_tuple(&meta, &tuple1);
+        table_page.insert_tuple(&meta, &tuple2);
+
+        // NULL must survive writing the page out and reading it back
+        let bytes = TablePageCodec::encode(&table_page);
+        let (table_page, _) = TablePageCodec::decode(&bytes, schema.clone()).unwrap();
+        assert_eq!(table_page.get_tuple(&super::Rid::new(0, 0)).1, tuple1);
+        assert_eq!(table_page.get_tuple(&super::Rid::new(0, 1)).1, tuple2);
+    }
 }